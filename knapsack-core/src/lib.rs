@@ -0,0 +1,566 @@
+//! The 0/1 knapsack recurrence with nothing around it: no printing, no UI,
+//! no allocator beyond `alloc`. Values are generic so callers can use any
+//! ordered, addable number type (`u32`, `u64`, `u128`, a big-integer, …).
+//!
+//! `table[i][w]` is the best benefit using the first `i` items with
+//! capacity `w`; row 0 is the all-zero "no items" baseline.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::Add;
+
+/// A benefit type the recurrence can work with.
+pub trait Value: Clone + Ord + Default + Add<Output = Self> {}
+
+impl<T: Clone + Ord + Default + Add<Output = T>> Value for T {}
+
+// ─── Table ───────────────────────────────────────────────────────────────────
+
+/// Fill `row` (item of `weight` / `benefit`) from the previous row `prev`:
+/// `row[w] = max(prev[w], benefit + prev[w - weight])` where the item fits.
+pub fn fill_row<V: Value>(prev: &[V], row: &mut [V], weight: usize, benefit: &V) {
+    for (w, cell) in row.iter_mut().enumerate() {
+        *cell = if weight > w {
+            prev[w].clone()
+        } else {
+            prev[w].clone().max(benefit.clone() + prev[w - weight].clone())
+        };
+    }
+}
+
+/// The full `(n + 1) × (capacity + 1)` table.
+pub fn table<V: Value>(capacity: usize, weights: &[usize], benefits: &[V]) -> Vec<Vec<V>> {
+    let mut table = vec![vec![V::default(); capacity + 1]; weights.len() + 1];
+    for i in 1..=weights.len() {
+        let (done, rest) = table.split_at_mut(i);
+        fill_row(&done[i - 1], &mut rest[0], weights[i - 1], &benefits[i - 1]);
+    }
+    table
+}
+
+/// Optimal benefit using a single rolling row: `O(capacity)` memory.
+pub fn best_value<V: Value>(capacity: usize, weights: &[usize], benefits: &[V]) -> V {
+    let mut row = vec![V::default(); capacity + 1];
+    for (&w, b) in weights.iter().zip(benefits) {
+        // right-to-left so row[c - w] still holds the previous row's value
+        for c in (w..=capacity).rev() {
+            let with = b.clone() + row[c - w].clone();
+            if with > row[c] {
+                row[c] = with;
+            }
+        }
+    }
+    row[capacity].clone()
+}
+
+// ─── Traceback ───────────────────────────────────────────────────────────────
+
+/// One hop of the traceback: at `(row, col)` item `row` was either taken
+/// (the walk continues at `col - weight`) or left out (it stays in `col`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TraceHop {
+    pub row: usize,
+    pub col: usize,
+    pub taken: bool,
+}
+
+/// Every cell visited when walking the finished table from
+/// `table[n][capacity]` up to row 1, bottom row first.
+pub fn trace_path<V: Value>(table: &[Vec<V>], weights: &[usize], capacity: usize) -> Vec<TraceHop> {
+    let mut path = Vec::with_capacity(table.len().saturating_sub(1));
+    let mut w = capacity;
+    for i in (1..table.len()).rev() {
+        let taken = table[i][w] != table[i - 1][w];
+        path.push(TraceHop { row: i, col: w, taken });
+        if taken {
+            w -= weights[i - 1];
+        }
+    }
+    path
+}
+
+/// 1-based indices of the items in the optimal selection, in ascending order.
+pub fn chosen_items<V: Value>(table: &[Vec<V>], weights: &[usize], capacity: usize) -> Vec<usize> {
+    let mut chosen: Vec<usize> = trace_path(table, weights, capacity)
+        .into_iter()
+        .filter(|hop| hop.taken)
+        .map(|hop| hop.row)
+        .collect();
+    chosen.reverse();
+    chosen
+}
+
+// ─── Breakpoints ─────────────────────────────────────────────────────────────
+
+/// `reachable[c]` is true when some subset of `weights` sums to exactly `c`.
+/// Values in a table row can only change at these capacities; every other
+/// column repeats the one to its left.
+pub fn subset_sums(weights: &[usize], capacity: usize) -> Vec<bool> {
+    let mut reachable = vec![false; capacity + 1];
+    reachable[0] = true;
+    for &w in weights {
+        for c in (w..=capacity).rev() {
+            if reachable[c - w] {
+                reachable[c] = true;
+            }
+        }
+    }
+    reachable
+}
+
+// ─── Reveal order ────────────────────────────────────────────────────────────
+
+/// The order a visualizer fills and reveals the data cells, item rows
+/// `1..=n` × capacity columns `0..n_cols`. Any order is sound as long as
+/// row `i - 1` is done up to column `w` before `(i, w)`.
+#[derive(Clone, Copy, Debug)]
+pub enum StepPlan {
+    /// Item by item, each row left to right (the textbook loop nest).
+    RowMajor,
+    /// Capacity by capacity, each column top to bottom.
+    ColumnMajor,
+    /// Cells in increasing `key(row, col)`, ties row by row.
+    Custom(fn(usize, usize) -> usize),
+}
+
+impl StepPlan {
+    /// A plan revealing cells in increasing `key(row, col)`; `row` is the
+    /// 1-based item. Equal keys keep row-major order between them.
+    pub fn custom(key: fn(usize, usize) -> usize) -> Self {
+        StepPlan::Custom(key)
+    }
+
+    /// The plan laid out over a table with `n` item rows and `n_cols`
+    /// capacity columns. Only custom plans store their cells.
+    pub fn layout(self, n: usize, n_cols: usize) -> RevealOrder {
+        let layout = match self {
+            StepPlan::RowMajor => Layout::RowMajor,
+            StepPlan::ColumnMajor => Layout::ColumnMajor,
+            StepPlan::Custom(key) => {
+                let mut cells: Vec<(usize, usize)> =
+                    (1..=n).flat_map(|row| (0..n_cols).map(move |col| (row, col))).collect();
+                // stable, so ties stay row-major
+                cells.sort_by_key(|&(row, col)| key(row, col));
+                let mut ordinals = vec![0; n * n_cols];
+                for (ordinal, &(row, col)) in cells.iter().enumerate() {
+                    ordinals[(row - 1) * n_cols + col] = ordinal;
+                }
+                Layout::Ranked { cells, ordinals }
+            }
+        };
+        RevealOrder { n, n_cols, layout }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Layout {
+    RowMajor,
+    ColumnMajor,
+    Ranked { cells: Vec<(usize, usize)>, ordinals: Vec<usize> },
+}
+
+/// A [`StepPlan`] for one table size: maps each data cell to its 0-based
+/// position in the reveal and back.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RevealOrder {
+    n: usize,
+    n_cols: usize,
+    layout: Layout,
+}
+
+impl RevealOrder {
+    /// Number of data cells revealed.
+    pub fn len(&self) -> usize {
+        self.n * self.n_cols
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 0-based position of data cell `(row, col)`, `row` 1-based.
+    pub fn ordinal(&self, row: usize, col: usize) -> usize {
+        match &self.layout {
+            Layout::RowMajor => (row - 1) * self.n_cols + col,
+            Layout::ColumnMajor => col * self.n + (row - 1),
+            Layout::Ranked { ordinals, .. } => ordinals[(row - 1) * self.n_cols + col],
+        }
+    }
+
+    /// Inverse of [`RevealOrder::ordinal`].
+    pub fn cell(&self, ordinal: usize) -> (usize, usize) {
+        match &self.layout {
+            Layout::RowMajor => (ordinal / self.n_cols + 1, ordinal % self.n_cols),
+            Layout::ColumnMajor => (ordinal % self.n + 1, ordinal / self.n),
+            Layout::Ranked { cells, .. } => cells[ordinal],
+        }
+    }
+
+    /// The first cell, in reveal order, shown before a cell of the row above
+    /// it could read: some `(i - 1, c)` with `c <= w` comes after `(i, w)`.
+    pub fn first_violation(&self) -> Option<(usize, usize)> {
+        let mut late = None::<(usize, (usize, usize))>;
+        for row in 2..=self.n {
+            // latest reveal among (row - 1, 0..=col)
+            let mut needed = 0;
+            for col in 0..self.n_cols {
+                needed = needed.max(self.ordinal(row - 1, col));
+                let ordinal = self.ordinal(row, col);
+                if ordinal < needed && late.is_none_or(|(o, _)| ordinal < o) {
+                    late = Some((ordinal, (row, col)));
+                }
+            }
+        }
+        late.map(|(_, cell)| cell)
+    }
+}
+
+// ─── Simulation ──────────────────────────────────────────────────────────────
+
+/// One request to a [`Simulation`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SimEvent {
+    /// Reveal the next cell; after the last one the fill completes and the
+    /// traceback starts. A complete table starts over at its first cell.
+    Forward,
+    /// Reveal up to `n` more cells at once, as one change; from the last
+    /// cell it completes like [`SimEvent::Forward`]. For fast playback.
+    Advance(usize),
+    /// Hide the cell revealed last, keeping the first; a complete table
+    /// goes back to its last cell.
+    Back,
+    /// Reveal every cell and show the whole path.
+    Finish,
+    /// Show exactly the first `n` cells, at least one.
+    Seek(usize),
+    /// Walk one more traceback hop.
+    TraceForward,
+    /// Show exactly the first `k` traceback hops.
+    TraceSeek(usize),
+    /// Show the whole traceback path.
+    TraceAll,
+}
+
+/// Stepping through a finished table without any UI: which cells are
+/// revealed, how far the traceback has walked and which cell is active. A
+/// visualizer only draws one of these; tests and embedders drive it with
+/// [`SimEvent`]s directly.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Simulation {
+    order: RevealOrder,
+    path: Vec<TraceHop>,
+    /// Data cells revealed; `None` once the fill is complete.
+    revealed: Option<usize>,
+    /// Traceback hops walked once complete; `None` shows the whole path.
+    traced: Option<usize>,
+}
+
+/// How far a [`Simulation`] is through its two phases.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Progress {
+    pub filled: usize,
+    pub cells: usize,
+    pub walked: usize,
+    pub hops: usize,
+}
+
+impl Default for Simulation {
+    fn default() -> Self {
+        Simulation::new::<usize>(&[], &[], StepPlan::RowMajor)
+    }
+}
+
+impl Simulation {
+    /// A simulation of `table` with no cell revealed yet.
+    pub fn new<V: Value>(table: &[Vec<V>], weights: &[usize], plan: StepPlan) -> Self {
+        let n_cols = table.first().map_or(0, Vec::len);
+        Simulation {
+            order: plan.layout(table.len().saturating_sub(1), n_cols),
+            path: trace_path(table, weights, n_cols.saturating_sub(1)),
+            revealed: Some(0),
+            traced: None,
+        }
+    }
+
+    /// Lay the cells out in `plan` instead, keeping how many are revealed.
+    pub fn set_plan(&mut self, plan: StepPlan) {
+        self.order = plan.layout(self.order.n, self.order.n_cols);
+    }
+
+    /// Apply `event`; false when it changed nothing (e.g. the traceback is
+    /// already at its end).
+    pub fn apply(&mut self, event: SimEvent) -> bool {
+        let before = (self.revealed, self.traced);
+        let len = self.order.len();
+        match (event, self.revealed) {
+            (SimEvent::Forward, None) => (self.revealed, self.traced) = (Some(1.min(len)), None),
+            (SimEvent::Forward, Some(r)) if r >= len => (self.revealed, self.traced) = (None, Some(0)),
+            (SimEvent::Forward, Some(r)) => self.revealed = Some(r + 1),
+            (SimEvent::Advance(0), _) => {}
+            (SimEvent::Advance(_), None) => (self.revealed, self.traced) = (Some(1.min(len)), None),
+            (SimEvent::Advance(_), Some(r)) if r >= len => (self.revealed, self.traced) = (None, Some(0)),
+            (SimEvent::Advance(k), Some(r)) => self.revealed = Some(r.saturating_add(k).min(len)),
+            (SimEvent::Back, None) => self.revealed = Some(len),
+            (SimEvent::Back, Some(r)) => self.revealed = Some(r.saturating_sub(1).max(1).min(len)),
+            (SimEvent::Finish, _) => (self.revealed, self.traced) = (None, None),
+            (SimEvent::Seek(n), _) => (self.revealed, self.traced) = (Some(n.clamp(1.min(len), len)), None),
+            (SimEvent::TraceForward, None) => match self.traced {
+                Some(k) if k < self.path.len() => self.traced = Some(k + 1),
+                _ => {}
+            },
+            (SimEvent::TraceSeek(k), None) => self.traced = Some(k.min(self.path.len())),
+            (SimEvent::TraceAll, None) => self.traced = None,
+            // the traceback waits for the fill
+            (SimEvent::TraceForward | SimEvent::TraceSeek(_) | SimEvent::TraceAll, Some(_)) => {}
+        }
+        (self.revealed, self.traced) != before
+    }
+
+    pub fn order(&self) -> &RevealOrder {
+        &self.order
+    }
+
+    /// Data cells revealed; `None` once the fill is complete.
+    pub fn revealed(&self) -> Option<usize> {
+        self.revealed
+    }
+
+    /// Traceback hops walked; `None` shows the whole path.
+    pub fn traced(&self) -> Option<usize> {
+        self.traced
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.revealed.is_none()
+    }
+
+    /// `(row, col)` of the cell revealed last, while stepping.
+    pub fn active_cell(&self) -> Option<(usize, usize)> {
+        let r = self.revealed?.checked_sub(1)?;
+        (r < self.order.len()).then(|| self.order.cell(r))
+    }
+
+    /// Whether `dp[row][col]` is on screen; row 0 always is.
+    pub fn is_revealed(&self, row: usize, col: usize) -> bool {
+        match self.revealed {
+            _ if row == 0 => true,
+            None => true,
+            Some(r) => self.order.ordinal(row, col) < r,
+        }
+    }
+
+    /// The traceback path, once the fill is complete.
+    pub fn path(&self) -> &[TraceHop] {
+        if self.is_complete() { &self.path } else { &[] }
+    }
+
+    /// The hops walked so far, the whole path when it is all shown.
+    pub fn walked(&self) -> &[TraceHop] {
+        let path = self.path();
+        &path[..self.traced.unwrap_or(path.len()).min(path.len())]
+    }
+
+    pub fn progress(&self) -> Progress {
+        let cells = self.order.len();
+        let hops = self.path().len();
+        Progress {
+            filled: self.revealed.unwrap_or(cells),
+            cells,
+            walked: self.walked().len(),
+            hops,
+        }
+    }
+}
+
+// ─── Playback pace ───────────────────────────────────────────────────────────
+
+/// Turns elapsed time into whole steps at a steady rate, carrying the
+/// fraction over, so playback keeps its speed at any frame rate: at 60 fps
+/// and 300 steps a second each frame takes 5 steps in one go.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Pacer {
+    per_second: f64,
+    carry: f64,
+}
+
+impl Pacer {
+    /// Longest gap counted, so a paused tab doesn't come back with a jump.
+    pub const MAX_GAP_MS: f64 = 250.0;
+
+    pub fn new(per_second: f64) -> Self {
+        Pacer { per_second: per_second.max(0.0), carry: 0.0 }
+    }
+
+    /// Steps due after another `elapsed_ms`.
+    pub fn advance(&mut self, elapsed_ms: f64) -> usize {
+        self.carry += elapsed_ms.clamp(0.0, Self::MAX_GAP_MS) * self.per_second / 1000.0;
+        let steps = self.carry as usize;
+        self.carry -= steps as f64;
+        steps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ── Reveal order ──
+
+    #[test]
+    fn built_in_orders_map_cells_and_ordinals_both_ways() {
+        for plan in [StepPlan::RowMajor, StepPlan::ColumnMajor, StepPlan::custom(|row, col| row + col)] {
+            let order = plan.layout(3, 6);
+            assert_eq!(order.len(), 18);
+            for ordinal in 0..order.len() {
+                let (row, col) = order.cell(ordinal);
+                assert_eq!(order.ordinal(row, col), ordinal);
+            }
+        }
+        assert_eq!(StepPlan::RowMajor.layout(3, 6).cell(7), (2, 1));
+        assert_eq!(StepPlan::ColumnMajor.layout(3, 6).cell(7), (2, 2));
+    }
+
+    #[test]
+    fn custom_ties_keep_row_major_order() {
+        let order = StepPlan::custom(|_, _| 0).layout(3, 4);
+        let row_major = StepPlan::RowMajor.layout(3, 4);
+        for ordinal in 0..order.len() {
+            assert_eq!(order.cell(ordinal), row_major.cell(ordinal));
+        }
+    }
+
+    #[test]
+    fn sound_orders_have_no_violation() {
+        assert_eq!(StepPlan::RowMajor.layout(4, 5).first_violation(), None);
+        assert_eq!(StepPlan::ColumnMajor.layout(4, 5).first_violation(), None);
+        // anti-diagonals: (i - 1, c) with c <= w always has a smaller key than (i, w)
+        assert_eq!(StepPlan::custom(|row, col| row + col).layout(4, 5).first_violation(), None);
+    }
+
+    #[test]
+    fn bottom_row_first_violates_at_its_first_cell() {
+        let order = StepPlan::custom(|row, _| 10 - row).layout(3, 4);
+        assert_eq!(order.first_violation(), Some((3, 0)));
+        // revealing (2, 3) before (1, 3) is caught even when the rest is sound
+        let late = StepPlan::custom(|row, col| if (row, col) == (1, 3) { 100 } else { row * 10 + col }).layout(2, 4);
+        assert_eq!(late.first_violation(), Some((2, 3)));
+    }
+
+    // ── Simulation ──
+
+    /// Items (2, 3), (3, 4), (4, 5) with capacity 5: 3 × 6 data cells, and
+    /// the optimum 7 takes items 1 and 2.
+    fn simulation() -> Simulation {
+        let weights = [2, 3, 4];
+        Simulation::new(&table(5, &weights, &[3u32, 4, 5]), &weights, StepPlan::RowMajor)
+    }
+
+    #[test]
+    fn starts_with_only_the_base_row_shown() {
+        let sim = simulation();
+        assert_eq!(sim.revealed(), Some(0));
+        assert_eq!(sim.active_cell(), None);
+        assert!(sim.is_revealed(0, 5));
+        assert!(!sim.is_revealed(1, 0));
+        assert!(sim.path().is_empty());
+    }
+
+    #[test]
+    fn forward_and_back_step_one_cell_keeping_the_first() {
+        let mut sim = simulation();
+        assert!(sim.apply(SimEvent::Forward));
+        assert_eq!(sim.active_cell(), Some((1, 0)));
+        assert!(sim.apply(SimEvent::Forward));
+        assert_eq!(sim.active_cell(), Some((1, 1)));
+        assert!(sim.apply(SimEvent::Back));
+        assert_eq!(sim.revealed(), Some(1));
+        assert!(!sim.apply(SimEvent::Back));
+        assert!(sim.is_revealed(1, 0) && !sim.is_revealed(1, 1));
+    }
+
+    #[test]
+    fn seek_clamps_to_at_least_one_cell_and_at_most_all() {
+        let mut sim = simulation();
+        sim.apply(SimEvent::Seek(8));
+        assert_eq!(sim.active_cell(), Some((2, 1)));
+        sim.apply(SimEvent::Seek(0));
+        assert_eq!(sim.revealed(), Some(1));
+        sim.apply(SimEvent::Seek(100));
+        assert_eq!(sim.revealed(), Some(18));
+        assert!(!sim.is_complete());
+    }
+
+    #[test]
+    fn advance_reveals_several_cells_then_completes() {
+        let mut sim = simulation();
+        sim.apply(SimEvent::Forward);
+        assert!(!sim.apply(SimEvent::Advance(0)));
+        sim.apply(SimEvent::Advance(4));
+        assert_eq!(sim.revealed(), Some(5));
+        sim.apply(SimEvent::Advance(100));
+        assert_eq!(sim.revealed(), Some(18));
+        sim.apply(SimEvent::Advance(3));
+        assert!(sim.is_complete());
+        assert_eq!(sim.traced(), Some(0));
+    }
+
+    #[test]
+    fn traceback_waits_for_the_fill_then_walks_hop_by_hop() {
+        let mut sim = simulation();
+        sim.apply(SimEvent::Forward);
+        assert!(!sim.apply(SimEvent::TraceForward));
+        assert!(!sim.apply(SimEvent::TraceAll));
+
+        sim.apply(SimEvent::Seek(18));
+        sim.apply(SimEvent::Forward);
+        assert!(sim.walked().is_empty());
+        assert_eq!(
+            sim.path(),
+            [
+                TraceHop { row: 3, col: 5, taken: false },
+                TraceHop { row: 2, col: 5, taken: true },
+                TraceHop { row: 1, col: 2, taken: true },
+            ]
+        );
+        sim.apply(SimEvent::TraceForward);
+        assert_eq!(sim.walked().len(), 1);
+        sim.apply(SimEvent::TraceSeek(10));
+        assert_eq!(sim.walked().len(), 3);
+        assert!(!sim.apply(SimEvent::TraceForward));
+        sim.apply(SimEvent::TraceSeek(1));
+        sim.apply(SimEvent::TraceAll);
+        assert_eq!(sim.traced(), None);
+        assert_eq!(sim.walked().len(), 3);
+    }
+
+    #[test]
+    fn finish_shows_everything_and_back_or_forward_leave_it() {
+        let mut sim = simulation();
+        sim.apply(SimEvent::Finish);
+        assert!(sim.is_revealed(3, 5));
+        assert_eq!(sim.progress(), Progress { filled: 18, cells: 18, walked: 3, hops: 3 });
+
+        let mut back = sim.clone();
+        back.apply(SimEvent::Back);
+        assert_eq!(back.revealed(), Some(18));
+        assert!(back.path().is_empty());
+
+        sim.apply(SimEvent::Forward);
+        assert_eq!(sim.revealed(), Some(1));
+        assert_eq!(sim.active_cell(), Some((1, 0)));
+    }
+
+    #[test]
+    fn a_new_plan_keeps_the_count_revealed() {
+        let mut sim = simulation();
+        sim.apply(SimEvent::Seek(4));
+        sim.set_plan(StepPlan::ColumnMajor);
+        assert_eq!(sim.revealed(), Some(4));
+        assert_eq!(sim.active_cell(), Some((1, 1)));
+        assert!(sim.is_revealed(3, 0) && !sim.is_revealed(3, 1));
+    }
+}
@@ -0,0 +1,371 @@
+use crate::problem::Problem;
+
+/// Quick statistics about an instance, cheap enough to compute on every edit.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InstanceStats {
+    pub n: usize,
+    pub capacity: usize,
+    /// Cells in the full DP table, `(n + 1) × (W + 1)`.
+    pub cells: usize,
+    /// Pearson correlation between weights and benefits, `None` when either
+    /// is constant.
+    pub correlation: Option<f64>,
+    /// Value of greedily packing by benefit/weight ratio.
+    pub greedy: usize,
+    /// Fractional (LP) upper bound on the optimum.
+    pub upper_bound: f64,
+    pub difficulty: Difficulty,
+}
+
+impl InstanceStats {
+    /// Relative gap between the greedy value and the LP bound; a small gap
+    /// means greedy is already close to optimal.
+    pub fn greedy_gap(&self) -> f64 {
+        if self.upper_bound <= 0.0 {
+            0.0
+        } else {
+            (self.upper_bound - self.greedy as f64) / self.upper_bound
+        }
+    }
+}
+
+/// Heuristic rating of how instructive/hard an instance is for the DP.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Difficulty {
+    Easy,
+    Moderate,
+    Hard,
+}
+
+impl Difficulty {
+    pub fn label(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Moderate => "Moderate",
+            Difficulty::Hard => "Hard",
+        }
+    }
+
+    pub fn class(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "difficulty easy",
+            Difficulty::Moderate => "difficulty moderate",
+            Difficulty::Hard => "difficulty hard",
+        }
+    }
+}
+
+pub fn analyze(p: &Problem) -> InstanceStats {
+    let (greedy, upper_bound) = greedy_and_bound(p);
+    let correlation = pearson(&p.weights, &p.benefits);
+    let mut stats = InstanceStats {
+        n: p.len(),
+        capacity: p.capacity,
+        cells: (p.len() + 1) * (p.capacity + 1),
+        correlation,
+        greedy,
+        upper_bound,
+        difficulty: Difficulty::Easy,
+    };
+    stats.difficulty = rate(&stats);
+    stats
+}
+
+// ── helpers ──────────────────────────────────────────────────────────────────
+
+/// Items by decreasing benefit/weight; zero-weight items first, those with a
+/// benefit before those without, and ties in index order.
+pub(crate) fn by_ratio(p: &Problem) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..p.len()).collect();
+    // 0/0 has no ratio, so zero weights are ranked apart to keep the order total
+    let rank = |i: usize| match (p.weights[i], p.benefits[i]) {
+        (0, 0) => 1,
+        (0, _) => 0,
+        _ => 2,
+    };
+    // compare b_i / w_i > b_j / w_j as b_i · w_j > b_j · w_i to stay exact
+    order.sort_by(|&i, &j| {
+        let lhs = p.benefits[i] as u128 * p.weights[j] as u128;
+        let rhs = p.benefits[j] as u128 * p.weights[i] as u128;
+        rank(i).cmp(&rank(j)).then(rhs.cmp(&lhs)).then(i.cmp(&j))
+    });
+    order
+}
+
+/// Greedy integer value and the fractional bound in one pass over the ratio
+/// order: greedy skips items that do not fit, the bound takes a fraction of
+/// the first one that does not.
+fn greedy_and_bound(p: &Problem) -> (usize, f64) {
+    let order = by_ratio(p);
+
+    let mut room = p.capacity;
+    let mut greedy = 0;
+    for &i in &order {
+        if p.weights[i] <= room {
+            room -= p.weights[i];
+            greedy += p.benefits[i];
+        }
+    }
+
+    let mut room = p.capacity as f64;
+    let mut bound = 0.0;
+    for &i in &order {
+        let (w, b) = (p.weights[i] as f64, p.benefits[i] as f64);
+        if w <= room {
+            room -= w;
+            bound += b;
+        } else {
+            bound += b * room / w;
+            break;
+        }
+    }
+    (greedy, bound)
+}
+
+fn pearson(xs: &[usize], ys: &[usize]) -> Option<f64> {
+    let n = xs.len() as f64;
+    if xs.len() < 2 {
+        return None;
+    }
+    let mean = |v: &[usize]| v.iter().map(|&x| x as f64).sum::<f64>() / n;
+    let (mx, my) = (mean(xs), mean(ys));
+    let (mut sxy, mut sxx, mut syy) = (0.0, 0.0, 0.0);
+    for (&x, &y) in xs.iter().zip(ys) {
+        let (dx, dy) = (x as f64 - mx, y as f64 - my);
+        sxy += dx * dy;
+        sxx += dx * dx;
+        syy += dy * dy;
+    }
+    (sxx > 0.0 && syy > 0.0).then(|| sxy / (sxx * syy).sqrt())
+}
+
+/// Strongly correlated weights and benefits defeat ratio-based shortcuts, a
+/// large greedy gap means the DP really matters, and big tables are slow.
+fn rate(s: &InstanceStats) -> Difficulty {
+    let mut score = 0;
+    if s.correlation.is_some_and(|r| r > 0.9) {
+        score += 2;
+    } else if s.correlation.is_some_and(|r| r > 0.6) {
+        score += 1;
+    }
+    if s.greedy_gap() > 0.1 {
+        score += 2;
+    } else if s.greedy_gap() > 0.02 {
+        score += 1;
+    }
+    if s.cells > 1_000_000 {
+        score += 2;
+    } else if s.cells > 10_000 {
+        score += 1;
+    }
+    match score {
+        0..=1 => Difficulty::Easy,
+        2..=3 => Difficulty::Moderate,
+        _ => Difficulty::Hard,
+    }
+}
+
+// ─── Degenerate instances ────────────────────────────────────────────────────
+
+/// Something about an instance that makes its table trivial or surprising.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Degeneracy {
+    /// Every item is heavier than the capacity, so the answer is 0.
+    NothingFits,
+    /// These items (1-based) weigh nothing and are always packed.
+    Weightless(Vec<usize>),
+    /// Every item has this benefit, so the optimum is just the most items
+    /// that fit together.
+    EqualBenefits(usize),
+}
+
+impl Degeneracy {
+    /// Stable key, e.g. for remembering a dismissed banner.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Degeneracy::NothingFits => "nothing-fits",
+            Degeneracy::Weightless(_) => "weightless",
+            Degeneracy::EqualBenefits(_) => "equal-benefits",
+        }
+    }
+
+    pub fn describe(&self, p: &Problem) -> String {
+        match self {
+            Degeneracy::NothingFits => format!(
+                "Every item is heavier than the capacity {}, so nothing fits: every cell stays 0 and the answer is trivially 0.",
+                p.units.weight(p.capacity),
+            ),
+            Degeneracy::Weightless(items) => format!(
+                "Item{} {} weigh{} nothing, so {} taken in every column; the other items decide the rest of the table.",
+                if items.len() == 1 { "" } else { "s" },
+                items.iter().map(usize::to_string).collect::<Vec<_>>().join(", "),
+                if items.len() == 1 { "s" } else { "" },
+                if items.len() == 1 { "it is" } else { "they are" },
+            ),
+            Degeneracy::EqualBenefits(b) => format!(
+                "All benefits are {b}, so the optimum is {b} × the largest number of items that fit: packing the lightest items first is optimal."
+            ),
+        }
+    }
+}
+
+/// Degenerate properties of `p`, in the order their banners are shown.
+pub fn degeneracies(p: &Problem) -> Vec<Degeneracy> {
+    let mut found = Vec::new();
+    if !p.weights.is_empty() && p.weights.iter().all(|&w| w > p.capacity) {
+        found.push(Degeneracy::NothingFits);
+    }
+    let weightless: Vec<usize> = (0..p.len()).filter(|&i| p.weights[i] == 0).map(|i| i + 1).collect();
+    if !weightless.is_empty() {
+        found.push(Degeneracy::Weightless(weightless));
+    }
+    if p.len() > 1 && p.benefits.iter().all(|&b| b == p.benefits[0]) {
+        found.push(Degeneracy::EqualBenefits(p.benefits[0]));
+    }
+    found
+}
+
+// ─── Preprocessing ───────────────────────────────────────────────────────────
+
+/// Why an item can be dropped without changing the optimum.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RemovalReason {
+    /// Heavier than the whole capacity.
+    TooHeavy,
+    /// Each listed item (1-based) is at most as heavy and at least as
+    /// valuable, and they cannot all fit together with this one.
+    DominatedBy(Vec<usize>),
+}
+
+/// An item removed by [`simplify`], by its 1-based index in the original.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Removal {
+    pub item: usize,
+    pub reason: RemovalReason,
+}
+
+impl Removal {
+    pub fn describe(&self, p: &Problem) -> String {
+        let i = self.item;
+        let (w, b) = (p.weights[i - 1], p.benefits[i - 1]);
+        match &self.reason {
+            RemovalReason::TooHeavy => format!(
+                "Item {i} weighs {}, more than the capacity {}.",
+                p.units.weight(w),
+                p.units.weight(p.capacity),
+            ),
+            RemovalReason::DominatedBy(by) => format!(
+                "Item {i} ({}, {b}) is dominated by item{} {}: never lighter-and-better, and they can't all fit with it.",
+                p.units.weight(w),
+                if by.len() == 1 { "" } else { "s" },
+                by.iter().map(usize::to_string).collect::<Vec<_>>().join(", "),
+            ),
+        }
+    }
+}
+
+/// Drop items that cannot affect the optimum: those heavier than the
+/// capacity, and those dominated by items they can never all be packed with.
+///
+/// Item `i` dominates `j` when `wᵢ ≤ wⱼ` and `bᵢ ≥ bⱼ` (ties broken by index).
+/// If every solution holding `j` must leave out one of its dominators, `j`
+/// can be swapped for that dominator without losing value — unlike plain
+/// dominance, which is unsafe in 0/1 knapsack where both could be packed.
+pub fn simplify(p: &Problem) -> (Problem, Vec<Removal>) {
+    let dominates = |i: usize, j: usize| {
+        i != j
+            && p.weights[i] <= p.weights[j]
+            && p.benefits[i] >= p.benefits[j]
+            && (p.weights[i] < p.weights[j] || p.benefits[i] > p.benefits[j] || i < j)
+    };
+
+    let mut removals = Vec::new();
+    for j in 0..p.len() {
+        if p.weights[j] > p.capacity {
+            removals.push(Removal { item: j + 1, reason: RemovalReason::TooHeavy });
+            continue;
+        }
+        let by: Vec<usize> = (0..p.len()).filter(|&i| dominates(i, j)).collect();
+        let together: usize = p.weights[j] + by.iter().map(|&i| p.weights[i]).sum::<usize>();
+        if !by.is_empty() && together > p.capacity {
+            removals.push(Removal {
+                item: j + 1,
+                reason: RemovalReason::DominatedBy(by.into_iter().map(|i| i + 1).collect()),
+            });
+        }
+    }
+
+    let removed = |i: usize| removals.iter().any(|r| r.item == i + 1);
+    let kept: Vec<usize> = (0..p.len()).filter(|&i| !removed(i)).collect();
+    let reduced = Problem {
+        capacity: p.capacity,
+        weights: kept.iter().map(|&i| p.weights[i]).collect(),
+        benefits: kept.iter().map(|&i| p.benefits[i]).collect(),
+        units: p.units.clone(),
+    };
+    (reduced, removals)
+}
+
+/// Greatest common divisor of the capacity and every weight; `1` when
+/// scaling cannot shrink the table.
+pub fn weight_gcd(p: &Problem) -> usize {
+    let g = p.weights.iter().fold(p.capacity, |g, &w| gcd(g, w));
+    g.max(1)
+}
+
+pub(crate) fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/// One step of the preprocessing pre-phase, in the order it is shown.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PreStep {
+    Remove(Removal),
+    /// Divide the capacity and all remaining weights by `divisor`.
+    Scale { divisor: usize },
+}
+
+/// Too-heavy items, then dominated items, then a gcd rescaling of what is
+/// left — each step preserving the optimal benefit.
+pub fn preprocess(p: &Problem) -> Vec<PreStep> {
+    let (reduced, mut removals) = simplify(p);
+    removals.sort_by_key(|r| !matches!(r.reason, RemovalReason::TooHeavy));
+    let mut steps: Vec<PreStep> = removals.into_iter().map(PreStep::Remove).collect();
+    let divisor = weight_gcd(&reduced);
+    if divisor > 1 {
+        steps.push(PreStep::Scale { divisor });
+    }
+    steps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::Rng;
+
+    #[test]
+    fn ratio_order_ranks_zero_weights_first_and_is_total() {
+        let p = Problem { capacity: 10, weights: vec![2, 0, 1, 0, 4], benefits: vec![4, 0, 3, 5, 8], ..Default::default() };
+        assert_eq!(by_ratio(&p), vec![3, 1, 2, 0, 4]);
+
+        // random instances with (0, 0) items used to break the sort's ordering
+        let mut rng = Rng::new(5);
+        for _ in 0..500 {
+            let n = rng.between(1, 40);
+            let p = Problem {
+                capacity: 20,
+                weights: (0..n).map(|_| rng.between(0, 3)).collect(),
+                benefits: (0..n).map(|_| rng.between(0, 3)).collect(),
+                ..Default::default()
+            };
+            let order = by_ratio(&p);
+            let ratio_le = |i: usize, j: usize| p.benefits[i] * p.weights[j] <= p.benefits[j] * p.weights[i];
+            for pair in order.windows(2) {
+                let (i, j) = (pair[0], pair[1]);
+                if p.weights[i] > 0 && p.weights[j] > 0 {
+                    assert!(ratio_le(j, i), "{p:?}");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::store::StateStore;
+
+/// Days of history kept; older days are dropped as new ones are recorded.
+pub const MAX_DAYS: usize = 365;
+/// Key the history is stored under.
+pub const USAGE_KEY: &str = "knapsack-dp.usage";
+
+/// Something worth counting. Nothing here leaves the browser.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UsageEvent {
+    /// An instance was solved by the named solver (`"table DP"`, `"top-down"`…).
+    Solved(String),
+    /// Cells revealed while stepping or playing.
+    Steps(u32),
+    /// An assignment answer was graded.
+    Answered { correct: bool },
+}
+
+/// Counters of one calendar day (UTC).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DayStats {
+    /// Days since the Unix epoch.
+    pub day: u64,
+    pub solved: u32,
+    pub steps: u32,
+    pub answered: u32,
+    pub correct: u32,
+    /// Solves per solver.
+    #[serde(default)]
+    pub modes: BTreeMap<String, u32>,
+}
+
+impl DayStats {
+    /// Share of graded answers that were correct, if any were graded.
+    pub fn accuracy(&self) -> Option<f64> {
+        (self.answered > 0).then(|| self.correct as f64 / self.answered as f64)
+    }
+}
+
+/// Local usage history, one entry per day something was recorded, oldest first.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Usage {
+    pub days: Vec<DayStats>,
+}
+
+impl Usage {
+    pub fn record(&mut self, day: u64, event: UsageEvent) {
+        if self.days.last().is_none_or(|d| d.day < day) {
+            self.days.push(DayStats { day, ..Default::default() });
+            if self.days.len() > MAX_DAYS {
+                self.days.drain(..self.days.len() - MAX_DAYS);
+            }
+        }
+        // a clock set back counts towards the latest day
+        let today = self.days.last_mut().expect("a day was just pushed");
+        match event {
+            UsageEvent::Solved(mode) => {
+                today.solved += 1;
+                *today.modes.entry(mode).or_default() += 1;
+            }
+            UsageEvent::Steps(n) => today.steps += n,
+            UsageEvent::Answered { correct } => {
+                today.answered += 1;
+                today.correct += correct as u32;
+            }
+        }
+    }
+
+    /// Every day added up; `day` is that of the latest one.
+    pub fn totals(&self) -> DayStats {
+        let mut total = DayStats { day: self.days.last().map_or(0, |d| d.day), ..Default::default() };
+        for d in &self.days {
+            total.solved += d.solved;
+            total.steps += d.steps;
+            total.answered += d.answered;
+            total.correct += d.correct;
+            for (mode, n) in &d.modes {
+                *total.modes.entry(mode.clone()).or_default() += n;
+            }
+        }
+        total
+    }
+
+    /// The last `n` calendar days ending at `today`, days without use as zeros.
+    pub fn recent(&self, today: u64, n: usize) -> Vec<DayStats> {
+        (0..n as u64)
+            .rev()
+            .filter_map(|back| today.checked_sub(back))
+            .map(|day| self.days.iter().find(|d| d.day == day).cloned().unwrap_or(DayStats { day, ..Default::default() }))
+            .collect()
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("usage serializes")
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, String> {
+        serde_json::from_str(text).map_err(|e| format!("Unreadable usage data: {e}"))
+    }
+}
+
+/// Days since the Unix epoch of a time in milliseconds, as `Date.now()` gives.
+pub fn day_of(epoch_ms: f64) -> u64 {
+    (epoch_ms / 86_400_000.0).max(0.0) as u64
+}
+
+/// The stored history; empty when there is none or it can't be read.
+pub async fn load(store: &impl StateStore) -> Usage {
+    store.get(USAGE_KEY).await.ok().flatten().and_then(|json| Usage::from_json(&json).ok()).unwrap_or_default()
+}
+
+pub async fn save(store: &impl StateStore, usage: &Usage) -> Result<(), String> {
+    store.put(USAGE_KEY, &usage.to_json()).await
+}
+
+pub async fn clear(store: &impl StateStore) -> Result<(), String> {
+    store.delete(USAGE_KEY).await
+}
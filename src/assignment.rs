@@ -0,0 +1,211 @@
+use crate::playlist;
+use crate::problem::Problem;
+use crate::settings::Settings;
+use crate::solver::solve_headless;
+
+/// Query parameter an assignment link carries its instance in, encoded like
+/// one playlist entry.
+pub const ASSIGNMENT_PARAM: &str = "assignment";
+/// Prefix of result codes, versioned so the format can change.
+pub const CODE_PREFIX: &str = "KDP2";
+
+/// A student's answer: the optimum and the 1-based items that reach it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Submission {
+    pub optimum: usize,
+    pub items: Vec<usize>,
+}
+
+/// How a submission compares with the solved instance. Any subset reaching
+/// the optimum counts, not only the one the traceback finds.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grade {
+    pub expected: usize,
+    pub optimum_correct: bool,
+    /// Total weight and benefit of the submitted items.
+    pub weight: usize,
+    pub benefit: usize,
+    pub fits: bool,
+    pub items_optimal: bool,
+}
+
+impl Grade {
+    pub fn passed(&self) -> bool {
+        self.optimum_correct && self.items_optimal
+    }
+
+    /// One sentence per check, in the order they were made.
+    pub fn feedback(&self, capacity: usize) -> Vec<String> {
+        let mut lines = vec![if self.optimum_correct {
+            "The optimum is correct.".to_string()
+        } else {
+            "The optimum is not correct.".to_string()
+        }];
+        lines.push(if !self.fits {
+            format!("The items weigh {}, more than the capacity {capacity}.", self.weight)
+        } else if self.items_optimal {
+            format!("The items fit ({} of {capacity}) and reach the optimum.", self.weight)
+        } else {
+            format!("The items fit ({} of {capacity}) but are worth {}, not the optimum.", self.weight, self.benefit)
+        });
+        lines
+    }
+}
+
+/// The instance of an assignment link's parameter. Anyone can write a link,
+/// so it must fit the default limits before the student's page solves it.
+pub fn decode(text: &str) -> Result<Problem, String> {
+    let mut problems = playlist::decode(text)?;
+    let p = match problems.len() {
+        1 => problems.remove(0),
+        n => return Err(format!("An assignment holds one instance, not {n}.")),
+    };
+    Settings::default().check_size(&p)?;
+    Ok(p)
+}
+
+/// 1-based item numbers separated by commas or spaces, each at most once.
+pub fn parse_items(text: &str, n: usize) -> Result<Vec<usize>, String> {
+    let mut items = Vec::new();
+    for token in text.split([',', ' ', ';']).map(str::trim).filter(|t| !t.is_empty()) {
+        let item: usize = token.parse().map_err(|_| format!("'{token}' is not an item number."))?;
+        if !(1..=n).contains(&item) {
+            return Err(format!("There is no item {item}; items are numbered 1 to {n}."));
+        }
+        if items.contains(&item) {
+            return Err(format!("Item {item} is listed twice."));
+        }
+        items.push(item);
+    }
+    items.sort_unstable();
+    Ok(items)
+}
+
+pub fn grade(p: &Problem, s: &Submission) -> Grade {
+    let expected = solve_headless(p).best;
+    // a total past usize::MAX is over any capacity and above any optimum
+    let total = |values: &[usize]| {
+        s.items.iter().try_fold(0usize, |sum, &i| sum.checked_add(values[i - 1])).unwrap_or(usize::MAX)
+    };
+    let weight = total(&p.weights);
+    let benefit = total(&p.benefits);
+    let fits = weight <= p.capacity;
+    Grade {
+        expected,
+        optimum_correct: s.optimum == expected,
+        weight,
+        benefit,
+        fits,
+        items_optimal: fits && benefit == expected,
+    }
+}
+
+// ─── Result codes ────────────────────────────────────────────────────────────
+
+/// What a verified result code says.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Verified {
+    pub submission: Submission,
+    /// Seconds since the Unix epoch when the answer was graded.
+    pub timestamp: u64,
+    /// The submission graded again against the teacher's instance.
+    pub grade: Grade,
+}
+
+/// A short line the student hands in, e.g. `KDP2.90.2-4.t3uwow.99fdc46d2c8d`:
+/// the answer, when it was graded (base 36) and a checksum over those and
+/// the instance. The instance itself is left out, since the teacher has it.
+///
+/// The checksum makes a code edited by hand, or pasted against another
+/// instance, fail [`verify_code`]. It is not a signature: someone who reads
+/// this function can compute a valid code for any answer.
+pub fn result_code(p: &Problem, s: &Submission, timestamp: u64) -> String {
+    let items = if s.items.is_empty() {
+        "0".to_string()
+    } else {
+        s.items.iter().map(usize::to_string).collect::<Vec<_>>().join("-")
+    };
+    format!(
+        "{CODE_PREFIX}.{}.{items}.{}.{:012x}",
+        s.optimum,
+        to_base36(timestamp),
+        checksum(p, s, timestamp) & 0xffff_ffff_ffff
+    )
+}
+
+/// Check a pasted code against the instance it was issued for.
+pub fn verify_code(p: &Problem, code: &str) -> Result<Verified, String> {
+    let parts: Vec<&str> = code.trim().split('.').collect();
+    let [prefix, optimum, items, time, sum] = parts[..] else {
+        return Err("A result code has five parts separated by dots.".into());
+    };
+    if prefix != CODE_PREFIX {
+        return Err(format!("Unknown code version '{prefix}'; expected {CODE_PREFIX}."));
+    }
+    let malformed = || "The code is malformed.".to_string();
+    let optimum = optimum.parse().map_err(|_| malformed())?;
+    let items = match items {
+        "0" => Vec::new(),
+        list => list.split('-').map(|i| i.parse().map_err(|_| malformed())).collect::<Result<Vec<usize>, _>>()?,
+    };
+    if items.iter().any(|&i| i == 0 || i > p.len()) {
+        return Err("The code names items this instance doesn't have.".into());
+    }
+    let timestamp = u64::from_str_radix(time, 36).map_err(|_| malformed())?;
+    let submission = Submission { optimum, items };
+    let expected = checksum(p, &submission, timestamp) & 0xffff_ffff_ffff;
+    if u64::from_str_radix(sum, 16).ok() != Some(expected) {
+        return Err("The checksum doesn't match: the code was edited or is for another instance.".into());
+    }
+    let grade = grade(p, &submission);
+    Ok(Verified { submission, timestamp, grade })
+}
+
+/// FNV-1a over the instance, the answer and the time, with a final mix so
+/// nearby inputs give unrelated sums.
+fn checksum(p: &Problem, s: &Submission, timestamp: u64) -> u64 {
+    let text = format!("{}|{}|{:?}|{timestamp}", playlist::encode(std::slice::from_ref(p)), s.optimum, s.items);
+    let mut h: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in text.bytes() {
+        h ^= byte as u64;
+        h = h.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    h ^= h >> 33;
+    h = h.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    h ^ (h >> 33)
+}
+
+fn to_base36(mut n: u64) -> String {
+    const DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+    let mut out = Vec::new();
+    loop {
+        out.push(DIGITS[(n % 36) as usize]);
+        n /= 36;
+        if n == 0 {
+            break;
+        }
+    }
+    out.reverse();
+    String::from_utf8(out).expect("base-36 digits are ASCII")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn links_beyond_the_default_limits_are_refused() {
+        assert!(decode("99999999999:1:1").is_err());
+        assert!(decode(&format!("5:1,1:{},1", usize::MAX)).is_err());
+        let p = decode("5:2,3:3,4").unwrap();
+        assert_eq!((p.capacity, p.weights, p.benefits), (5, vec![2, 3], vec![3, 4]));
+    }
+
+    #[test]
+    fn grading_saturates_instead_of_overflowing() {
+        let p = Problem { capacity: 5, weights: vec![usize::MAX, 1], benefits: vec![3, 4], ..Default::default() };
+        let g = grade(&p, &Submission { optimum: 4, items: vec![1, 2] });
+        assert_eq!(g.weight, usize::MAX);
+        assert!(!g.fits && g.optimum_correct && !g.passed());
+    }
+}
@@ -0,0 +1,107 @@
+//! Command-line solver: `knapsack-cli --capacity 10 --weights 4,3,2 --benefits 40,25,15`
+//! or `knapsack-cli saved.knap`.
+
+use std::process::ExitCode;
+
+use mochila_leptos::format::Locale;
+use mochila_leptos::io::{KnapFile, KNAP_VERSION};
+use mochila_leptos::parse::parse_problem;
+use mochila_leptos::problem::{Problem, Units};
+use mochila_leptos::reference::{cross_check, CrossCheck};
+use mochila_leptos::schema::{Report, Solution, Table, SCHEMA_VERSION};
+
+const USAGE: &str = "\
+usage: knapsack-cli --capacity W --weights w1,w2,… --benefits b1,b2,… [--table] [--check] [--json]
+       knapsack-cli FILE.knap [--table] [--check] [--json]";
+
+struct Args {
+    problem: Problem,
+    table: bool,
+    check: bool,
+    json: bool,
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("knapsack-cli: solve a 0/1 knapsack instance\n{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("{e}\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let p = &args.problem;
+
+    let table = Table::solve(p);
+    let solution = Solution::from_table(&table, p);
+    let best = solution.best;
+
+    if args.json {
+        let report = Report {
+            version: SCHEMA_VERSION,
+            problem: p.clone(),
+            solution,
+            table: args.table.then_some(table),
+        };
+        println!("{}", serde_json::to_string_pretty(&report).expect("reports are always serializable"));
+    } else {
+        if args.table {
+            for row in &table.values {
+                println!("{}", row.iter().map(|v| format!("{v:>6}")).collect::<String>());
+            }
+        }
+        println!("Optimal benefit: {best}");
+        println!("Items taken: {:?}", solution.items);
+        println!("Weight used: {} of {}", solution.weight, p.capacity);
+    }
+
+    if args.check {
+        match cross_check(p, best) {
+            CrossCheck::Agrees => println!("Cross-check: ok"),
+            CrossCheck::Skipped => println!("Cross-check: skipped (too many items)"),
+            CrossCheck::Disagrees { expected } => {
+                eprintln!("Cross-check FAILED: reference optimum is {expected}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+/// The arguments, or `None` when help was asked for.
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Args>, String> {
+    let (mut capacity, mut weights, mut benefits, mut file) = (None, None, None, None);
+    let (mut table, mut check, mut json) = (false, false, false);
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or_else(|| format!("{name} needs a value"));
+        match arg.as_str() {
+            "--capacity" => capacity = Some(value("--capacity")?),
+            "--weights" => weights = Some(value("--weights")?),
+            "--benefits" => benefits = Some(value("--benefits")?),
+            "--table" => table = true,
+            "--check" => check = true,
+            "--json" => json = true,
+            "-h" | "--help" => return Ok(None),
+            s if s.starts_with('-') => return Err(format!("unknown option {s}")),
+            _ => file = Some(arg),
+        }
+    }
+
+    let problem = match (file, capacity, weights, benefits) {
+        (Some(path), None, None, None) => {
+            let text = std::fs::read_to_string(&path).map_err(|e| format!("{path}: {e}"))?;
+            let loaded = KnapFile::load(&text)?;
+            if let Some(v) = loaded.upgraded_from {
+                eprintln!("note: {path} is in the older format v{v}; read it as v{KNAP_VERSION}");
+            }
+            loaded.file.problem
+        }
+        (None, Some(c), Some(w), Some(b)) => parse_problem(&c, &w, &b, Units::default(), Locale::En)?,
+        _ => return Err("give either a .knap file or all of --capacity, --weights and --benefits".into()),
+    };
+    problem.validate()?;
+    Ok(Some(Args { problem, table, check, json }))
+}
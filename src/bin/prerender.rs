@@ -0,0 +1,22 @@
+//! Writes the pre-solved example pages into the site root, e.g.
+//! `cargo run --no-default-features --features ssr --bin prerender -- target/site`.
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let root = PathBuf::from(std::env::args().nth(1).unwrap_or_else(|| "target/site".into()));
+    for (path, html) in mochila_leptos::prerender_examples() {
+        let path = root.join(path);
+        let written = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| std::fs::write(&path, html));
+        if let Err(e) = written {
+            eprintln!("{}: {e}", path.display());
+            return ExitCode::FAILURE;
+        }
+        println!("wrote {}", path.display());
+    }
+    ExitCode::SUCCESS
+}
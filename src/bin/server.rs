@@ -0,0 +1,31 @@
+//! SSR server: pre-renders the routes in `App` and serves the hydrate bundle
+//! built by `cargo leptos`.
+
+#![recursion_limit = "256"]
+
+use axum::Router;
+use leptos::prelude::*;
+use leptos_axum::{LeptosRoutes, generate_route_list};
+use mochila_leptos::{App, shell};
+
+#[tokio::main]
+async fn main() {
+    let conf = get_configuration(Some("Cargo.toml")).expect("Cargo.toml has [package.metadata.leptos]");
+    let options = conf.leptos_options;
+    let addr = options.site_addr;
+    let routes = generate_route_list(App);
+
+    let app = Router::new()
+        .leptos_routes(&options, routes, {
+            let options = options.clone();
+            move || shell(options.clone())
+        })
+        .fallback(leptos_axum::file_and_error_handler(shell))
+        .with_state(options);
+
+    let listener = tokio::net::TcpListener::bind(&addr)
+        .await
+        .unwrap_or_else(|e| panic!("cannot bind {addr}: {e}"));
+    println!("listening on http://{addr}");
+    axum::serve(listener, app.into_make_service()).await.unwrap();
+}
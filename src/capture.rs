@@ -0,0 +1,92 @@
+use leptos::prelude::*;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{CanvasRenderingContext2d, Element, HtmlCanvasElement, HtmlElement};
+
+use crate::components::drawing::{context_2d, draw_strokes, Stroke};
+
+const TRANSPARENT: &str = "rgba(0, 0, 0, 0)";
+
+/// Render the table inside `host` (plus any freehand `strokes` drawn over
+/// it) onto an offscreen canvas and return it as a PNG data URL.
+///
+/// Cells are repainted from their on-screen geometry and computed styles, so
+/// the image matches what is currently shown: collapsed columns, highlights,
+/// revealed cells and the active colour scheme.
+pub fn capture_png(host: &Element, strokes: &[Stroke]) -> Result<String, JsValue> {
+    let origin = host.get_bounding_client_rect();
+    let (w, h) = (origin.width(), origin.height());
+    let scale = window().device_pixel_ratio().max(1.0);
+
+    let canvas: HtmlCanvasElement = document().create_element("canvas")?.unchecked_into();
+    canvas.set_width((w * scale).ceil() as u32);
+    canvas.set_height((h * scale).ceil() as u32);
+    let ctx = context_2d(&canvas).ok_or_else(|| JsValue::from_str("2d context unavailable"))?;
+    ctx.scale(scale, scale)?;
+
+    ctx.set_fill_style_str(&background(&document().body().map(Into::into))?);
+    ctx.fill_rect(0.0, 0.0, w, h);
+
+    let cells = host.query_selector_all("th, td")?;
+    for i in 0..cells.length() {
+        let Some(cell) = cells.item(i).and_then(|n| n.dyn_into::<HtmlElement>().ok()) else {
+            continue;
+        };
+        let r = cell.get_bounding_client_rect();
+        paint_cell(&ctx, &cell, r.left() - origin.left(), r.top() - origin.top(), r.width(), r.height())?;
+    }
+
+    draw_strokes(&ctx, strokes, 0.0, 0.0);
+    canvas.to_data_url_with_type("image/png")
+}
+
+// ── helpers ──────────────────────────────────────────────────────────────────
+
+fn paint_cell(
+    ctx: &CanvasRenderingContext2d,
+    cell: &HtmlElement,
+    x: f64,
+    y: f64,
+    w: f64,
+    h: f64,
+) -> Result<(), JsValue> {
+    let Some(style) = window().get_computed_style(cell)? else {
+        return Ok(());
+    };
+
+    let bg = style.get_property_value("background-color")?;
+    if bg != TRANSPARENT {
+        ctx.set_fill_style_str(&bg);
+        ctx.fill_rect(x, y, w, h);
+    }
+    ctx.set_stroke_style_str(&style.get_property_value("border-bottom-color")?);
+    ctx.set_line_width(1.0);
+    ctx.stroke_rect(x + 0.5, y + 0.5, w - 1.0, h - 1.0);
+
+    // the value is the first line; ordinals and note markers are dropped
+    let text = cell.inner_text();
+    let Some(label) = text.lines().map(str::trim).find(|l| !l.is_empty()) else {
+        return Ok(());
+    };
+    ctx.set_fill_style_str(&style.get_property_value("color")?);
+    ctx.set_font(&format!(
+        "{} {} {}",
+        style.get_property_value("font-weight")?,
+        style.get_property_value("font-size")?,
+        style.get_property_value("font-family")?,
+    ));
+    ctx.set_text_align("center");
+    ctx.set_text_baseline("middle");
+    ctx.fill_text(label, x + w / 2.0, y + h / 2.0)
+}
+
+/// Background colour of `el`, falling back to white when it is transparent.
+fn background(el: &Option<Element>) -> Result<String, JsValue> {
+    let color = match el {
+        Some(el) => match window().get_computed_style(el)? {
+            Some(style) => style.get_property_value("background-color")?,
+            None => String::new(),
+        },
+        None => String::new(),
+    };
+    Ok(if color.is_empty() || color == TRANSPARENT { "#ffffff".into() } else { color })
+}
@@ -0,0 +1,255 @@
+use crate::problem::Problem;
+
+/// Languages the code panel can generate a solver in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Language {
+    #[default]
+    Rust,
+    Python,
+    Cpp,
+}
+
+impl Language {
+    pub const ALL: [Language; 3] = [Language::Rust, Language::Python, Language::Cpp];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::Rust => "rust",
+            Language::Python => "python",
+            Language::Cpp => "cpp",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|l| l.code() == code)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Language::Rust => "Rust",
+            Language::Python => "Python",
+            Language::Cpp => "C++",
+        }
+    }
+
+    /// File name offered when downloading the generated program.
+    pub fn filename(self) -> &'static str {
+        match self {
+            Language::Rust => "knapsack.rs",
+            Language::Python => "knapsack.py",
+            Language::Cpp => "knapsack.cpp",
+        }
+    }
+}
+
+/// A complete, dependency-free program that solves `problem` bottom-up and
+/// prints the optimal benefit and the chosen items.
+pub fn generate(lang: Language, problem: &Problem) -> String {
+    let template = match lang {
+        Language::Rust => RUST,
+        Language::Python => PYTHON,
+        Language::Cpp => CPP,
+    };
+    template
+        .replace("{capacity}", &problem.capacity.to_string())
+        .replace("{weights}", &list(&problem.weights))
+        .replace("{benefits}", &list(&problem.benefits))
+}
+
+// ── templates ────────────────────────────────────────────────────────────────
+
+fn list(values: &[usize]) -> String {
+    values.iter().map(usize::to_string).collect::<Vec<_>>().join(", ")
+}
+
+const RUST: &str = r#"// 0/1 knapsack, bottom-up dynamic programming
+fn main() {
+    let capacity: usize = {capacity};
+    let weights: Vec<usize> = vec![{weights}];
+    let benefits: Vec<usize> = vec![{benefits}];
+    let n = weights.len();
+
+    let mut dp = vec![vec![0usize; capacity + 1]; n + 1];
+    for i in 1..=n {
+        for w in 0..=capacity {
+            if weights[i - 1] > w {
+                dp[i][w] = dp[i - 1][w];
+            } else {
+                dp[i][w] = dp[i - 1][w].max(benefits[i - 1] + dp[i - 1][w - weights[i - 1]]);
+            }
+        }
+    }
+
+    let mut chosen = Vec::new();
+    let mut w = capacity;
+    for i in (1..=n).rev() {
+        if dp[i][w] != dp[i - 1][w] {
+            chosen.push(i);
+            w -= weights[i - 1];
+        }
+    }
+    chosen.reverse();
+
+    println!("Optimal benefit: {}", dp[n][capacity]);
+    println!("Items taken: {:?}", chosen);
+}
+"#;
+
+const PYTHON: &str = r#"# 0/1 knapsack, bottom-up dynamic programming
+capacity = {capacity}
+weights = [{weights}]
+benefits = [{benefits}]
+n = len(weights)
+
+dp = [[0] * (capacity + 1) for _ in range(n + 1)]
+for i in range(1, n + 1):
+    for w in range(capacity + 1):
+        if weights[i - 1] > w:
+            dp[i][w] = dp[i - 1][w]
+        else:
+            dp[i][w] = max(dp[i - 1][w], benefits[i - 1] + dp[i - 1][w - weights[i - 1]])
+
+chosen = []
+w = capacity
+for i in range(n, 0, -1):
+    if dp[i][w] != dp[i - 1][w]:
+        chosen.append(i)
+        w -= weights[i - 1]
+chosen.reverse()
+
+print("Optimal benefit:", dp[n][capacity])
+print("Items taken:", chosen)
+"#;
+
+const CPP: &str = r#"// 0/1 knapsack, bottom-up dynamic programming
+#include <algorithm>
+#include <iostream>
+#include <vector>
+
+int main() {
+    const std::size_t capacity = {capacity};
+    const std::vector<std::size_t> weights = {{weights}};
+    const std::vector<std::size_t> benefits = {{benefits}};
+    const std::size_t n = weights.size();
+
+    std::vector<std::vector<std::size_t>> dp(n + 1, std::vector<std::size_t>(capacity + 1, 0));
+    for (std::size_t i = 1; i <= n; ++i) {
+        for (std::size_t w = 0; w <= capacity; ++w) {
+            if (weights[i - 1] > w) {
+                dp[i][w] = dp[i - 1][w];
+            } else {
+                dp[i][w] = std::max(dp[i - 1][w], benefits[i - 1] + dp[i - 1][w - weights[i - 1]]);
+            }
+        }
+    }
+
+    std::vector<std::size_t> chosen;
+    std::size_t w = capacity;
+    for (std::size_t i = n; i >= 1; --i) {
+        if (dp[i][w] != dp[i - 1][w]) {
+            chosen.push_back(i);
+            w -= weights[i - 1];
+        }
+    }
+    std::reverse(chosen.begin(), chosen.end());
+
+    std::cout << "Optimal benefit: " << dp[n][capacity] << "\n";
+    std::cout << "Items taken:";
+    for (std::size_t i : chosen) std::cout << " " << i;
+    std::cout << "\n";
+}
+"#;
+
+// ─── Step highlighting ───────────────────────────────────────────────────────
+
+/// Which side of the recurrence produced a cell.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Branch {
+    /// `w[i] > w`: the item cannot fit, the value is copied from above.
+    TooHeavy,
+    /// The max kept `dp[i-1][w]`.
+    Skip,
+    /// The max took the item.
+    Take,
+}
+
+impl Branch {
+    /// How `table[row][col]` was decided, given item `row`'s `weight`.
+    pub fn of(table: &[Vec<usize>], row: usize, col: usize, weight: usize) -> Self {
+        if weight > col {
+            Branch::TooHeavy
+        } else if table[row][col] != table[row - 1][col] {
+            Branch::Take
+        } else {
+            Branch::Skip
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Branch::TooHeavy => "too heavy → copy from above",
+            Branch::Skip => "max kept dp[i-1][w]",
+            Branch::Take => "max took the item",
+        }
+    }
+}
+
+/// One iteration of the bottom-up loop nest: `i = row`, `w = col`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LoopStep {
+    pub row: usize,
+    pub col: usize,
+    pub branch: Branch,
+}
+
+/// 0-based lines of the generated Rust program executed for `step`: the
+/// outer loop header, the inner loop header and the assignment taken.
+pub fn rust_step_lines(step: LoopStep) -> [usize; 3] {
+    let line = |needle: &str| {
+        RUST.lines().position(|l| l.contains(needle)).expect("marker is in the Rust template")
+    };
+    let assignment = match step.branch {
+        Branch::TooHeavy => line("dp[i][w] = dp[i - 1][w];"),
+        Branch::Skip | Branch::Take => line("dp[i][w] = dp[i - 1][w].max("),
+    };
+    [line("for i in 1..=n"), line("for w in 0..=capacity"), assignment]
+}
+
+/// One pseudo-instruction of a loop iteration, e.g. `load dp[2][5] = 40`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TraceOp {
+    pub op: &'static str,
+    pub args: String,
+    pub result: String,
+}
+
+/// 1-based iteration number of `(row, col)` in the generated loop nest, which
+/// always runs row by row whatever order the table is revealed in.
+pub fn iteration(step: LoopStep, capacity: usize) -> usize {
+    (step.row - 1) * (capacity + 1) + step.col + 1
+}
+
+/// The reads, comparisons and the store the generated loop performs for
+/// `step`, in execution order.
+pub fn machine_trace(table: &[Vec<usize>], problem: &Problem, step: LoopStep) -> Vec<TraceOp> {
+    let LoopStep { row: i, col: w, branch } = step;
+    let (wt, b) = (problem.weights[i - 1], problem.benefits[i - 1]);
+    let op = |op, args: String, result: String| TraceOp { op, args, result };
+    let above = table[i - 1][w];
+
+    let mut ops = vec![op("cmp", format!("weights[{}] = {wt} > w = {w}", i - 1), (wt > w).to_string())];
+    if branch == Branch::TooHeavy {
+        ops.push(op("load", format!("dp[{}][{w}]", i - 1), above.to_string()));
+    } else {
+        let rest = table[i - 1][w - wt];
+        let with = b + rest;
+        ops.extend([
+            op("load", format!("dp[{}][{w}]", i - 1), above.to_string()),
+            op("load", format!("dp[{}][{}]", i - 1, w - wt), rest.to_string()),
+            op("add", format!("benefits[{}] = {b} + {rest}", i - 1), with.to_string()),
+            op("max", format!("{above}, {with}"), if branch == Branch::Take { "take" } else { "keep" }.into()),
+        ]);
+    }
+    ops.push(op("store", format!("dp[{i}][{w}]"), table[i][w].to_string()));
+    ops
+}
@@ -0,0 +1,103 @@
+use std::collections::HashSet;
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+
+use crate::solver::subset_sums;
+
+/// Which capacity columns the table draws individually; the rest are
+/// folded into collapsed placeholder columns.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnFilter {
+    #[default]
+    All,
+    /// Every k-th capacity (plus the last one).
+    EveryNth(usize),
+    /// Capacities equal to some item's weight (plus 0 and the last one).
+    ItemWeights,
+    /// Capacities reachable as a sum of item weights (plus the last one):
+    /// the only columns where a row's value can change.
+    Breakpoints,
+}
+
+impl ColumnFilter {
+    pub const ALL: [ColumnFilter; 6] = [
+        ColumnFilter::All,
+        ColumnFilter::EveryNth(2),
+        ColumnFilter::EveryNth(5),
+        ColumnFilter::EveryNth(10),
+        ColumnFilter::ItemWeights,
+        ColumnFilter::Breakpoints,
+    ];
+
+    pub fn code(self) -> String {
+        match self {
+            ColumnFilter::All => "all".into(),
+            ColumnFilter::EveryNth(k) => format!("every-{k}"),
+            ColumnFilter::ItemWeights => "weights".into(),
+            ColumnFilter::Breakpoints => "breakpoints".into(),
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|f| f.code() == code)
+    }
+
+    pub fn label(self) -> String {
+        match self {
+            ColumnFilter::All => "All columns".into(),
+            ColumnFilter::EveryNth(k) => format!("Every {k} columns"),
+            ColumnFilter::ItemWeights => "Item-weight columns".into(),
+            ColumnFilter::Breakpoints => "Breakpoints only".into(),
+        }
+    }
+
+    /// `kept[c]` tells whether capacity column `c` is drawn individually.
+    pub fn kept_columns(self, capacity: usize, weights: &[usize]) -> Vec<bool> {
+        if self == ColumnFilter::Breakpoints {
+            let mut kept = subset_sums(weights, capacity);
+            kept[capacity] = true;
+            return kept;
+        }
+        (0..=capacity)
+            .map(|c| match self {
+                ColumnFilter::EveryNth(k) => c.is_multiple_of(k.max(1)) || c == capacity,
+                ColumnFilter::ItemWeights => c == 0 || c == capacity || weights.contains(&c),
+                ColumnFilter::All | ColumnFilter::Breakpoints => true,
+            })
+            .collect()
+    }
+}
+
+/// A run of the table's columns as it is drawn.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ColumnGroup {
+    Shown(usize),
+    Collapsed(Range<usize>),
+}
+
+/// Split capacities `0..=capacity` into shown columns and collapsed runs.
+/// Columns in `expanded` are always shown.
+pub fn column_groups(
+    capacity: usize,
+    keep: impl Fn(usize) -> bool,
+    expanded: &HashSet<usize>,
+) -> Vec<ColumnGroup> {
+    let mut groups = Vec::new();
+    let mut run_start = None;
+    for c in 0..=capacity {
+        if keep(c) || expanded.contains(&c) {
+            if let Some(start) = run_start.take() {
+                groups.push(ColumnGroup::Collapsed(start..c));
+            }
+            groups.push(ColumnGroup::Shown(c));
+        } else if run_start.is_none() {
+            run_start = Some(c);
+        }
+    }
+    if let Some(start) = run_start {
+        groups.push(ColumnGroup::Collapsed(start..capacity + 1));
+    }
+    groups
+}
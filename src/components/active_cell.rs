@@ -0,0 +1,36 @@
+use leptos::prelude::*;
+
+use crate::codegen::LoopStep;
+use crate::problem::Problem;
+
+/// The cell a visualizer is stepping through, shared through context so the
+/// formula, the code, the loop trace and the table all mark the same step.
+#[derive(Clone, Copy)]
+pub struct ActiveCell {
+    /// Loop iteration of the cell revealed last, None when not stepping.
+    pub step: Signal<Option<LoopStep>>,
+    pub table: Signal<Option<Vec<Vec<usize>>>>,
+    pub problem: Signal<Problem>,
+}
+
+impl ActiveCell {
+    /// `(row, col)` of the active cell.
+    pub fn cell(&self) -> Option<(usize, usize)> {
+        self.step.get().map(|s| (s.row, s.col))
+    }
+}
+
+pub fn provide_active_cell(
+    step: Signal<Option<LoopStep>>,
+    table: Signal<Option<Vec<Vec<usize>>>>,
+    problem: Signal<Problem>,
+) -> ActiveCell {
+    let active = ActiveCell { step, table, problem };
+    provide_context(active);
+    active
+}
+
+/// The active cell of the enclosing visualizer, if there is one.
+pub fn use_active_cell() -> Option<ActiveCell> {
+    use_context()
+}
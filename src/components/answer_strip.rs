@@ -0,0 +1,86 @@
+use leptos::prelude::*;
+
+use crate::format::NumberFormat;
+use crate::problem::Problem;
+
+/// Widest row still drawn cell by cell; longer rows become a curve.
+const MAX_STRIP_CELLS: usize = 120;
+const PLOT_W: f64 = 480.0;
+const PLOT_H: f64 = 90.0;
+
+/// Only the last table row `dp[n][0..=m]`, the best value for every
+/// capacity with all items available, with the answer `dp[n][m]`
+/// emphasized. Short rows are drawn as cells, long ones as a step curve.
+#[component]
+pub fn AnswerStrip(problem: Problem, row: Vec<usize>, fmt: NumberFormat) -> impl IntoView {
+    let units = problem.units.clone();
+    let m = problem.capacity;
+    let best = row.get(m).copied().unwrap_or(0);
+
+    let body = if row.len() <= MAX_STRIP_CELLS {
+        let cells = row.iter().enumerate().map(|(c, &v)| {
+            let cls = if c == m { "cell cell-answer" } else { "cell" };
+            view! { <td class=cls title=format!("dp[{}][{c}] = {v}", problem.len())>{units.benefit(v, &fmt)}</td> }
+        }).collect_view();
+        view! {
+            <div class="table-wrap">
+                <table class="dp-table answer-strip">
+                    <thead>
+                        <tr>
+                            <th class="corner">"w"</th>
+                            {(0..=m).map(|w| view! { <th>{w}</th> }).collect_view()}
+                        </tr>
+                    </thead>
+                    <tbody>
+                        <tr>
+                            <td class="item-header">{format!("dp[{}]", problem.len())}</td>
+                            {cells}
+                        </tr>
+                    </tbody>
+                </table>
+            </div>
+        }.into_any()
+    } else {
+        // the row never decreases, so a step through sampled points keeps its shape
+        let step = row.len().div_ceil(PLOT_W as usize);
+        let top = best.max(1) as f64;
+        let x = |c: usize| c as f64 / m.max(1) as f64 * PLOT_W;
+        let y = |v: usize| PLOT_H - v as f64 / top * PLOT_H;
+        let mut points = Vec::new();
+        let mut prev = row[0];
+        for c in (0..row.len()).step_by(step).chain(std::iter::once(m)) {
+            points.push(format!("{:.1},{:.1}", x(c), y(prev)));
+            points.push(format!("{:.1},{:.1}", x(c), y(row[c])));
+            prev = row[c];
+        }
+        view! {
+            <svg
+                class="answer-curve"
+                viewBox=format!("0 0 {PLOT_W} {PLOT_H}")
+                role="img"
+                aria-label="Best value for every capacity"
+            >
+                <polyline points=points.join(" ") />
+                <circle cx=x(m).to_string() cy=y(best).to_string() r="4">
+                    <title>{format!("dp[{}][{m}] = {best}", problem.len())}</title>
+                </circle>
+            </svg>
+            <div class="mc-axis">
+                <span>"w = 0"</span>
+                <span>{format!("w = {m}")}</span>
+            </div>
+        }.into_any()
+    };
+
+    view! {
+        <section class="summary-card strip-card" aria-label="Answer strip">
+            <h2 class="summary-title">"Answer strip"</h2>
+            <p class="summary-note">
+                "Last row only: the best value at every capacity with all items. The answer is "
+                <strong class="strip-answer">{units.benefit(best, &fmt)}</strong>
+                {format!(" at w = {m}.")}
+            </p>
+            {body}
+        </section>
+    }
+}
@@ -0,0 +1,180 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::analytics::UsageEvent;
+use crate::assignment::{grade, parse_items, result_code, verify_code, Grade, Submission};
+use crate::components::usage::use_usage;
+use crate::files::copy_text;
+use crate::problem::Problem;
+
+/// An instance handed out as homework: the items are shown but can't be
+/// edited and no table is drawn. The student enters the optimum and the
+/// items reaching it, gets it graded here and copies a result code to hand in.
+#[component]
+pub fn AssignmentView(problem: Problem) -> impl IntoView {
+    let p = StoredValue::new(problem);
+    let (optimum, set_optimum) = signal(String::new());
+    let (items, set_items) = signal(String::new());
+    let (error, set_error) = signal(None::<String>);
+    let (graded, set_graded) = signal(None::<(Grade, String)>);
+    let (copied, set_copied) = signal(false);
+    let usage = use_usage();
+
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        let n = p.with_value(Problem::len);
+        let submission = optimum.with_untracked(|o| o.trim().parse::<usize>())
+            .map_err(|_| "Enter the optimum as a whole number.".to_string())
+            .and_then(|optimum| Ok(Submission { optimum, items: items.with_untracked(|t| parse_items(t, n))? }));
+        match submission {
+            Ok(s) => {
+                let (g, code) = p.with_value(|p| {
+                    let g = grade(p, &s);
+                    let code = result_code(p, &s, (js_sys::Date::now() / 1000.0) as u64);
+                    (g, code)
+                });
+                usage.record(UsageEvent::Answered { correct: g.passed() });
+                set_error.set(None);
+                set_copied.set(false);
+                set_graded.set(Some((g, code)));
+            }
+            Err(e) => set_error.set(Some(e)),
+        }
+    };
+    let on_copy = move |_| {
+        let Some((_, code)) = graded.get_untracked() else { return };
+        spawn_local(async move { set_copied.set(copy_text(&code).await.is_ok()) });
+    };
+
+    let rows = p.with_value(|p| {
+        (1..=p.len()).map(|i| view! {
+            <tr>
+                <td>{i}</td>
+                <td>{p.weights[i - 1]}</td>
+                <td>{p.benefits[i - 1]}</td>
+            </tr>
+        }).collect_view()
+    });
+
+    view! {
+        <section class="form-card assignment-card" aria-label="Assignment">
+            <h2 class="summary-title">"Assignment"</h2>
+            <p class="summary-note">
+                {format!(
+                    "Choose items, each at most once, with total weight at most {} and the largest total benefit.",
+                    p.with_value(|p| p.capacity)
+                )}
+            </p>
+            <table class="assignment-items">
+                <thead>
+                    <tr><th>"Item"</th><th>"Weight"</th><th>"Benefit"</th></tr>
+                </thead>
+                <tbody>{rows}</tbody>
+            </table>
+            <form class="assignment-form" on:submit=on_submit>
+                <div class="field">
+                    <label for="assignment-optimum">"Optimum"</label>
+                    <input
+                        id="assignment-optimum"
+                        inputmode="numeric"
+                        prop:value=optimum
+                        on:input:target=move |ev| set_optimum.set(ev.target().value())
+                    />
+                </div>
+                <div class="field">
+                    <label for="assignment-items">"Chosen items"</label>
+                    <input
+                        id="assignment-items"
+                        placeholder="e.g. 1, 3"
+                        prop:value=items
+                        on:input:target=move |ev| set_items.set(ev.target().value())
+                    />
+                </div>
+                <div class="btn-row">
+                    <button type="submit" class="btn btn-solve">"Submit"</button>
+                </div>
+            </form>
+            {move || error.get().map(|e| view! { <p class="field-error">{e}</p> })}
+            {move || graded.get().map(|(g, code)| {
+                let capacity = p.with_value(|p| p.capacity);
+                view! {
+                    <div class="assignment-result" class:passed=g.passed() role="status">
+                        <p class="assignment-verdict">
+                            {if g.passed() { "✓ Correct" } else { "✗ Not quite" }}
+                        </p>
+                        <ul>
+                            {g.feedback(capacity).into_iter().map(|line| view! { <li>{line}</li> }).collect_view()}
+                        </ul>
+                        <div class="field">
+                            <label for="assignment-code">"Result code"</label>
+                            <div class="code-toolbar">
+                                <input id="assignment-code" class="result-code" readonly prop:value=code />
+                                <button class="btn-small" on:click=on_copy>
+                                    {move || if copied.get() { "Copied" } else { "Copy" }}
+                                </button>
+                            </div>
+                        </div>
+                        <p class="toolbar-hint">"Hand this code in; it records your answer and when it was graded, and shows if it was edited."</p>
+                    </div>
+                }
+            })}
+        </section>
+    }
+}
+
+/// A teacher's check of a handed-in result code against the instance it was
+/// assigned for, usually the active tab's.
+#[component]
+pub fn VerifyCodePanel(#[prop(into)] problem: Signal<Option<Problem>>) -> impl IntoView {
+    let (code, set_code) = signal(String::new());
+    let verdict = Memo::new(move |_| {
+        let code = code.get();
+        if code.trim().is_empty() {
+            return None;
+        }
+        Some(match problem.get() {
+            Some(p) => verify_code(&p, &code).map(|v| (v, p.capacity)),
+            None => Err("This tab holds no valid instance to check against.".into()),
+        })
+    });
+
+    view! {
+        <section class="verify-card" aria-label="Verify a result code">
+            <div class="field">
+                <label for="verify-code">"Result code"</label>
+                <input
+                    id="verify-code"
+                    class="result-code"
+                    placeholder="KDP2.…"
+                    prop:value=code
+                    on:input:target=move |ev| set_code.set(ev.target().value())
+                />
+            </div>
+            <p class="toolbar-hint">"Checked against this tab's instance."</p>
+            {move || verdict.get().map(|v| match v {
+                Ok((v, capacity)) => {
+                    let when = js_sys::Date::new(&((v.timestamp as f64) * 1000.0).into());
+                    let items = if v.submission.items.is_empty() {
+                        "none".to_string()
+                    } else {
+                        v.submission.items.iter().map(usize::to_string).collect::<Vec<_>>().join(", ")
+                    };
+                    view! {
+                        <div class="assignment-result" class:passed=v.grade.passed() role="status">
+                            <p class="assignment-verdict">
+                                {if v.grade.passed() { "✓ Genuine, correct" } else { "✓ Genuine, not correct" }}
+                            </p>
+                            <ul>
+                                <li>{format!("Graded {}.", String::from(when.to_locale_string("default", &wasm_bindgen::JsValue::UNDEFINED)))}</li>
+                                <li>{format!("Optimum {}, items {items}.", v.submission.optimum)}</li>
+                                {v.grade.feedback(capacity).into_iter().map(|line| view! { <li>{line}</li> }).collect_view()}
+                            </ul>
+                        </div>
+                    }
+                    .into_any()
+                }
+                Err(e) => view! { <p class="field-error">"✗ "{e}</p> }.into_any(),
+            })}
+        </section>
+    }
+}
@@ -0,0 +1,53 @@
+use leptos::prelude::*;
+
+use crate::problem::Problem;
+
+/// A knapsack drawn as a bag whose height is the capacity, filled from the
+/// bottom with one block per packed item, each as tall as its weight.
+#[component]
+pub fn PackingBag(
+    problem: Problem,
+    /// 1-based items currently in the bag, bottom first.
+    #[prop(into)]
+    packed: Signal<Vec<usize>>,
+) -> impl IntoView {
+    let p = StoredValue::new(problem);
+    let pct = move |w: usize| w as f64 * 100.0 / p.with_value(|p| p.capacity.max(1)) as f64;
+
+    let used = move || packed.with(|items| p.with_value(|p| items.iter().map(|&i| p.weights[i - 1]).sum::<usize>()));
+
+    view! {
+        <section class="summary-card bag-card">
+            <h2 class="summary-title">"Packing"</h2>
+            <div class="bag-wrap">
+                <div class="bag" role="img" aria-label="Items packed in the knapsack">
+                    <div class="bag-fill">
+                        <For
+                            each=move || packed.get()
+                            key=|&i| i
+                            children=move |i| {
+                                let (w, label) = p.with_value(|p| (p.weights[i - 1], p.units.weight(p.weights[i - 1])));
+                                view! {
+                                    <div
+                                        class="bag-block"
+                                        style=format!("height: {:.2}%", pct(w))
+                                        title=format!("Item #{i}: {label}")
+                                    >
+                                        <span>{format!("#{i}")}</span>
+                                    </div>
+                                }
+                            }
+                        />
+                    </div>
+                </div>
+                <p class="bag-caption">
+                    {move || p.with_value(|p| format!(
+                        "{} of {} used",
+                        p.units.weight(used()),
+                        p.units.weight(p.capacity),
+                    ))}
+                </p>
+            </div>
+        </section>
+    }
+}
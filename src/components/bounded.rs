@@ -0,0 +1,115 @@
+use leptos::prelude::*;
+
+use crate::components::metrics::use_metrics;
+use crate::format::NumberFormat;
+use crate::metrics::RunMetrics;
+use crate::problem::Problem;
+use crate::solver::{merge_duplicates, solve_bounded, BoundedItem, BoundedSolution};
+use crate::timing::now_ms;
+
+/// Hint shown when some items repeat the same `(weight, benefit)` pair, with
+/// a button that merges them into bounded items (`count` copies each) and
+/// solves that smaller table instead. Hidden when every item is distinct.
+#[component]
+pub fn DuplicateMerge(
+    #[prop(into)] problem: Signal<Option<Problem>>,
+    #[prop(into)] fmt: Signal<NumberFormat>,
+) -> impl IntoView {
+    let metrics = use_metrics();
+    let merged = Memo::new(move |_| {
+        problem.with(|p| {
+            let p = p.as_ref()?;
+            let items = merge_duplicates(p);
+            (items.len() < p.len()).then(|| (p.clone(), items))
+        })
+    });
+    let (result, set_result) = signal(None::<(Problem, Vec<BoundedItem>, BoundedSolution)>);
+
+    let on_merge = move |_| {
+        let Some((p, items)) = merged.get_untracked() else { return };
+        let t0 = now_ms();
+        let s = solve_bounded(&items, p.capacity);
+        metrics.record(RunMetrics {
+            solver: "bounded (merged)",
+            items: items.len(),
+            capacity: p.capacity,
+            best: s.best as u128,
+            cells: Some(items.len() * (p.capacity + 1)),
+            elapsed_ms: Some(now_ms() - t0),
+            ..Default::default()
+        });
+        set_result.set(Some((p, items, s)));
+    };
+
+    let table = move || {
+        // a result for an instance since edited is dropped
+        let current = move |p: &Problem| merged.with(|m| m.as_ref().is_some_and(|(q, _)| q == p));
+        result.get().filter(|(p, ..)| current(p)).map(|(p, items, s)| {
+            let fmt = fmt.get();
+            let rows = items.iter().enumerate().map(|(i, it)| {
+                let row = i + 1;
+                let cells = (0..=p.capacity).map(|c| {
+                    let k = s.copies[row][c];
+                    let cls = if k > 0 { "cell cell-took" } else { "cell" };
+                    view! {
+                        <td class=cls title=format!("T[{row}][{c}] = {}, {k} copies", s.table[row][c])>
+                            {p.units.benefit(s.table[row][c], &fmt)}
+                            {(k > 1).then(|| view! { <sub class="copies">{format!("×{k}")}</sub> })}
+                        </td>
+                    }
+                }).collect_view();
+                view! {
+                    <tr>
+                        <td class="item-header">
+                            {format!("w={} b={} ×{}", p.units.weight(it.weight), p.units.benefit(it.benefit, &fmt), it.count)}
+                        </td>
+                        {cells}
+                    </tr>
+                }
+            }).collect_view();
+            let taken = items.iter().zip(&s.taken)
+                .filter(|(_, k)| **k > 0)
+                .map(|(it, k)| format!("{k} × (w={}, b={})", it.weight, it.benefit))
+                .collect::<Vec<_>>()
+                .join(", ");
+            view! {
+                <p class="complexity-row">
+                    {format!(
+                        "{} bounded rows instead of {} items; optimum {}: {}.",
+                        items.len(), p.len(), p.units.benefit(s.best, &fmt),
+                        if taken.is_empty() { "nothing fits".to_string() } else { taken }
+                    )}
+                </p>
+                <div class="table-wrap">
+                    <table class="dp-table bounded-table" aria-label="Bounded knapsack table">
+                        <thead>
+                            <tr>
+                                <th class="corner">"item \\ w"</th>
+                                {(0..=p.capacity).map(|w| view! { <th>{w}</th> }).collect_view()}
+                            </tr>
+                        </thead>
+                        <tbody>{rows}</tbody>
+                    </table>
+                </div>
+            }
+        })
+    };
+
+    view! {
+        {move || merged.get().map(|(p, items)| {
+            let repeats = items.iter().filter(|it| it.count > 1)
+                .map(|it| format!("(w={}, b={}) ×{}", it.weight, it.benefit, it.count))
+                .collect::<Vec<_>>()
+                .join(", ");
+            view! {
+                <div class="duplicate-hint" role="note">
+                    <span>
+                        {format!("{} duplicate items: {repeats}.", p.len() - items.len())}
+                    </span>
+                    <button class="btn-small" on:click=on_merge>"Merge into bounded items"</button>
+                </div>
+            }
+        })}
+        {table}
+    }
+}
@@ -0,0 +1,89 @@
+use leptos::prelude::*;
+
+use crate::format::NumberFormat;
+use crate::problem::Problem;
+use crate::solver::CardinalitySolution;
+
+/// Layered tables of an "at most k items" solve. A slider picks the layer
+/// `j` on screen; cells of the traceback that fall in that layer are
+/// highlighted. There is no stepping with an item limit.
+#[component]
+pub fn CardinalityTable(problem: Problem, limit: usize, solution: CardinalitySolution, fmt: NumberFormat) -> impl IntoView {
+    let layer = RwSignal::new(limit);
+    let problem = StoredValue::new(problem);
+    let solution = StoredValue::new(solution);
+
+    let table = move || {
+        let j = layer.get();
+        problem.with_value(|p| {
+            solution.with_value(|s| {
+                let rows = s.layers[j].iter().enumerate().map(|(i, row)| {
+                    let label = match i {
+                        0 => "—".to_string(),
+                        i => format!("{i}  w={}", p.units.weight(p.weights[i - 1])),
+                    };
+                    let cells = row.iter().enumerate().map(|(c, &val)| {
+                        let cls = if i == 0 || j == 0 {
+                            "cell cell-base"
+                        } else if s.path.contains(&(j, i, c)) {
+                            "cell cell-backtrack"
+                        } else {
+                            "cell"
+                        };
+                        view! {
+                            <td class=cls title=format!("T{j}[{i}][{c}] = {val}")>{p.units.benefit(val, &fmt)}</td>
+                        }
+                    }).collect_view();
+                    view! {
+                        <tr>
+                            <td class="item-header">{label}</td>
+                            {cells}
+                        </tr>
+                    }
+                }).collect_view();
+                view! {
+                    <table class="dp-table">
+                        <thead>
+                            <tr>
+                                <th class="corner">"item \\ w"</th>
+                                {(0..=p.capacity).map(|w| view! { <th>{w}</th> }).collect_view()}
+                            </tr>
+                        </thead>
+                        <tbody>{rows}</tbody>
+                    </table>
+                }
+            })
+        })
+    };
+
+    let layer_best = move || {
+        let j = layer.get();
+        problem.with_value(|p| solution.with_value(|s| p.units.benefit(s.layers[j][p.len()][p.capacity], &fmt)))
+    };
+
+    view! {
+        <section class="table-wrap cardinality-table" aria-label="DP tables by number of items">
+            <label class="layer-slider">
+                "Layer "<strong class="mono">{move || format!("j = {}", layer.get())}</strong>
+                <input
+                    type="range"
+                    min="0"
+                    max=limit.to_string()
+                    prop:value=move || layer.get().to_string()
+                    on:input:target=move |ev| {
+                        if let Ok(j) = ev.target().value().parse() {
+                            layer.set(j);
+                        }
+                    }
+                />
+                <span>{move || format!("best with at most {} items: ", layer.get())}<strong>{layer_best}</strong></span>
+            </label>
+            {table}
+            <p class="wide-answer">
+                "Optimum "<strong>{solution.with_value(|s| problem.with_value(|p| p.units.benefit(s.best, &fmt)))}</strong>
+                " with items "<strong>{solution.with_value(|s| format!("{:?}", s.chosen))}</strong>
+                {format!(" (at most {limit} items).")}
+            </p>
+        </section>
+    }
+}
@@ -0,0 +1,93 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::codegen::{generate, rust_step_lines, Language};
+use crate::components::active_cell::use_active_cell;
+use crate::components::toast::use_toasts;
+use crate::files::{copy_text, download_text};
+use crate::problem::Problem;
+
+/// Ready-to-run solver source for the current instance, in a chosen language.
+/// In Rust, the lines run for the active cell of the enclosing visualizer
+/// are highlighted.
+#[component]
+pub fn CodePanel(
+    /// The instance on screen, or the form's instance before solving.
+    #[prop(into)]
+    problem: Signal<Option<Problem>>,
+    /// Start expanded, as in the split view.
+    #[prop(optional)]
+    open: bool,
+) -> impl IntoView {
+    let toasts = use_toasts();
+    let active = use_active_cell();
+    let (lang, set_lang) = signal(Language::default());
+    let source = Memo::new(move |_| problem.with(|p| p.as_ref().map(|p| generate(lang.get(), p))));
+
+    let on_copy = move |_| {
+        let Some(text) = source.get_untracked() else { return };
+        spawn_local(async move {
+            match copy_text(&text).await {
+                Ok(()) => toasts.info("Code copied to the clipboard."),
+                Err(_) => toasts.warn("Could not copy to the clipboard."),
+            }
+        });
+    };
+    let on_download = move |_| {
+        let Some(text) = source.get_untracked() else { return };
+        if download_text(lang.get_untracked().filename(), "text/plain", &text).is_err() {
+            toasts.warn("Could not start the download.");
+        }
+    };
+
+    view! {
+        <details class="settings-card code-card" open=open>
+            <summary class="settings-title">"Code"</summary>
+            <div class="code-toolbar">
+                <select
+                    aria-label="Language"
+                    on:change:target=move |ev| {
+                        if let Some(l) = Language::from_code(&ev.target().value()) {
+                            set_lang.set(l);
+                        }
+                    }
+                >
+                    {Language::ALL.into_iter().map(|l| view! {
+                        <option value=l.code() selected=move || lang.get() == l>{l.label()}</option>
+                    }).collect_view()}
+                </select>
+                <button class="btn-small" on:click=on_copy disabled=move || source.with(Option::is_none)>
+                    "Copy"
+                </button>
+                <button class="btn-small" on:click=on_download disabled=move || source.with(Option::is_none)>
+                    "Download"
+                </button>
+            </div>
+            {move || match source.get() {
+                Some(code) => {
+                    let step = active.and_then(|a| a.step.get()).filter(|_| lang.get() == Language::Rust);
+                    let marked = step.map(rust_step_lines);
+                    let lines = code.lines().enumerate().map(|(n, line)| {
+                        let role = marked.and_then(|m| m.iter().position(|&l| l == n));
+                        let note = match (role, step) {
+                            (Some(0), Some(s)) => format!("  ← i = {}", s.row),
+                            (Some(1), Some(s)) => format!("  ← w = {}", s.col),
+                            (Some(_), Some(s)) => format!("  ← {}", s.branch.label()),
+                            _ => String::new(),
+                        };
+                        view! {
+                            <span class=if role.is_some() { "code-line active" } else { "code-line" }>
+                                {line.to_string()}
+                                <span class="code-note">{note}</span>
+                            </span>
+                        }
+                    }).collect_view();
+                    view! { <pre class="code-block"><code>{lines}</code></pre> }.into_any()
+                }
+                None => view! {
+                    <p class="complexity-row">"Enter a valid instance to generate code."</p>
+                }.into_any(),
+            }}
+        </details>
+    }
+}
@@ -0,0 +1,49 @@
+use leptos::prelude::*;
+
+use crate::format::format_bytes;
+use crate::solver::{headless_bytes, table_bytes};
+
+/// O(n·W) time/space with the current instance's numbers substituted, the
+/// estimated memory before solving and the real allocation afterwards.
+#[component]
+pub fn ComplexityReadout(
+    /// `(n, capacity)` of the instance currently in the form, if it parses.
+    #[prop(into)]
+    draft: Signal<Option<(usize, usize)>>,
+    /// Bytes held by the table on screen, if one has been built.
+    #[prop(into)]
+    allocated: Signal<Option<usize>>,
+) -> impl IntoView {
+    view! {
+        <div class="complexity">
+            {move || match draft.get() {
+                None => view! {
+                    <p class="complexity-row">"Enter a valid instance to see its cost."</p>
+                }.into_any(),
+                Some((n, cap)) => {
+                    let cols = cap + 1;
+                    view! {
+                        <p class="complexity-row">
+                            <span class="complexity-label">"Time  O(n·W)"</span>
+                            {format!("n = {n}, W = {cap}  →  {n} × {cols} = {} cell updates", n * cols)}
+                        </p>
+                        <p class="complexity-row">
+                            <span class="complexity-label">"Space O(n·W)"</span>
+                            {format!(
+                                "≈ {} for the table  ·  ≈ {} solve-only",
+                                format_bytes(table_bytes(n, cap)),
+                                format_bytes(headless_bytes(n, cap))
+                            )}
+                        </p>
+                    }.into_any()
+                }
+            }}
+            {move || allocated.get().map(|bytes| view! {
+                <p class="complexity-row">
+                    <span class="complexity-label">"Allocated"</span>
+                    {format!("{} held by the current table", format_bytes(bytes))}
+                </p>
+            })}
+        </div>
+    }
+}
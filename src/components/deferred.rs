@@ -0,0 +1,11 @@
+use leptos::prelude::*;
+
+/// Builds its children the first time `when` is true and keeps them from
+/// then on, so views that start hidden cost nothing until they are shown.
+#[component]
+pub fn Deferred(#[prop(into)] when: Signal<bool>, children: Children) -> impl IntoView {
+    let children = StoredValue::new_local(Some(children));
+    // latches: once shown, stays built
+    let shown = Memo::new(move |was: Option<&bool>| was.copied().unwrap_or(false) || when.get());
+    move || shown.get().then(|| children.try_update_value(Option::take).flatten().map(|c| c()))
+}
@@ -0,0 +1,40 @@
+use leptos::prelude::*;
+
+use crate::analysis::degeneracies;
+use crate::problem::Problem;
+
+/// One dismissible banner per degenerate property of the instance, so a
+/// table of zeros or a trivial optimum comes with its explanation. Dismissed
+/// banners come back once the instance changes.
+#[component]
+pub fn DegenerateBanners(#[prop(into)] problem: Signal<Option<Problem>>) -> impl IntoView {
+    let dismissed = RwSignal::new(Vec::<&'static str>::new());
+    Effect::new(move |_| {
+        problem.track();
+        dismissed.set(Vec::new());
+    });
+
+    move || {
+        problem.get().map(|p| {
+            degeneracies(&p)
+                .into_iter()
+                .filter(|d| !dismissed.with(|ds| ds.contains(&d.code())))
+                .map(|d| {
+                    let code = d.code();
+                    view! {
+                        <div class="degenerate-banner" role="status">
+                            <p>{d.describe(&p)}</p>
+                            <button
+                                class="btn-small"
+                                aria-label="Dismiss"
+                                on:click=move |_| dismissed.update(|ds| ds.push(code))
+                            >
+                                "×"
+                            </button>
+                        </div>
+                    }
+                })
+                .collect_view()
+        })
+    }
+}
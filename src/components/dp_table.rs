@@ -0,0 +1,491 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use leptos::prelude::*;
+
+use crate::columns::{ColumnFilter, ColumnGroup};
+use crate::display::Density;
+use crate::focus::{id_scope, IdScope};
+use crate::io::CellNotes;
+use crate::problem::Problem;
+use crate::settings::Settings;
+use crate::solver::{check_row, weight_used, InvariantViolation, Simulation, TraceHop};
+
+use super::drawing::{DrawingLayer, Stroke};
+
+/// DOM id of the table cell at item row `i`, capacity `c`.
+pub fn cell_id(scope: IdScope, i: usize, c: usize) -> String {
+    scope.id(&format!("cell-{i}-{c}"))
+}
+
+/// The DP table, one row per item under the "0 items" baseline, with the
+/// complexity annotations and the drawing layer around it. It is built once
+/// per instance and display settings; each step only updates the cells
+/// whose look it changes.
+#[component]
+pub fn DpTable(
+    table: Arc<Vec<Vec<usize>>>,
+    problem: Problem,
+    /// Display options as they were when the table was built.
+    settings: Settings,
+    /// Capacity columns, with runs folded away by the column filter.
+    groups: Memo<Vec<ColumnGroup>>,
+    /// Divisor of the gcd-scaled instance on screen; 1 when unscaled.
+    scale: usize,
+    /// `breakpoints[w]`: some subset of the weights sums to `w`.
+    breakpoints: Vec<bool>,
+    sim: RwSignal<Simulation>,
+    trace_marks: Memo<TraceMarks>,
+    active_pos: Memo<Option<(usize, usize)>>,
+    /// `(row, weight)` of the item whose weight is under the pointer.
+    hover_weight: ReadSignal<Option<(usize, usize)>>,
+    set_hover_weight: WriteSignal<Option<(usize, usize)>>,
+    /// Item row singled out with the row it reads from.
+    focused_row: ReadSignal<Option<usize>>,
+    set_focused_row: WriteSignal<Option<usize>>,
+    /// Capacity columns the user expanded out of collapsed groups.
+    expanded_cols: RwSignal<HashSet<usize>>,
+    notes: RwSignal<CellNotes>,
+    /// Double-click on a cell, with its `(row, col)`.
+    on_edit_note: Callback<(usize, usize)>,
+    drawing: ReadSignal<bool>,
+    strokes: RwSignal<Vec<Stroke>>,
+    /// Id of the element a capture renders.
+    capture_id: String,
+) -> impl IntoView {
+    let scope = id_scope();
+    let p = problem;
+    let cap = p.capacity;
+    let ws = p.weights.clone();
+    let bs = p.benefits.clone();
+    let n = ws.len(); // number of items
+    let n_cols = cap + 1;
+    let units = p.units.clone();
+    let w_symbol = units.weight_symbol().to_string();
+    let fmt = settings.number_format;
+    let show_complexity = settings.show_complexity_overlay;
+    let show_ordinals = settings.show_fill_ordinals;
+    let show_deltas = settings.show_row_deltas;
+    let shade = settings.shade_by_optimum;
+    let optimum = table.last().and_then(|row| row.last()).copied().unwrap_or(0);
+    let column_filter = settings.column_filter;
+    let density = settings.density;
+    let reveal_ms = settings.reveal_ms;
+    let check_invariants = settings.check_invariants;
+    let breakpoints = Arc::new(breakpoints);
+    // row here is 1-based item row (row 0 is always shown)
+    let is_visible = move |row: usize, col: usize| -> bool { sim.with(|s| s.is_revealed(row, col)) };
+    // Hovering item r's weight w marks column w, where the item first
+    // fits, and the cells of row r - 1 its row reads, shifted by w
+    let col_hover = move |c: usize| move || hover_weight.get().is_some_and(|(_, w)| w == c);
+    let row_dim = move |row: usize| move || focused_row.get().is_some_and(|r| row != r && row + 1 != r);
+    let shift_source = move |row: usize, c: usize| move || {
+        hover_weight.get().is_some_and(|(r, w)| row + 1 == r && c + w <= cap)
+    };
+
+    let header_cell = {
+        let units = units.clone();
+        let breakpoints = breakpoints.clone();
+        move |g: ColumnGroup| {
+            let header_cls = if let ColumnGroup::Shown(w) = g && w > 0 && breakpoints[w] {
+                "w-header breakpoint"
+            } else {
+                "w-header"
+            };
+            match g {
+                ColumnGroup::Shown(w) if scale > 1 => view! {
+                    <th
+                        class=header_cls
+                        class:col-hover=col_hover(w)
+                        title=format!("scaled {} = original {}", w, w * scale)>
+                        {w}
+                        <span class="w-original">{format!("×{scale}={}", w * scale)}</span>
+                    </th>
+                }.into_any(),
+                ColumnGroup::Shown(w) => view! {
+                    <th class=header_cls class:col-hover=col_hover(w) title=units.weight(w)>{w}</th>
+                }.into_any(),
+                ColumnGroup::Collapsed(r) => {
+                    let title = if column_filter == ColumnFilter::Breakpoints {
+                        format!(
+                            "Capacities {}–{} repeat column {}; click to show",
+                            r.start, r.end - 1, r.start.saturating_sub(1)
+                        )
+                    } else {
+                        format!("Show capacities {}–{}", r.start, r.end - 1)
+                    };
+                    view! {
+                        <th class="w-collapsed">
+                            <button
+                                class="expand-btn"
+                                title=title.clone()
+                                aria-label=title
+                                on:click=move |_| expanded_cols.update(|cols| cols.extend(r.clone()))
+                            >
+                                "⋯"
+                            </button>
+                        </th>
+                    }.into_any()
+                }
+            }
+        }
+    };
+
+    // One data cell. Its look lives in a memo of its own and each of its
+    // classes is toggled on the same node, so a step only touches the
+    // classes of the cells whose look it changes.
+    let data_cell = {
+        let table = table.clone();
+        let units = units.clone();
+        let (ws, bs) = (ws.clone(), bs.clone());
+        move |i: usize, violations: Option<Memo<Vec<InvariantViolation>>>, g: ColumnGroup| {
+            let c = match g {
+                ColumnGroup::Shown(c) => c,
+                ColumnGroup::Collapsed(_) => {
+                    return view! { <td class="cell-collapsed"></td> }.into_any();
+                }
+            };
+            let (wi, bi) = (ws[i - 1], bs[i - 1]);
+            let val = table[i][c];
+
+            // Did we take the item in this cell?
+            let took_item = wi <= c
+                && val == table[i-1][c - wi] + bi
+                && val > table[i-1][c];
+            let look = Memo::new(move |_| trace_marks.with(|marks| {
+                CellLook::of(is_visible(i, c), active_pos.get() == Some((i, c)), marks, (i, c), took_item)
+            }));
+            let starred = Memo::new(move |_| trace_marks.with(|marks| marks.taken.contains(&(i, c))));
+            // cells drawn hidden fade in as they are revealed; those
+            // drawn revealed, when the table is rebuilt, do not
+            let enters = !look.get_untracked().visible();
+            let violated = Memo::new(move |_| {
+                violations.is_some_and(|v| v.with(|v| v.iter().any(|v| v.cell() == (i, c))))
+            });
+
+            // gain of item i at this capacity, for the delta view
+            let delta = val.saturating_sub(table[i - 1][c]);
+            let gain = delta > 0;
+            let pct = (val as u128 * 100).checked_div(optimum as u128).unwrap_or(0);
+
+            // exact value on hover, whatever the display format
+            let tooltip = format!(
+                "dp[{}][{}] = {}  (raw {}, capacity {}){}",
+                i, c, units.benefit(val, &fmt), val, units.weight(c),
+                if shade { format!(", {pct}% of the optimum") } else { String::new() }
+            );
+            let text = match show_deltas {
+                true if gain => format!("+{}", fmt.format(delta)),
+                true => "·".to_string(),
+                false => fmt.format(val),
+            };
+
+            view! {
+                <td
+                    id=cell_id(scope, i, c)
+                    class="cell"
+                    class:cell-hidden=move || !look.get().visible()
+                    class:cell-active=move || look.get() == CellLook::Active
+                    class:cell-backtrack=move || look.get().taken()
+                    class:cell-traced=move || look.get().traced()
+                    class:trace-head=move || look.get().head()
+                    class:cell-took=move || look.get() == CellLook::Took
+                    class:cell-enter=move || enters && look.get().visible()
+                    class:cell-violation=violated
+                    class:delta-gain=move || show_deltas && gain && look.get().visible()
+                    class:delta-zero=move || show_deltas && !gain && look.get().visible()
+                    class:cell-shaded=move || shade && look.get().visible()
+                    class:col-hover=col_hover(c)
+                    class:shift-source=shift_source(i, c)
+                    class:shift-nofit=move || hover_weight.get().is_some_and(|(r, w)| r == i && c < w)
+                    style=move || (shade && look.get().visible()).then(|| format!("--pct: {pct}%"))
+                    title=move || look.get().visible().then(|| tooltip.clone())
+                    on:dblclick=move |_| on_edit_note.run((i, c))
+                >
+                {move || notes.with(|n| n.get(&(i, c)).cloned()).map(|note| view! {
+                    <span class="note-marker" aria-label="Note"></span>
+                    <span class="note-text" role="note">{note}</span>
+                })}
+                {show_ordinals.then(|| {
+                    let ordinal = Memo::new(move |_| sim.with(|s| s.order().ordinal(i, c)));
+                    view! { <span class="cell-ordinal">{move || ordinal.get() + 1}</span> }
+                })}
+                {move || if look.get().visible() { text.clone() } else { String::new() }}
+                {move || starred.get().then(|| view! { <span class="star">"★"</span> })}
+                </td>
+            }.into_any()
+        }
+    };
+
+    view! {
+        // ── Complexity annotations ─────────────────────────
+        {show_complexity.then(|| view! {
+            <p class="cx-banner">
+                "n × (W+1) = "{n}" × "{n_cols}" = "
+                <strong>{n * n_cols}</strong>" cells"
+            </p>
+            <div class="cx-axis-top">
+                {format!("←  W + 1 = {n_cols} capacity states  →")}
+            </div>
+        })}
+
+        <div class="cx-body">
+        {show_complexity.then(|| view! {
+            <div class="cx-axis-left">{format!("←  n = {n} iterations  →")}</div>
+        })}
+        <div class="draw-host" id=capture_id>
+        <DrawingLayer active=drawing strokes=strokes />
+        <table class=format!("dp-table {}", density.class()) style=format!("--reveal: {reveal_ms}ms")>
+            <thead>
+                <tr>
+                    // top-left corner: "item \ w"
+                    <th class="corner">
+                        "item \\ w"
+                        {(!w_symbol.is_empty()).then(|| view! {
+                            <span class="unit">" ("{w_symbol.clone()}")"</span>
+                        })}
+                    </th>
+                    // one column per capacity value 0..=m, minus collapsed runs
+                    <For each=move || groups.get() key=|g| g.clone() children=header_cell />
+                </tr>
+            </thead>
+            <tbody>
+                // Row 0: the "no items" baseline (always fully visible)
+                <tr class="row-base" class:row-dim=row_dim(0) class:row-focus=move || focused_row.get() == Some(1)>
+                    <td class="item-header">
+                        <span class="item-badge">"—"</span>
+                        <span class="item-meta">"base"</span>
+                    </td>
+                    <For each=move || groups.get() key=|g| g.clone() let:g>
+                        {match g {
+                            ColumnGroup::Shown(c) => view! {
+                                <td
+                                    id=cell_id(scope, 0, c)
+                                    class="cell cell-base"
+                                    class:col-hover=col_hover(c)
+                                    class:shift-source=shift_source(0, c)
+                                >
+                                    "0"
+                                </td>
+                            }.into_any(),
+                            ColumnGroup::Collapsed(_) => view! { <td class="cell-collapsed"></td> }.into_any(),
+                        }}
+                    </For>
+                </tr>
+
+                // Rows 1..=n: one per item
+                {(1..=n).map(|i| {
+                    let wi = ws[i - 1];
+                    let bi = bs[i - 1];
+                    let violations = check_invariants.then(|| {
+                        let table = table.clone();
+                        Memo::new(move |_| check_row(&table, i, |c| is_visible(i, c)))
+                    });
+                    let row_status = violations.map(|violations| {
+                        let table = table.clone();
+                        let status = Memo::new(move |_| violations.with(|violations| if violations.is_empty() {
+                            ("inv-status ok", "✓", "Row is non-decreasing and ≥ the row above".to_string())
+                        } else {
+                            ("inv-status bad", "✗", violations.iter()
+                                .map(|v| v.describe(&table))
+                                .collect::<Vec<_>>()
+                                .join("\n"))
+                        }));
+                        view! {
+                            <td class=move || status.get().0 title=move || status.get().2>{move || status.get().1}</td>
+                        }
+                    });
+                    let data_cell = data_cell.clone();
+                    view! {
+                        <tr class:row-dim=row_dim(i) class:row-focus=move || focused_row.get().is_some_and(|r| r == i || r == i + 1)>
+                            // item header column; a click focuses the row
+                            <td
+                                class="item-header focusable"
+                                title="Click to focus this row and the row above it"
+                                on:click=move |_| set_focused_row.update(|r| *r = if *r == Some(i) { None } else { Some(i) })
+                            >
+                                <span class="item-badge">{i}</span>
+                                <span class="item-meta">
+                                    "w="<strong
+                                        class="w-hoverable"
+                                        title=format!(
+                                            "Item {i} first fits at w = {wi}; every cell from there reads dp[{}][w − {wi}]",
+                                            i - 1
+                                        )
+                                        on:mouseenter=move |_| set_hover_weight.set(Some((i, wi)))
+                                        on:mouseleave=move |_| set_hover_weight.set(None)
+                                    >
+                                        {units.weight(wi)}
+                                    </strong>
+                                    " b="<strong>{units.benefit(bi, &fmt)}</strong>
+                                </span>
+                            </td>
+                            // data cells, kept across steps and column expansion
+                            <For
+                                each=move || groups.get()
+                                key=|g| g.clone()
+                                children=move |g| data_cell(i, violations, g)
+                            />
+                            {row_status}
+                        </tr>
+                    }
+                }).collect_view()}
+            </tbody>
+        </table>
+        </div>
+        </div>
+    }
+}
+
+/// A second table over the same cells: the weight each cell's best subset
+/// actually uses, with the cells that leave the bag partly empty marked.
+#[component]
+pub fn WeightUsedTable(
+    table: Arc<Vec<Vec<usize>>>,
+    weights: Vec<usize>,
+    density: Density,
+    groups: Memo<Vec<ColumnGroup>>,
+    sim: RwSignal<Simulation>,
+) -> impl IntoView {
+    let n = weights.len();
+    let n_cols = table[0].len();
+    let is_visible = move |row: usize, col: usize| -> bool { sim.with(|s| s.is_revealed(row, col)) };
+    let used = Arc::new(weight_used(&table, &weights));
+    let slack_cells = {
+        let used = used.clone();
+        move || (1..=n)
+            .flat_map(|i| (0..n_cols).map(move |c| (i, c)))
+            .filter(|&(i, c)| is_visible(i, c) && used[i][c] < c)
+            .count()
+    };
+    view! {
+        <div class="table-wrap weight-used">
+            <p class="toolbar-hint">
+                {move || format!(
+                    "Weight actually used by each cell's best subset; {} revealed cells leave the bag partly empty.",
+                    slack_cells()
+                )}
+            </p>
+            <table class=format!("dp-table {}", density.class()) aria-label="Weight used">
+                <thead>
+                    <tr>
+                        <th class="corner">"item \\ w"</th>
+                        <For each=move || groups.get() key=|g| g.clone() let:g>
+                            {match g {
+                                ColumnGroup::Shown(w) => view! { <th>{w}</th> }.into_any(),
+                                ColumnGroup::Collapsed(_) => view! { <th class="w-collapsed"></th> }.into_any(),
+                            }}
+                        </For>
+                    </tr>
+                </thead>
+                <tbody>
+                    {(1..=n).map(|i| {
+                        let used = used.clone();
+                        view! {
+                            <tr>
+                                <td class="item-header"><span class="item-badge">{i}</span></td>
+                                <For each=move || groups.get() key=|g| g.clone() let:g>
+                                    {match g {
+                                        ColumnGroup::Shown(c) => {
+                                            let u = used[i][c];
+                                            let shown = Memo::new(move |_| is_visible(i, c));
+                                            let slack = u < c;
+                                            view! {
+                                                <td
+                                                    class="cell"
+                                                    class:cell-hidden=move || !shown.get()
+                                                    class:used-slack=move || shown.get() && slack
+                                                    class:used-full=move || shown.get() && !slack
+                                                    title=move || shown.get().then(|| format!("dp[{i}][{c}] uses {u} of {c}"))
+                                                >
+                                                    {move || shown.get().then_some(u)}
+                                                </td>
+                                            }.into_any()
+                                        }
+                                        ColumnGroup::Collapsed(_) => view! { <td class="cell-collapsed"></td> }.into_any(),
+                                    }}
+                                </For>
+                            </tr>
+                        }
+                    }).collect_view()}
+                </tbody>
+            </table>
+        </div>
+    }
+}
+
+/// Cells of the traceback shown so far: the taken ones, the skipped ones
+/// while it is being stepped, and the hop it stands on.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TraceMarks {
+    taken: HashSet<(usize, usize)>,
+    skipped: HashSet<(usize, usize)>,
+    head: Option<(usize, usize)>,
+}
+
+impl TraceMarks {
+    pub fn of(hops: &[TraceHop], traced: Option<usize>) -> Self {
+        let walked = &hops[..traced.unwrap_or(hops.len()).min(hops.len())];
+        let cells = |taken: bool| walked.iter().filter(|hop| hop.taken == taken).map(|hop| (hop.row, hop.col)).collect();
+        TraceMarks {
+            taken: cells(true),
+            skipped: if traced.is_some() { cells(false) } else { HashSet::new() },
+            head: traced.and_then(|k| k.checked_sub(1)).and_then(|k| hops.get(k)).map(|hop| (hop.row, hop.col)),
+        }
+    }
+}
+
+/// How a data cell is drawn at the current step.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CellLook {
+    Hidden,
+    Active,
+    TakenHead,
+    TracedHead,
+    Taken,
+    Traced,
+    Took,
+    Plain,
+}
+
+impl CellLook {
+    fn of(visible: bool, active: bool, marks: &TraceMarks, cell: (usize, usize), took_item: bool) -> Self {
+        let taken = marks.taken.contains(&cell);
+        let head = marks.head == Some(cell);
+        if !visible {
+            CellLook::Hidden
+        } else if active {
+            CellLook::Active
+        } else if head && taken {
+            CellLook::TakenHead
+        } else if head {
+            CellLook::TracedHead
+        } else if taken {
+            CellLook::Taken
+        } else if marks.skipped.contains(&cell) {
+            CellLook::Traced
+        } else if took_item {
+            CellLook::Took
+        } else {
+            CellLook::Plain
+        }
+    }
+
+    /// Part of the traceback's chosen path.
+    fn taken(self) -> bool {
+        matches!(self, CellLook::Taken | CellLook::TakenHead)
+    }
+
+    /// Walked by the traceback without taking its item.
+    fn traced(self) -> bool {
+        matches!(self, CellLook::Traced | CellLook::TracedHead)
+    }
+
+    fn head(self) -> bool {
+        matches!(self, CellLook::TakenHead | CellLook::TracedHead)
+    }
+
+    fn visible(self) -> bool {
+        self != CellLook::Hidden
+    }
+}
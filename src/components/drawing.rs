@@ -0,0 +1,87 @@
+use leptos::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::CanvasRenderingContext2d;
+
+const STROKE_COLOR: &str = "#e8c84a";
+const STROKE_WIDTH: f64 = 3.0;
+
+/// One freehand line, in CSS pixels relative to the drawing layer.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Stroke(pub Vec<(f64, f64)>);
+
+/// Paint `strokes` onto `ctx`, offset by `(dx, dy)`.
+pub fn draw_strokes(ctx: &CanvasRenderingContext2d, strokes: &[Stroke], dx: f64, dy: f64) {
+    ctx.set_stroke_style_str(STROKE_COLOR);
+    ctx.set_line_width(STROKE_WIDTH);
+    ctx.set_line_cap("round");
+    ctx.set_line_join("round");
+    for Stroke(points) in strokes {
+        let Some(&(x, y)) = points.first() else { continue };
+        ctx.begin_path();
+        ctx.move_to(x + dx, y + dy);
+        for &(x, y) in &points[1..] {
+            ctx.line_to(x + dx, y + dy);
+        }
+        ctx.stroke();
+    }
+}
+
+pub fn context_2d(canvas: &web_sys::HtmlCanvasElement) -> Option<CanvasRenderingContext2d> {
+    canvas.get_context("2d").ok().flatten()?.dyn_into().ok()
+}
+
+/// A transparent canvas laid over the table for circling cells and drawing
+/// arrows. Pointer input is only captured while `active`; the strokes stay
+/// visible either way.
+#[component]
+pub fn DrawingLayer(#[prop(into)] active: Signal<bool>, strokes: RwSignal<Vec<Stroke>>) -> impl IntoView {
+    let canvas = NodeRef::<leptos::html::Canvas>::new();
+    let drawing = StoredValue::new(false);
+
+    // Match the bitmap to the element's size and repaint every stroke
+    let redraw = move || {
+        let Some(c) = canvas.get() else { return };
+        let (w, h) = (c.client_width().max(0) as u32, c.client_height().max(0) as u32);
+        if c.width() != w || c.height() != h {
+            c.set_width(w);
+            c.set_height(h);
+        }
+        let Some(ctx) = context_2d(&c) else { return };
+        ctx.clear_rect(0.0, 0.0, w as f64, h as f64);
+        strokes.with(|s| draw_strokes(&ctx, s, 0.0, 0.0));
+    };
+    Effect::new(move |_| {
+        strokes.track();
+        redraw();
+    });
+
+    let point = |ev: &leptos::ev::PointerEvent| (ev.offset_x() as f64, ev.offset_y() as f64);
+
+    view! {
+        <canvas
+            node_ref=canvas
+            class=move || if active.get() { "draw-layer active" } else { "draw-layer" }
+            on:pointerdown=move |ev| {
+                if !active.get_untracked() {
+                    return;
+                }
+                drawing.set_value(true);
+                if let Some(c) = canvas.get() {
+                    _ = c.set_pointer_capture(ev.pointer_id());
+                }
+                strokes.update(|s| s.push(Stroke(vec![point(&ev)])));
+            }
+            on:pointermove=move |ev| {
+                if drawing.get_value() {
+                    strokes.update(|s| {
+                        if let Some(Stroke(points)) = s.last_mut() {
+                            points.push(point(&ev));
+                        }
+                    });
+                }
+            }
+            on:pointerup=move |_| drawing.set_value(false)
+            on:pointercancel=move |_| drawing.set_value(false)
+        />
+    }
+}
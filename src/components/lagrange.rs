@@ -0,0 +1,125 @@
+use leptos::prelude::*;
+
+use crate::lagrange::{breakpoints, relax, tightest};
+use crate::problem::Problem;
+use crate::solver::solve_headless;
+
+const PLOT_W: f64 = 320.0;
+const PLOT_H: f64 = 120.0;
+/// Slider positions between 0 and the largest ratio (plus a margin).
+const SLIDER_STEPS: usize = 400;
+
+/// Lagrangian bound explorer: a slider sets the multiplier λ on the
+/// capacity constraint; the panel shows `L(λ)`, the relaxed selection and
+/// the curve of `L` with its minimum marked against the DP optimum, whose
+/// distance to it is the duality gap.
+#[component]
+pub fn LagrangePanel(#[prop(into)] problem: Signal<Option<Problem>>) -> impl IntoView {
+    let lambda = RwSignal::new(0.0f64);
+    // (breakpoints, largest λ on the slider, tightest relaxation, optimum)
+    let shape = Memo::new(move |_| {
+        problem.with(|p| {
+            p.as_ref().map(|p| {
+                let points = breakpoints(p);
+                let top = points.last().copied().unwrap_or(0.0).max(1.0) * 1.25;
+                (points, top, tightest(p), solve_headless(p).best)
+            })
+        })
+    });
+
+    let body = move || {
+        let (p, (points, top, best, optimum)) = (problem.get()?, shape.get()?);
+        let at = relax(&p, lambda.get());
+
+        // curve through the breakpoints, which is exact since L is piecewise linear
+        let mut xs = points.clone();
+        xs.push(top);
+        let curve: Vec<(f64, f64)> = xs.iter().map(|&l| (l, relax(&p, l).bound)).collect();
+        let y_max = curve.iter().map(|&(_, y)| y).fold(optimum as f64, f64::max).max(1.0);
+        let px = move |l: f64| l / top * PLOT_W;
+        let py = move |y: f64| PLOT_H - y / y_max * PLOT_H;
+        let polyline = curve.iter().map(|&(l, y)| format!("{:.1},{:.1}", px(l), py(y))).collect::<Vec<_>>().join(" ");
+
+        let feasible = at.weight <= p.capacity;
+        Some(view! {
+            <p class="complexity-row">
+                {format!("λ = {:.3}:  L(λ) = {:.2}", at.lambda, at.bound)}
+                {format!("  ≥ optimum {optimum}")}
+            </p>
+            <p class="complexity-row">
+                "Relaxed selection "<strong>{format!("{:?}", at.selection)}</strong>
+                {format!(
+                    ", weight {} of {} ({})",
+                    at.weight,
+                    p.capacity,
+                    if feasible { "fits" } else { "over capacity" }
+                )}
+            </p>
+            <svg
+                class="lg-plot"
+                viewBox=format!("0 0 {PLOT_W} {PLOT_H}")
+                role="img"
+                aria-label="Lagrangian bound as a function of lambda"
+            >
+                <line
+                    class="lg-optimum"
+                    x1="0" x2=PLOT_W.to_string()
+                    y1=py(optimum as f64).to_string() y2=py(optimum as f64).to_string()
+                />
+                <polyline class="lg-curve" points=polyline />
+                <line
+                    class="lg-lambda"
+                    x1=px(at.lambda).to_string() x2=px(at.lambda).to_string()
+                    y1="0" y2=PLOT_H.to_string()
+                />
+                <circle class="lg-best" cx=px(best.lambda).to_string() cy=py(best.bound).to_string() r="4">
+                    <title>{format!("tightest bound {:.2} at λ = {:.3}", best.bound, best.lambda)}</title>
+                </circle>
+            </svg>
+            <p class="toolbar-hint">
+                {format!(
+                    "Tightest bound {:.2} at λ* = {:.3} (the LP bound); duality gap {:.2}.",
+                    best.bound, best.lambda, best.bound - optimum as f64
+                )}
+            </p>
+        })
+    };
+
+    view! {
+        <details class="settings-card lg-card">
+            <summary class="settings-title">"Lagrangian bound"</summary>
+            <div class="code-toolbar">
+                <label class="layer-slider">
+                    <strong class="mono">"λ"</strong>
+                    <input
+                        type="range"
+                        min="0"
+                        max=SLIDER_STEPS.to_string()
+                        prop:value=move || {
+                            let top = shape.with(|s| s.as_ref().map_or(1.0, |s| s.1));
+                            ((lambda.get() / top * SLIDER_STEPS as f64).round() as usize).to_string()
+                        }
+                        on:input:target=move |ev| {
+                            let top = shape.with_untracked(|s| s.as_ref().map_or(1.0, |s| s.1));
+                            if let Ok(step) = ev.target().value().parse::<f64>() {
+                                lambda.set(step / SLIDER_STEPS as f64 * top);
+                            }
+                        }
+                    />
+                </label>
+                <button
+                    class="btn-small"
+                    disabled=move || shape.with(Option::is_none)
+                    on:click=move |_| {
+                        if let Some(best) = shape.with_untracked(|s| s.as_ref().map(|s| s.2.lambda)) {
+                            lambda.set(best);
+                        }
+                    }
+                >
+                    "Go to λ*"
+                </button>
+            </div>
+            {body}
+        </details>
+    }
+}
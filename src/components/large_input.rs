@@ -0,0 +1,36 @@
+use leptos::prelude::*;
+
+use crate::focus::id_scope;
+
+/// Asks what to do with an instance too large to draw as a table.
+#[component]
+pub fn LargeInputDialog(
+    count: usize,
+    limit: usize,
+    on_truncate: Callback<()>,
+    on_solve_only: Callback<()>,
+    on_cancel: Callback<()>,
+) -> impl IntoView {
+    let scope = id_scope();
+    let id = move |base: &str| scope.id(base);
+    view! {
+        <div class="dialog-backdrop">
+            <div class="dialog" role="dialog" aria-modal="true" aria-labelledby=id("large-input-title")>
+                <h2 id=id("large-input-title") class="dialog-title">"Large instance"</h2>
+                <p>
+                    "You entered "<strong>{count}</strong>" items. Tables with more than "
+                    {limit}" rows are hard to follow and slow to draw."
+                </p>
+                <div class="btn-row">
+                    <button class="btn btn-step" on:click=move |_| on_truncate.run(())>
+                        {format!("Visualize first {limit}")}
+                    </button>
+                    <button class="btn btn-solve" on:click=move |_| on_solve_only.run(())>
+                        "Solve without table"
+                    </button>
+                    <button class="btn btn-ghost" on:click=move |_| on_cancel.run(())>"Cancel"</button>
+                </div>
+            </div>
+        </div>
+    }
+}
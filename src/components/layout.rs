@@ -0,0 +1,103 @@
+use leptos::prelude::*;
+
+use crate::components::deferred::Deferred;
+use crate::display::Panel;
+use crate::settings::Settings;
+
+const DRAG_TYPE: &str = "text/plain";
+
+/// One movable section of the page. Dragging its handle onto another panel
+/// moves it to that place, its body resizes from the bottom-right corner and
+/// the chevron folds it to its title bar; all three are saved in the
+/// settings' layout.
+#[component]
+pub fn LayoutPanel(
+    panel: Panel,
+    settings: RwSignal<Settings>,
+    /// Extra class for the wrapper, e.g. its place in the split view.
+    #[prop(optional)]
+    class: &'static str,
+    /// Build the body only once the panel is first expanded. Not for panels
+    /// whose elements are looked up by id while folded.
+    #[prop(optional)]
+    defer: bool,
+    children: Children,
+) -> impl IntoView {
+    let body = NodeRef::<leptos::html::Div>::new();
+    let (over, set_over) = signal(false);
+    let height = move || settings.with(|s| s.layout.height(panel));
+    let collapsed = move || settings.with(|s| s.layout.is_collapsed(panel));
+
+    // The browser writes the dragged size into the inline style
+    let on_resized = move |_| {
+        let Some(el) = body.get() else { return };
+        let css = web_sys::HtmlElement::style(&el).get_property_value("height").unwrap_or_default();
+        let Some(h) = css.strip_suffix("px").and_then(|h| h.parse::<f64>().ok()) else { return };
+        let h = h.round() as u32;
+        if height() != Some(h) {
+            settings.update(|s| s.layout.set_height(panel, Some(h)));
+        }
+    };
+
+    view! {
+        <div
+            class=format!("layout-panel {class}")
+            class:drop-target=over
+            class:collapsed=collapsed
+            style=move || format!("order: {}", settings.with(|s| s.layout.position(panel)))
+            on:dragover=move |ev| {
+                ev.prevent_default();
+                set_over.set(true);
+            }
+            on:dragleave=move |_| set_over.set(false)
+            on:drop=move |ev| {
+                ev.prevent_default();
+                set_over.set(false);
+                let dragged = ev.data_transfer().and_then(|dt| dt.get_data(DRAG_TYPE).ok());
+                if let Some(from) = dragged.as_deref().and_then(Panel::from_code)
+                    && from != panel
+                {
+                    settings.update(|s| s.layout.move_to(from, panel));
+                }
+            }
+        >
+            <div
+                class="panel-handle"
+                draggable="true"
+                title="Drag onto another panel to move it there"
+                on:dragstart=move |ev| {
+                    if let Some(dt) = ev.data_transfer() {
+                        _ = dt.set_data(DRAG_TYPE, panel.code());
+                    }
+                }
+            >
+                <span class="panel-grip" aria-hidden="true">"⠿"</span>
+                <button
+                    class="panel-chevron"
+                    aria-expanded=move || (!collapsed()).to_string()
+                    aria-label=format!("Show or hide {}", panel.label())
+                    on:click=move |_| settings.update(|s| s.layout.toggle_collapsed(panel))
+                >
+                    {move || if collapsed() { "▸" } else { "▾" }}
+                </button>
+                {panel.label()}
+                <button
+                    class="btn-small"
+                    on:click=move |_| settings.update(|s| s.layout.set_height(panel, None))
+                    disabled=move || height().is_none() || collapsed()
+                >
+                    "Fit content"
+                </button>
+            </div>
+            <div
+                node_ref=body
+                class="panel-body"
+                hidden=collapsed
+                style=move || height().map(|h| format!("height: {h}px"))
+                on:pointerup=on_resized
+            >
+                <Deferred when=Signal::derive(move || !defer || !collapsed())>{children()}</Deferred>
+            </div>
+        </div>
+    }
+}
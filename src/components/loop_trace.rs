@@ -0,0 +1,47 @@
+use leptos::prelude::*;
+
+use crate::codegen::{iteration, machine_trace};
+use crate::components::active_cell::use_active_cell;
+
+/// The active cell of the enclosing visualizer as the generated loop executes
+/// it: loop indices, then one line per read, comparison and store.
+#[component]
+pub fn LoopTraceView(
+    /// Start expanded, as in the split view.
+    #[prop(optional)]
+    open: bool,
+) -> impl IntoView {
+    let active = use_active_cell();
+    let body = move || {
+        let Some((step, a)) = active.and_then(|a| a.step.get().map(|s| (s, a))) else {
+            return view! {
+                <p class="complexity-row">"Step through the table to trace the loop."</p>
+            }.into_any();
+        };
+        let ops = a.table.with(|t| {
+            t.as_ref().map(|t| a.problem.with(|p| machine_trace(t, p, step))).unwrap_or_default()
+        });
+        let iter = iteration(step, a.problem.with(|p| p.capacity));
+        view! {
+            <pre class="code-block machine-trace">
+                <span class="mt-head">
+                    {format!("iter {iter:>6}   i = {}   w = {}   ({})", step.row, step.col, step.branch.label())}
+                </span>
+                {ops.into_iter().map(|o| view! {
+                    <span class="mt-line">
+                        <span class="mt-op">{format!("  {:<6}", o.op)}</span>
+                        {format!("{:<34}", o.args)}
+                        <span class="mt-result">{format!("→ {}", o.result)}</span>
+                    </span>
+                }).collect_view()}
+            </pre>
+        }.into_any()
+    };
+
+    view! {
+        <details class="settings-card trace-card" open=open>
+            <summary class="settings-title">"Loop trace"</summary>
+            {body}
+        </details>
+    }
+}
@@ -0,0 +1,108 @@
+use leptos::prelude::*;
+
+use crate::analytics::UsageEvent;
+use crate::components::usage::{use_usage, UsageLog};
+use crate::metrics::{sort_runs, RunMetrics, SortKey};
+
+/// Solver runs of this session, shared through context by every
+/// visualizer on the page.
+#[derive(Clone, Copy)]
+pub struct MetricsLog {
+    runs: RwSignal<Vec<RunMetrics>>,
+    usage: UsageLog,
+}
+
+impl MetricsLog {
+    /// Append a run, numbering it after the previous ones.
+    pub fn record(&self, mut m: RunMetrics) {
+        self.usage.record(UsageEvent::Solved(m.solver.to_string()));
+        self.runs.update(|runs| {
+            m.run = runs.last().map_or(1, |r| r.run + 1);
+            runs.push(m);
+        });
+    }
+
+    pub fn clear(&self) {
+        self.runs.update(Vec::clear);
+    }
+}
+
+pub fn provide_metrics() -> MetricsLog {
+    let log = MetricsLog { runs: RwSignal::new(Vec::new()), usage: use_usage() };
+    provide_context(log);
+    log
+}
+
+/// The log of the enclosing workspace, or a fresh one for a lone visualizer.
+pub fn use_metrics() -> MetricsLog {
+    use_context().unwrap_or_else(provide_metrics)
+}
+
+/// Sortable table of every solver run in the session: cells filled, nodes
+/// explored and pruned, memo hits and time, as far as each solver has them.
+#[component]
+pub fn MetricsPane() -> impl IntoView {
+    let log = use_metrics();
+    let sort = RwSignal::new((SortKey::Run, false));
+
+    let rows = move || {
+        let mut runs = log.runs.get();
+        let (key, descending) = sort.get();
+        sort_runs(&mut runs, key, descending);
+        runs.into_iter().map(|m| {
+            let cells = SortKey::ALL.map(|k| view! { <td>{k.cell(&m)}</td> });
+            view! { <tr>{cells.into_iter().collect_view()}</tr> }
+        }).collect_view()
+    };
+
+    let headers = move || SortKey::ALL.into_iter().map(|key| {
+        let aria = move || match sort.get() {
+            (k, false) if k == key => "ascending",
+            (k, true) if k == key => "descending",
+            _ => "none",
+        };
+        view! {
+            <th aria-sort=aria>
+                <button
+                    class="sort-btn"
+                    on:click=move |_| sort.update(|(k, desc)| {
+                        *desc = *k == key && !*desc;
+                        *k = key;
+                    })
+                >
+                    {key.label()}
+                    {move || match sort.get() {
+                        (k, false) if k == key => " ▲",
+                        (k, true) if k == key => " ▼",
+                        _ => "",
+                    }}
+                </button>
+            </th>
+        }
+    }).collect_view();
+
+    view! {
+        <details class="settings-card metrics-card">
+            <summary class="settings-title">
+                {move || format!("Solver metrics ({} runs)", log.runs.with(Vec::len))}
+            </summary>
+            <div class="code-toolbar">
+                <button class="btn-small" on:click=move |_| log.clear() disabled=move || log.runs.with(Vec::is_empty)>
+                    "Clear"
+                </button>
+                <span class="toolbar-hint">"Click a column to sort; click again to reverse."</span>
+            </div>
+            <Show
+                when=move || log.runs.with(|r| !r.is_empty())
+                fallback=|| view! { <p class="complexity-row">"No runs yet: solve an instance to start collecting."</p> }
+            >
+                <div class="table-wrap">
+                    <table class="metrics-table">
+                        <thead><tr>{headers()}</tr></thead>
+                        <tbody>{rows}</tbody>
+                    </table>
+                </div>
+            </Show>
+        </details>
+    }
+}
@@ -1,2 +1,37 @@
+pub mod active_cell;
+pub mod answer_strip;
+pub mod assignment;
+pub mod bag;
+pub mod bounded;
+pub mod cardinality;
+pub mod code_panel;
+pub mod complexity;
 pub mod counter_btn;
+pub mod deferred;
+pub mod diagnostics;
+pub mod dp_table;
+pub mod drawing;
+pub mod lagrange;
+pub mod large_input;
+pub mod layout;
 pub mod legend;
+pub mod loop_trace;
+pub mod metrics;
+pub mod monte_carlo;
+pub mod onboarding;
+pub mod precedence;
+pub mod preprocess;
+pub mod race;
+pub mod robust;
+pub mod sandbox;
+pub mod settings;
+pub mod startup;
+pub mod stats;
+pub mod stepper;
+pub mod summary;
+pub mod table_toolbar;
+pub mod toast;
+pub mod top_down;
+pub mod usage;
+pub mod waterfall;
+pub mod wide_table;
@@ -0,0 +1,130 @@
+use leptos::prelude::*;
+
+use crate::format::{Locale, NumberFormat};
+use crate::parse::{parse_list, parse_number};
+use crate::problem::Problem;
+use crate::simulation::{histogram, simulate, Simulation};
+
+/// Samples × table cells one run may evaluate; larger runs are cut short.
+const CELL_BUDGET: usize = 20_000_000;
+const BINS: usize = 12;
+
+/// Monte Carlo panel: every benefit gets a ± range, and each sample draws
+/// the benefits uniformly from it. The set that is optimal on the entered
+/// (expected) values is compared with solving each sample again.
+#[component]
+pub fn MonteCarloPanel(
+    #[prop(into)] problem: Signal<Option<Problem>>,
+    #[prop(into)] locale: Signal<Locale>,
+    #[prop(into)] fmt: Signal<NumberFormat>,
+) -> impl IntoView {
+    let (spreads_input, set_spreads_input) = signal(String::from("1"));
+    let (samples_input, set_samples_input) = signal(String::from("500"));
+    let (seed, set_seed) = signal(1u64);
+    let (result, set_result) = signal(Option::<Result<(Problem, Simulation, Option<usize>), String>>::None);
+
+    let run = move |_| {
+        let Some(p) = problem.get_untracked() else {
+            set_result.set(Some(Err("Enter a valid instance first.".into())));
+            return;
+        };
+        let locale = locale.get_untracked();
+        let outcome = (|| {
+            let mut spreads = parse_list(&spreads_input.get_untracked(), locale).map_err(|e| format!("Ranges: {e}"))?;
+            if spreads.len() == 1 {
+                spreads = vec![spreads[0]; p.len()];
+            }
+            if spreads.len() != p.len() {
+                return Err(format!("{} ranges for {} items (or give one range for all)", spreads.len(), p.len()));
+            }
+            let asked = parse_number(&samples_input.get_untracked(), locale).map_err(|e| format!("Samples: {e}"))?;
+            let cells = p.len() * (p.capacity + 1);
+            let samples = asked.min(CELL_BUDGET / cells.max(1)).max(1);
+            let sim = simulate(&p, &spreads, samples, seed.get_untracked());
+            Ok((p, sim, (samples < asked).then_some(asked)))
+        })();
+        set_result.set(Some(outcome));
+        set_seed.update(|s| *s += 1);
+    };
+
+    let body = move || {
+        result.get().map(|outcome| match outcome {
+            Err(e) => view! { <p class="field-error">{e}</p> }.into_any(),
+            Ok((p, sim, cut)) => {
+                let fmt = fmt.get();
+                let value = |v: f64| p.units.benefit(v.round() as usize, &fmt);
+                let (lows, counts) = histogram(&[&sim.fixed_values, &sim.reoptimized_values], BINS);
+                let peak = counts.iter().flatten().copied().max().unwrap_or(1).max(1);
+                let height = |c: usize| format!("height: {:.1}%", c as f64 * 100.0 / peak as f64);
+                let bars = lows.iter().enumerate().map(|(b, &low)| {
+                    let (f, r) = (counts[0][b], counts[1][b]);
+                    view! {
+                        <div class="mc-bin" title=format!("from {low}: fixed set {f}, re-optimized {r}")>
+                            <div class="mc-bar fixed" style=height(f)></div>
+                            <div class="mc-bar reopt" style=height(r)></div>
+                        </div>
+                    }
+                }).collect_view();
+                view! {
+                    <p class="complexity-row">
+                        {format!(
+                            "The expected-value optimum {:?} was still best in {:.1}% of {} samples.",
+                            sim.fixed_items, sim.still_optimal() * 100.0, sim.samples()
+                        )}
+                    </p>
+                    <p class="complexity-row">
+                        "Mean realized value: "<strong>{value(sim.mean_fixed())}</strong>
+                        " with the fixed set, "<strong>{value(sim.mean_reoptimized())}</strong>
+                        " when re-optimizing each sample."
+                    </p>
+                    {cut.map(|asked| view! {
+                        <p class="toolbar-hint">{format!(
+                            "Ran {} of the {asked} samples asked for, to keep the page responsive.", sim.samples()
+                        )}</p>
+                    })}
+                    <div class="mc-chart" role="img" aria-label="Distribution of realized values">{bars}</div>
+                    <div class="mc-axis">
+                        <span>{lows.first().map(|v| v.to_string())}</span>
+                        <span>{sim.fixed_values.iter().chain(&sim.reoptimized_values).max().map(|v| v.to_string())}</span>
+                    </div>
+                    <p class="mc-legend">
+                        <span class="mc-key fixed"></span>"fixed set  "
+                        <span class="mc-key reopt"></span>"re-optimized"
+                    </p>
+                }.into_any()
+            }
+        })
+    };
+
+    view! {
+        <details class="settings-card mc-card">
+            <summary class="settings-title">"Uncertain benefits (Monte Carlo)"</summary>
+            <div class="code-toolbar">
+                <label>
+                    "± range "
+                    <input
+                        type="text"
+                        class="mc-input"
+                        title="One range for all items, or one per item"
+                        prop:value=move || spreads_input.get()
+                        on:input:target=move |ev| set_spreads_input.set(ev.target().value())
+                    />
+                </label>
+                <label>
+                    "samples "
+                    <input
+                        type="text"
+                        inputmode="numeric"
+                        class="mc-input"
+                        prop:value=move || samples_input.get()
+                        on:input:target=move |ev| set_samples_input.set(ev.target().value())
+                    />
+                </label>
+                <button class="btn-small" on:click=run disabled=move || problem.with(Option::is_none)>
+                    "▶ Simulate"
+                </button>
+            </div>
+            {body}
+        </details>
+    }
+}
@@ -0,0 +1,20 @@
+use leptos::prelude::*;
+
+/// First-visit note over the sample instance: what the highlight colours
+/// mean and how to carry on stepping.
+#[component]
+pub fn WelcomeCallout(on_dismiss: Callback<()>) -> impl IntoView {
+    view! {
+        <aside class="welcome-callout" role="note" aria-label="Welcome">
+            <h2 class="welcome-title">"Welcome! Here is a sample, five cells in."</h2>
+            <ul class="welcome-colors">
+                <li><span class="legend-cell cell-active">"·"</span>" the cell just filled"</li>
+                <li><span class="legend-cell cell-took">"·"</span>" the item was taken: its value beat the row above"</li>
+                <li><span class="legend-cell">"·"</span>" skipped: copied from the row above"</li>
+                <li><span class="legend-cell cell-backtrack">"★"</span>" on the traceback path once the table is done"</li>
+            </ul>
+            <p class="toolbar-hint">"Press Next step to keep filling, or edit the form to try your own instance."</p>
+            <button class="btn-small" on:click=move |_| on_dismiss.run(())>"Got it"</button>
+        </aside>
+    }
+}
@@ -0,0 +1,122 @@
+use leptos::prelude::*;
+
+use crate::format::NumberFormat;
+use crate::problem::Problem;
+use crate::solver::{missing_prerequisites, PrecedenceSolution};
+
+/// Dependency forest beside the tree-knapsack table of a precedence solve.
+/// Rows follow the preorder of the forest, so every subtree is a block of
+/// consecutive rows. Below, the unconstrained optimum is checked against
+/// the dependencies and items packed without their prerequisite are marked.
+#[component]
+pub fn PrecedenceView(
+    problem: Problem,
+    parents: Vec<Option<usize>>,
+    solution: PrecedenceSolution,
+    /// Items of the optimum without dependencies, 1-based.
+    unconstrained: Vec<usize>,
+    fmt: NumberFormat,
+) -> impl IntoView {
+    let units = problem.units.clone();
+    let n = problem.len();
+
+    // ── forest ──
+    let mut children = vec![Vec::new(); n];
+    for (i, parent) in parents.iter().enumerate() {
+        if let Some(a) = parent {
+            children[*a].push(i);
+        }
+    }
+    fn node(i: usize, children: &[Vec<usize>], p: &Problem, chosen: &[usize]) -> AnyView {
+        let cls = if chosen.contains(&(i + 1)) { "dep-node chosen" } else { "dep-node" };
+        let kids = (!children[i].is_empty()).then(|| {
+            let items = children[i].iter().map(|&c| node(c, children, p, chosen)).collect_view();
+            view! { <ul>{items}</ul> }
+        });
+        view! {
+            <li>
+                <span class=cls>{format!("Item {}  w={} b={}", i + 1, p.weights[i], p.benefits[i])}</span>
+                {kids}
+            </li>
+        }
+        .into_any()
+    }
+    let forest = (0..n)
+        .filter(|&i| parents[i].is_none())
+        .map(|i| node(i, &children, &problem, &solution.chosen))
+        .collect_view();
+
+    // ── table ──
+    let rows = solution.table.iter().enumerate().map(|(r, row)| {
+        let label = match solution.order.get(r) {
+            Some(&i) => match parents[i] {
+                Some(a) => format!("{}  needs {}", i + 1, a + 1),
+                None => format!("{}", i + 1),
+            },
+            None => "—".to_string(),
+        };
+        let cells = row.iter().enumerate().map(|(c, &val)| {
+            let cls = if r == n {
+                "cell cell-base"
+            } else if solution.path.contains(&(r, c)) {
+                "cell cell-backtrack"
+            } else {
+                "cell"
+            };
+            view! { <td class=cls title=format!("T[{r}][{c}] = {val}")>{units.benefit(val, &fmt)}</td> }
+        }).collect_view();
+        let skip_to = solution.subtree_end.get(r).map(|&end| format!("skipping jumps to row {end}"));
+        view! {
+            <tr>
+                <td class="item-header" title=skip_to>{label}</td>
+                {cells}
+            </tr>
+        }
+    }).collect_view();
+
+    // ── check of the unconstrained traceback ──
+    let missing = missing_prerequisites(&unconstrained, &parents);
+    let picks = unconstrained.iter().map(|&i| {
+        match missing.iter().find(|(item, _)| *item == i) {
+            Some((_, a)) => view! {
+                <span class="pick invalid-pick" title=format!("requires item {a}, which is not packed")>{i}</span>
+            }.into_any(),
+            None => view! { <span class="pick">{i}</span> }.into_any(),
+        }
+    }).collect_view();
+    let verdict = if missing.is_empty() {
+        "It already respects the dependencies.".to_string()
+    } else {
+        format!("{} of its items lack a prerequisite.", missing.len())
+    };
+
+    view! {
+        <section class="precedence-view" aria-label="Knapsack with dependencies">
+            <div class="precedence-split">
+                <div class="dep-forest">
+                    <h3>"Dependencies"</h3>
+                    <ul>{forest}</ul>
+                </div>
+                <div class="table-wrap">
+                    <table class="dp-table">
+                        <thead>
+                            <tr>
+                                <th class="corner">"item \\ w"</th>
+                                {(0..=problem.capacity).map(|w| view! { <th>{w}</th> }).collect_view()}
+                            </tr>
+                        </thead>
+                        <tbody>{rows}</tbody>
+                    </table>
+                </div>
+            </div>
+            <p class="wide-answer">
+                "Optimum "<strong>{units.benefit(solution.best, &fmt)}</strong>
+                " with items "<strong>{format!("{:?}", solution.chosen)}</strong>
+                " (respecting dependencies)."
+            </p>
+            <p class="dep-check">
+                "Without dependencies the traceback takes items "{picks}". "{verdict}
+            </p>
+        </section>
+    }
+}
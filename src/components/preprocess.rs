@@ -0,0 +1,134 @@
+use std::time::Duration;
+
+use leptos::prelude::*;
+
+use crate::analysis::{preprocess, PreStep, RemovalReason};
+use crate::problem::Problem;
+
+const STEP_INTERVAL: Duration = Duration::from_millis(1100);
+
+/// Animated pre-phase showing the classic reductions applied to the current
+/// instance before the DP: each removed item is struck out and explained,
+/// then the weights are rescaled by their common divisor.
+#[component]
+pub fn PreprocessView(#[prop(into)] problem: Signal<Option<Problem>>) -> impl IntoView {
+    let steps = Memo::new(move |_| problem.with(|p| p.as_ref().map(preprocess).unwrap_or_default()));
+    // how many steps have been played
+    let shown = RwSignal::new(0usize);
+    let timer = StoredValue::new(None::<IntervalHandle>);
+    let stop = move || {
+        if let Some(h) = timer.get_value() {
+            h.clear();
+            timer.set_value(None);
+        }
+    };
+    let advance = move || {
+        if shown.get_untracked() < steps.with_untracked(Vec::len) {
+            shown.update(|s| *s += 1);
+        } else {
+            stop();
+        }
+    };
+    let on_play = move |_| {
+        stop();
+        shown.set(0);
+        if let Ok(h) = set_interval_with_handle(advance, STEP_INTERVAL) {
+            timer.set_value(Some(h));
+        }
+    };
+    // a new instance starts over
+    Effect::new(move |_| {
+        steps.track();
+        stop();
+        shown.set(0);
+    });
+    on_cleanup(stop);
+
+    let body = move || {
+        let Some(p) = problem.get() else {
+            return view! { <p class="complexity-row">"Enter a valid instance to preprocess."</p> }.into_any();
+        };
+        let all = steps.get();
+        if all.is_empty() {
+            return view! {
+                <p class="complexity-row">"No reduction applies: every item can matter and the weights share no divisor."</p>
+            }.into_any();
+        }
+        let done = &all[..shown.get().min(all.len())];
+        let removed = |i: usize| done.iter().find_map(|s| match s {
+            PreStep::Remove(r) if r.item == i => Some(&r.reason),
+            _ => None,
+        });
+        let divisor = done.iter().find_map(|s| match s {
+            PreStep::Scale { divisor } => Some(*divisor),
+            _ => None,
+        }).unwrap_or(1);
+
+        let chips = (1..=p.len()).map(|i| {
+            let (w, b) = (p.weights[i - 1], p.benefits[i - 1]);
+            let cls = match removed(i) {
+                Some(RemovalReason::TooHeavy) => "pre-item removed heavy",
+                Some(RemovalReason::DominatedBy(_)) => "pre-item removed dominated",
+                None if divisor > 1 => "pre-item scaled",
+                None => "pre-item",
+            };
+            let weight = if divisor > 1 && removed(i).is_none() {
+                format!("{w}→{}", w / divisor)
+            } else {
+                w.to_string()
+            };
+            view! {
+                <span class=cls>
+                    <strong>{format!("#{i}")}</strong>
+                    {format!(" w {weight} · b {b}")}
+                </span>
+            }
+        }).collect_view();
+
+        let log = done.iter().map(|s| {
+            let text = match s {
+                PreStep::Remove(r) => r.describe(&p),
+                PreStep::Scale { divisor } => format!(
+                    "Capacity and all weights are multiples of {divisor}: divide them, W {} → {}, \
+                     shrinking the table {divisor}×.",
+                    p.capacity, p.capacity / divisor,
+                ),
+            };
+            view! { <li>{text}</li> }
+        }).collect_view();
+
+        view! {
+            <div class="pre-items">
+                <span class="pre-cap">{format!("W = {}", if divisor > 1 {
+                    format!("{}→{}", p.capacity, p.capacity / divisor)
+                } else {
+                    p.capacity.to_string()
+                })}</span>
+                {chips}
+            </div>
+            <ol class="pre-log">{log}</ol>
+        }.into_any()
+    };
+
+    view! {
+        <details class="settings-card pre-card">
+            <summary class="settings-title">"Preprocessing"</summary>
+            <div class="code-toolbar">
+                <button class="btn-small" on:click=on_play disabled=move || steps.with(Vec::is_empty)>
+                    "▶ Play"
+                </button>
+                <button
+                    class="btn-small"
+                    on:click=move |_| { stop(); shown.set(steps.with_untracked(Vec::len)); }
+                    disabled=move || steps.with(Vec::is_empty)
+                >
+                    "Show all"
+                </button>
+                <span class="toolbar-hint">
+                    {move || format!("{} / {} steps", shown.get().min(steps.with(Vec::len)), steps.with(Vec::len))}
+                </span>
+            </div>
+            {body}
+        </details>
+    }
+}
@@ -0,0 +1,211 @@
+use std::time::Duration;
+
+use leptos::prelude::*;
+
+use crate::components::metrics::use_metrics;
+use crate::metrics::RunMetrics;
+use crate::problem::Problem;
+use crate::solver::{BranchAndBound, RowSolver, TableSolver};
+
+const FRAME: Duration = Duration::from_millis(40);
+/// Work units per frame to choose from: one DP cell or one B&B node each.
+const SPEEDS: [usize; 4] = [5, 50, 500, 5000];
+
+/// Both solvers mid-race.
+struct Race {
+    dp: TableSolver,
+    cols: usize,
+    rows_total: usize,
+    rows_done: usize,
+    /// Work granted to the DP but not yet spent on a whole row.
+    credit: usize,
+    bnb: BranchAndBound,
+    capacity: usize,
+}
+
+/// What the lanes show after each frame.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Lap {
+    frame: usize,
+    cells: usize,
+    cells_total: usize,
+    dp_best: Option<usize>,
+    dp_done_at: Option<usize>,
+    nodes: usize,
+    pruned: usize,
+    bnb_best: usize,
+    bnb_done_at: Option<usize>,
+}
+
+/// DP against branch and bound on the same instance, advanced in
+/// interleaved frames with the same work budget each: a DP cell and a B&B
+/// node cost one unit. The lanes count cells computed and nodes explored
+/// and flag whichever finishes first; both bars are scaled to the number
+/// of DP cells.
+#[component]
+pub fn RacePanel(#[prop(into)] problem: Signal<Option<Problem>>) -> impl IntoView {
+    let metrics = use_metrics();
+    let speed = RwSignal::new(SPEEDS[1]);
+    let lap = RwSignal::new(None::<Lap>);
+    let race = StoredValue::new(None::<Race>);
+    let timer = StoredValue::new(None::<IntervalHandle>);
+    let stop = move || {
+        if let Some(h) = timer.get_value() {
+            h.clear();
+            timer.set_value(None);
+        }
+    };
+
+    let frame = move || {
+        let budget = speed.get_untracked();
+        let mut finished = false;
+        race.update_value(|r| {
+            let Some(r) = r.as_mut() else { return };
+            let mut l = lap.get_untracked().unwrap_or_default();
+            l.frame += 1;
+
+            if l.dp_done_at.is_none() {
+                r.credit += budget;
+                let rows = r.credit / r.cols;
+                if rows > 0 {
+                    r.credit -= rows * r.cols;
+                    r.rows_done = r.dp.fill_rows(rows).rows_done;
+                }
+                l.cells = r.rows_done * r.cols;
+                if r.rows_done >= r.rows_total {
+                    l.dp_done_at = Some(l.frame);
+                }
+            }
+            if l.bnb_done_at.is_none() {
+                r.bnb.step(budget);
+                (l.nodes, l.pruned, l.bnb_best) = (r.bnb.nodes, r.bnb.pruned, r.bnb.best());
+                if r.bnb.is_done() {
+                    l.bnb_done_at = Some(l.frame);
+                }
+            }
+            finished = l.dp_done_at.is_some() && l.bnb_done_at.is_some();
+            lap.set(Some(l));
+        });
+        if finished {
+            stop();
+            // the finished table is only needed for its corner value
+            if let Some(r) = race.try_update_value(|r| r.take()).flatten() {
+                let (items, capacity) = (r.rows_total, r.capacity);
+                let (nodes, pruned, bnb_best) = (r.bnb.nodes, r.bnb.pruned, r.bnb.best());
+                let table = r.dp.finish();
+                let best = table.last().and_then(|row| row.last()).copied().unwrap_or(0);
+                lap.update(|l| {
+                    if let Some(l) = l {
+                        l.dp_best = Some(best);
+                    }
+                });
+                let run = RunMetrics { items, capacity, ..Default::default() };
+                metrics.record(RunMetrics {
+                    solver: "table DP (race)",
+                    best: best as u128,
+                    cells: Some(items * (capacity + 1)),
+                    ..run.clone()
+                });
+                metrics.record(RunMetrics {
+                    solver: "branch and bound (race)",
+                    best: bnb_best as u128,
+                    nodes: Some(nodes),
+                    pruned: Some(pruned),
+                    ..run
+                });
+            }
+        }
+    };
+
+    let on_start = move |_| {
+        stop();
+        let Some(p) = problem.get_untracked() else { return };
+        let cols = p.capacity + 1;
+        race.set_value(Some(Race {
+            dp: TableSolver::new(p.capacity, &p.weights, &p.benefits),
+            cols,
+            rows_total: p.len(),
+            rows_done: 0,
+            credit: 0,
+            bnb: BranchAndBound::new(&p),
+            capacity: p.capacity,
+        }));
+        lap.set(Some(Lap { cells_total: p.len() * cols, ..Default::default() }));
+        if let Ok(h) = set_interval_with_handle(frame, FRAME) {
+            timer.set_value(Some(h));
+        }
+    };
+    on_cleanup(stop);
+
+    let lanes = move || {
+        lap.get().map(|l| {
+            // both bars measure work against the size of the DP table
+            let pct = |work: usize| (work * 100).checked_div(l.cells_total).unwrap_or(100).min(100);
+            let flag = |done: Option<usize>, other: Option<usize>| match (done, other) {
+                (Some(a), Some(b)) if a == b => " 🏁 tie",
+                (Some(a), Some(b)) if a < b => " 🏁 first",
+                (Some(_), None) => " 🏁 first",
+                (Some(_), _) => " finished",
+                (None, _) => "",
+            };
+            view! {
+                <div class="race-lane">
+                    <span class="race-name">"DP"</span>
+                    <div class="race-track">
+                        <div class="race-bar" style=format!("width: {}%", pct(l.cells))></div>
+                    </div>
+                    <span class="race-count">
+                        {format!("{} / {} cells", l.cells, l.cells_total)}
+                        {flag(l.dp_done_at, l.bnb_done_at)}
+                    </span>
+                </div>
+                <div class="race-lane">
+                    <span class="race-name">"B&B"</span>
+                    <div class="race-track">
+                        <div class="race-bar bnb" style=format!("width: {}%", pct(l.nodes))></div>
+                    </div>
+                    <span class="race-count">
+                        {format!("{} nodes, {} pruned, best {}", l.nodes, l.pruned, l.bnb_best)}
+                        {flag(l.bnb_done_at, l.dp_done_at)}
+                    </span>
+                </div>
+                {l.dp_best.map(|best| view! {
+                    <p class="toolbar-hint">
+                        {format!(
+                            "Both reach {best}: the DP after {} cells in {} frames, branch and bound after {} nodes in {} frames.",
+                            l.cells_total,
+                            l.dp_done_at.unwrap_or(0),
+                            l.nodes,
+                            l.bnb_done_at.unwrap_or(0),
+                        )}
+                    </p>
+                })}
+            }
+        })
+    };
+
+    view! {
+        <details class="settings-card race-card">
+            <summary class="settings-title">"DP vs branch and bound"</summary>
+            <div class="code-toolbar">
+                <button class="btn-small" on:click=on_start disabled=move || problem.with(Option::is_none)>
+                    "▶ Race"
+                </button>
+                <label>
+                    "work per frame "
+                    <select on:change:target=move |ev| {
+                        if let Ok(v) = ev.target().value().parse() {
+                            speed.set(v);
+                        }
+                    }>
+                        {SPEEDS.into_iter().map(|s| view! {
+                            <option value=s.to_string() selected=s == speed.get_untracked()>{s}</option>
+                        }).collect_view()}
+                    </select>
+                </label>
+                <span class="toolbar-hint">"one unit = one DP cell or one B&B node"</span>
+            </div>
+            {lanes}
+        </details>
+    }
+}
@@ -0,0 +1,152 @@
+use leptos::prelude::*;
+
+use crate::format::{Locale, NumberFormat};
+use crate::parse::parse_list;
+use crate::problem::Problem;
+use crate::robust::{trade_off, TradeOff};
+use crate::solver::solve_headless;
+
+/// Cells all `n + 1` robust solves may evaluate together; they rerun on
+/// every edit, so this stays small.
+const CELL_BUDGET: usize = 2_000_000;
+
+/// Worst-case mode: each benefit may drop by a given amount for up to Γ
+/// packed items. Compares the robust packing with the nominal DP optimum
+/// at the chosen Γ and draws the trade-off over every Γ.
+#[component]
+pub fn RobustPanel(
+    #[prop(into)] problem: Signal<Option<Problem>>,
+    #[prop(into)] locale: Signal<Locale>,
+    #[prop(into)] fmt: Signal<NumberFormat>,
+) -> impl IntoView {
+    let (drops_input, set_drops_input) = signal(String::from("1"));
+    let gamma = RwSignal::new(1usize);
+
+    // (problem, nominal items, curve for Γ = 0..=n)
+    let curve = Memo::new(move |_| {
+        let p = problem.get()?;
+        let outcome = (|| {
+            let mut drops = parse_list(&drops_input.get(), locale.get()).map_err(|e| format!("Drops: {e}"))?;
+            if drops.len() == 1 {
+                drops = vec![drops[0]; p.len()];
+            }
+            if drops.len() != p.len() {
+                return Err(format!("{} drops for {} items (or give one drop for all)", drops.len(), p.len()));
+            }
+            let n = p.len();
+            // an overflow (usize is 32 bits on wasm) is over budget too
+            let cells = n
+                .checked_mul(n + 1)
+                .and_then(|c| c.checked_mul(n + 1))
+                .and_then(|c| (c / 2).checked_mul(p.capacity + 1));
+            if cells.is_none_or(|c| c > CELL_BUDGET) {
+                return Err("This instance is too large to compare every Γ here.".into());
+            }
+            let nominal = solve_headless(&p).chosen;
+            let points = trade_off(&p, &drops, &nominal);
+            Ok((p, nominal, points))
+        })();
+        Some(outcome)
+    });
+
+    let body = move || {
+        curve.get().map(|outcome| match outcome {
+            Err(e) => view! { <p class="field-error">{e}</p> }.into_any(),
+            Ok((p, nominal, points)) => {
+                let fmt = fmt.get();
+                let g = gamma.get().min(p.len());
+                let at = &points[g];
+                let nominal_value: usize = nominal.iter().map(|&i| p.benefits[i - 1]).sum();
+                let value = |v: usize| p.units.benefit(v, &fmt);
+                let top = nominal_value.max(1) as f64;
+                let width = |v: usize| format!("width: {:.1}%", v as f64 * 100.0 / top);
+                let rows = points.iter().map(|pt: &TradeOff| {
+                    let cls = if pt.gamma == g { "rb-row current" } else { "rb-row" };
+                    view! {
+                        <div class=cls>
+                            <span class="rb-gamma">{format!("Γ={}", pt.gamma)}</span>
+                            <div class="rb-bars">
+                                <div
+                                    class="rb-bar robust"
+                                    style=width(pt.robust.worst_case)
+                                    title=format!("robust worst case {}", pt.robust.worst_case)
+                                ></div>
+                                <div
+                                    class="rb-bar nominal"
+                                    style=width(pt.nominal_worst_case)
+                                    title=format!("nominal optimum's worst case {}", pt.nominal_worst_case)
+                                ></div>
+                            </div>
+                        </div>
+                    }
+                }).collect_view();
+                view! {
+                    <table class="rb-compare">
+                        <thead>
+                            <tr><th></th><th>"items"</th><th>"nominal"</th><th>{format!("worst case (Γ={g})")}</th></tr>
+                        </thead>
+                        <tbody>
+                            <tr>
+                                <th>"Nominal DP optimum"</th>
+                                <td>{format!("{nominal:?}")}</td>
+                                <td>{value(nominal_value)}</td>
+                                <td>{value(at.nominal_worst_case)}</td>
+                            </tr>
+                            <tr>
+                                <th>"Robust packing"</th>
+                                <td>{format!("{:?}", at.robust.chosen)}</td>
+                                <td>{value(at.robust.nominal)}</td>
+                                <td>{value(at.robust.worst_case)}</td>
+                            </tr>
+                        </tbody>
+                    </table>
+                    <p class="complexity-row">
+                        {format!(
+                            "Price of robustness: {} of nominal value for {} more in the worst case.",
+                            value(nominal_value - at.robust.nominal),
+                            value(at.robust.worst_case - at.nominal_worst_case),
+                        )}
+                    </p>
+                    <div class="rb-chart" role="img" aria-label="Worst-case value for every Γ">{rows}</div>
+                    <p class="mc-legend">
+                        <span class="mc-key robust"></span>"robust packing  "
+                        <span class="mc-key nominal"></span>"nominal optimum (worst case)"
+                    </p>
+                }.into_any()
+            }
+        })
+    };
+
+    view! {
+        <details class="settings-card rb-card">
+            <summary class="settings-title">"Worst case (robust)"</summary>
+            <div class="code-toolbar">
+                <label>
+                    "drop "
+                    <input
+                        type="text"
+                        class="mc-input"
+                        title="How much each benefit can fall: one amount for all items, or one per item"
+                        prop:value=move || drops_input.get()
+                        on:input:target=move |ev| set_drops_input.set(ev.target().value())
+                    />
+                </label>
+                <label class="layer-slider">
+                    <strong class="mono">{move || format!("Γ = {}", gamma.get())}</strong>
+                    <input
+                        type="range"
+                        min="0"
+                        max=move || problem.with(|p| p.as_ref().map_or(0, Problem::len)).to_string()
+                        prop:value=move || gamma.get().to_string()
+                        on:input:target=move |ev| {
+                            if let Ok(g) = ev.target().value().parse() {
+                                gamma.set(g);
+                            }
+                        }
+                    />
+                </label>
+            </div>
+            {body}
+        </details>
+    }
+}
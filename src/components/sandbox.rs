@@ -0,0 +1,119 @@
+use leptos::prelude::*;
+
+use crate::format::NumberFormat;
+use crate::problem::Problem;
+use crate::solver::{knapsack_table, knapsack_table_with, RecurrenceBug};
+
+/// Larger instances are not drawn here; the sandbox is for small examples.
+const SANDBOX_MAX_CELLS: usize = 2_000;
+
+/// Switch deliberate mistakes into the recurrence and see the resulting table
+/// and answer next to the correct one.
+#[component]
+pub fn RecurrenceSandbox(
+    /// The instance on screen, or the form's instance before solving.
+    #[prop(into)]
+    problem: Signal<Option<Problem>>,
+    #[prop(into)]
+    fmt: Signal<NumberFormat>,
+) -> impl IntoView {
+    let bugs = RwSignal::new(Vec::<RecurrenceBug>::new());
+
+    let body = move || {
+        let Some(p) = problem.get() else {
+            return view! { <p class="complexity-row">"Enter a valid instance to experiment."</p> }.into_any();
+        };
+        let cells = (p.len() + 1) * (p.capacity + 1);
+        if cells > SANDBOX_MAX_CELLS {
+            return view! {
+                <p class="complexity-row">
+                    {format!("The sandbox draws up to {SANDBOX_MAX_CELLS} cells; this instance has {cells}.")}
+                </p>
+            }.into_any();
+        }
+        let fmt = fmt.get();
+        let correct = knapsack_table(p.capacity, &p.weights, &p.benefits);
+        let buggy = bugs.with(|b| knapsack_table_with(p.capacity, &p.weights, &p.benefits, b));
+        let (right, wrong) = (correct[p.len()][p.capacity], buggy[p.len()][p.capacity]);
+        let differs = |i: usize, w: usize| buggy[i][w] != correct[i][w];
+        let divergent = (0..=p.len())
+            .flat_map(|i| (0..=p.capacity).map(move |w| (i, w)))
+            .filter(|&(i, w)| differs(i, w))
+            .count();
+        // first wrong cell in the order the loop fills them
+        let first = (1..=p.len())
+            .flat_map(|i| (0..=p.capacity).map(move |w| (i, w)))
+            .find(|&(i, w)| differs(i, w));
+
+        view! {
+            <p class="complexity-row">
+                "Answer with these rules: "<strong>{p.units.benefit(wrong, &fmt)}</strong>
+                "  ·  correct: "<strong>{p.units.benefit(right, &fmt)}</strong>
+                {(wrong != right).then(|| view! { <span class="sandbox-wrong">"  ✗ wrong"</span> })}
+            </p>
+            <p class="complexity-row">
+                {match first {
+                    None => "All cells match the correct table.".to_string(),
+                    Some((i, w)) => format!(
+                        "{divergent} divergent cell{}; the bug first shows at dp[{i}][{w}].",
+                        if divergent == 1 { "" } else { "s" }
+                    ),
+                }}
+            </p>
+            <div class="sandbox-scroll">
+                <table class="dp-table density-ultra sandbox-table">
+                    <thead>
+                        <tr>
+                            <th class="corner">"i \\ w"</th>
+                            {(0..=p.capacity).map(|w| view! { <th class="w-header">{w}</th> }).collect_view()}
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {buggy.iter().enumerate().map(|(i, row)| view! {
+                            <tr>
+                                <th class="w-header">{i}</th>
+                                {row.iter().enumerate().map(|(w, &v)| {
+                                    let cls = match (differs(i, w), first == Some((i, w))) {
+                                        (true, true) => "cell cell-diff diff-first",
+                                        (true, false) => "cell cell-diff",
+                                        _ => "cell",
+                                    };
+                                    let title = differs(i, w)
+                                        .then(|| format!("correct: {}", fmt.format(correct[i][w])));
+                                    view! { <td class=cls title=title>{fmt.format(v)}</td> }
+                                }).collect_view()}
+                            </tr>
+                        }).collect_view()}
+                    </tbody>
+                </table>
+            </div>
+        }.into_any()
+    };
+
+    view! {
+        <details class="settings-card sandbox-card">
+            <summary class="settings-title">"Recurrence sandbox"</summary>
+            <div class="sandbox-bugs">
+                {RecurrenceBug::ALL.into_iter().map(|bug| view! {
+                    <label class="toggle">
+                        <input
+                            type="checkbox"
+                            prop:checked=move || bugs.with(|b| b.contains(&bug))
+                            on:change:target=move |ev| {
+                                let on = ev.target().checked();
+                                bugs.update(|b| {
+                                    b.retain(|&x| x != bug);
+                                    if on {
+                                        b.push(bug);
+                                    }
+                                });
+                            }
+                        />
+                        {bug.label()}
+                    </label>
+                }).collect_view()}
+            </div>
+            {body}
+        </details>
+    }
+}
@@ -0,0 +1,293 @@
+use leptos::prelude::*;
+
+use crate::display::{Density, Layout, Theme, FONT_SCALES};
+use crate::focus::id_scope;
+use crate::format::{Locale, NumberStyle};
+use crate::settings::{Settings, PLAY_SPEEDS, REVEAL_DURATIONS};
+use crate::solver::ValueType;
+
+#[component]
+pub fn SettingsPanel(settings: RwSignal<Settings>) -> impl IntoView {
+    let scope = id_scope();
+    let id = move |base: &str| scope.id(base);
+    view! {
+        <details class="settings-card">
+            <summary class="settings-title">"Settings"</summary>
+            <div class="settings-grid">
+                <div class="field">
+                    <label for=id("number-style")>"Number format"</label>
+                    <select
+                        id=id("number-style")
+                        on:change:target=move |ev| {
+                            if let Some(style) = NumberStyle::from_code(&ev.target().value()) {
+                                settings.update(|s| s.number_format.style = style);
+                            }
+                        }
+                    >
+                        {NumberStyle::ALL.into_iter().map(|style| view! {
+                            <option
+                                value=style.code()
+                                selected=move || settings.with(|s| s.number_format.style == style)
+                            >
+                                {style.label()}
+                            </option>
+                        }).collect_view()}
+                    </select>
+                </div>
+                <div class="field">
+                    <label for=id("locale")>"Locale"</label>
+                    <select
+                        id=id("locale")
+                        on:change:target=move |ev| {
+                            if let Some(locale) = Locale::from_code(&ev.target().value()) {
+                                settings.update(|s| s.number_format.locale = locale);
+                            }
+                        }
+                    >
+                        {Locale::ALL.into_iter().map(|locale| view! {
+                            <option
+                                value=locale.code()
+                                selected=move || settings.with(|s| s.number_format.locale == locale)
+                            >
+                                {locale.label()}
+                            </option>
+                        }).collect_view()}
+                    </select>
+                </div>
+                <div class="field">
+                    <label for=id("parse-locale")>"Input number locale"</label>
+                    <select
+                        id=id("parse-locale")
+                        on:change:target=move |ev| {
+                            if let Some(locale) = Locale::from_code(&ev.target().value()) {
+                                settings.update(|s| s.parse_locale = locale);
+                            }
+                        }
+                    >
+                        {Locale::ALL.into_iter().map(|locale| view! {
+                            <option
+                                value=locale.code()
+                                selected=move || settings.with(|s| s.parse_locale == locale)
+                            >
+                                {locale.label()}
+                            </option>
+                        }).collect_view()}
+                    </select>
+                </div>
+                <div class="field">
+                    <label for=id("value-type")>"Value type"</label>
+                    <select
+                        id=id("value-type")
+                        title="Wide values hold huge benefits; their table is read-only"
+                        on:change:target=move |ev| {
+                            if let Some(value_type) = ValueType::from_code(&ev.target().value()) {
+                                settings.update(|s| s.value_type = value_type);
+                            }
+                        }
+                    >
+                        {ValueType::ALL.into_iter().map(|value_type| view! {
+                            <option
+                                value=value_type.code()
+                                selected=move || settings.with(|s| s.value_type == value_type)
+                            >
+                                {value_type.label()}
+                            </option>
+                        }).collect_view()}
+                    </select>
+                </div>
+                <div class="field">
+                    <label for=id("density")>"Table density"</label>
+                    <select
+                        id=id("density")
+                        on:change:target=move |ev| {
+                            if let Some(density) = Density::from_code(&ev.target().value()) {
+                                settings.update(|s| s.density = density);
+                            }
+                        }
+                    >
+                        {Density::ALL.into_iter().map(|density| view! {
+                            <option
+                                value=density.code()
+                                selected=move || settings.with(|s| s.density == density)
+                            >
+                                {density.label()}
+                            </option>
+                        }).collect_view()}
+                    </select>
+                </div>
+                <div class="field">
+                    <label for=id("cap-min")>"Capacity min"</label>
+                    <input
+                        id=id("cap-min")
+                        type="number"
+                        min="1"
+                        prop:value=move || settings.with(|s| s.capacity_min.to_string())
+                        on:change:target=move |ev| {
+                            if let Ok(v) = ev.target().value().trim().parse::<usize>() {
+                                settings.update(|s| s.capacity_min = v.max(1));
+                            }
+                        }
+                    />
+                </div>
+                <div class="field">
+                    <label for=id("cap-max")>"Capacity max"</label>
+                    <input
+                        id=id("cap-max")
+                        type="number"
+                        min="1"
+                        prop:value=move || settings.with(|s| s.capacity_max.to_string())
+                        on:change:target=move |ev| {
+                            if let Ok(v) = ev.target().value().trim().parse::<usize>() {
+                                settings.update(|s| s.capacity_max = v.max(s.capacity_min));
+                            }
+                        }
+                    />
+                </div>
+                <div class="field">
+                    <label for=id("max-items")>"Max items drawn"</label>
+                    <input
+                        id=id("max-items")
+                        type="number"
+                        min="1"
+                        prop:value=move || settings.with(|s| s.max_visual_items.to_string())
+                        on:change:target=move |ev| {
+                            if let Ok(v) = ev.target().value().trim().parse::<usize>() {
+                                settings.update(|s| s.max_visual_items = v.max(1));
+                            }
+                        }
+                    />
+                </div>
+            </div>
+
+            <h3 class="settings-subtitle">"Accessibility"</h3>
+            <div class="settings-grid">
+                <div class="field">
+                    <label for=id("font-scale")>"Font size"</label>
+                    <select
+                        id=id("font-scale")
+                        on:change:target=move |ev| {
+                            if let Ok(scale) = ev.target().value().parse::<u16>() {
+                                settings.update(|s| s.font_scale = scale);
+                            }
+                        }
+                    >
+                        {FONT_SCALES.into_iter().map(|scale| view! {
+                            <option
+                                value=scale.to_string()
+                                selected=move || settings.with(|s| s.font_scale == scale)
+                            >
+                                {format!("{scale}%")}
+                            </option>
+                        }).collect_view()}
+                    </select>
+                </div>
+                <div class="field">
+                    <label for=id("play-speed")>"Auto-play speed"</label>
+                    <select
+                        id=id("play-speed")
+                        on:change:target=move |ev| {
+                            if let Ok(speed) = ev.target().value().parse::<u32>() {
+                                settings.update(|s| s.play_speed = speed);
+                            }
+                        }
+                    >
+                        {PLAY_SPEEDS.into_iter().map(|speed| view! {
+                            <option
+                                value=speed.to_string()
+                                selected=move || settings.with(|s| s.play_speed == speed)
+                            >
+                                {format!("{speed} cells/s")}
+                            </option>
+                        }).collect_view()}
+                    </select>
+                </div>
+                <div class="field">
+                    <label for=id("reveal-ms")>"Reveal animation"</label>
+                    <select
+                        id=id("reveal-ms")
+                        on:change:target=move |ev| {
+                            if let Ok(ms) = ev.target().value().parse::<u32>() {
+                                settings.update(|s| s.reveal_ms = ms);
+                            }
+                        }
+                    >
+                        {REVEAL_DURATIONS.into_iter().map(|ms| view! {
+                            <option
+                                value=ms.to_string()
+                                selected=move || settings.with(|s| s.reveal_ms == ms)
+                            >
+                                {if ms == 0 { "Off".to_string() } else { format!("{ms} ms") }}
+                            </option>
+                        }).collect_view()}
+                    </select>
+                </div>
+                <label class="toggle">
+                    <input
+                        type="checkbox"
+                        prop:checked=move || settings.with(|s| s.sound_effects)
+                        on:change:target=move |ev| {
+                            settings.update(|s| s.sound_effects = ev.target().checked())
+                        }
+                    />
+                    "Sound effects"
+                </label>
+                <div class="field">
+                    <label for=id("theme")>"Theme"</label>
+                    <select
+                        id=id("theme")
+                        on:change:target=move |ev| {
+                            if let Some(theme) = Theme::from_code(&ev.target().value()) {
+                                settings.update(|s| s.theme = theme);
+                            }
+                        }
+                    >
+                        {Theme::ALL.into_iter().map(|theme| view! {
+                            <option
+                                value=theme.code()
+                                selected=move || settings.with(|s| s.theme == theme)
+                            >
+                                {theme.label()}
+                            </option>
+                        }).collect_view()}
+                    </select>
+                </div>
+            </div>
+
+            <h3 class="settings-subtitle">"Layout"</h3>
+            <div class="settings-grid">
+                <p class="toolbar-hint">"Drag a panel by its title bar to move it; resize it from its corner."</p>
+                <button
+                    class="btn-small"
+                    on:click=move |_| settings.update(|s| s.layout = Layout::default())
+                    disabled=move || settings.with(|s| s.layout == Layout::default())
+                >
+                    "Reset layout"
+                </button>
+            </div>
+
+            <h3 class="settings-subtitle">"Debug"</h3>
+            <div class="settings-grid">
+                <label class="toggle">
+                    <input
+                        type="checkbox"
+                        prop:checked=move || settings.with(|s| s.check_invariants)
+                        on:change:target=move |ev| {
+                            settings.update(|s| s.check_invariants = ev.target().checked())
+                        }
+                    />
+                    "Row invariant overlay"
+                </label>
+                <label class="toggle">
+                    <input
+                        type="checkbox"
+                        prop:checked=move || settings.with(|s| s.cross_check)
+                        on:change:target=move |ev| {
+                            settings.update(|s| s.cross_check = ev.target().checked())
+                        }
+                    />
+                    "Cross-check with reference solver"
+                </label>
+            </div>
+        </details>
+    }
+}
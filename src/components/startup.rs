@@ -0,0 +1,50 @@
+use leptos::prelude::*;
+
+use crate::startup::{Mark, FIRST_TABLE_BUDGET_MS};
+use crate::timing::startup_timings;
+
+/// Start-up timings of this page load, for `?debug` links: when each mark
+/// was reached and how long it took after the one before.
+#[component]
+pub fn StartupPanel() -> impl IntoView {
+    // marks keep arriving after mount; read them again on demand
+    let timings = RwSignal::new(startup_timings());
+    request_animation_frame(move || timings.set(startup_timings()));
+
+    let rows = move || {
+        let t = timings.get();
+        Mark::ALL.into_iter().map(|mark| {
+            let (at, step) = match (t.at(mark), t.since_previous(mark)) {
+                (Some(at), Some(step)) => (format!("{at:.0} ms"), format!("+{step:.0} ms")),
+                _ => ("—".to_string(), String::new()),
+            };
+            view! {
+                <tr>
+                    <th>{mark.label()}</th>
+                    <td>{at}</td>
+                    <td>{step}</td>
+                </tr>
+            }
+        }).collect_view()
+    };
+
+    view! {
+        <details class="settings-card startup-card" open>
+            <summary class="settings-title">"Start-up timings"</summary>
+            <div class="code-toolbar">
+                <button class="btn-small" on:click=move |_| timings.set(startup_timings())>"Refresh"</button>
+                <span class="toolbar-hint">"Milliseconds since the page was requested."</span>
+            </div>
+            <table class="metrics-table">
+                <tbody>{rows}</tbody>
+            </table>
+            <p class="complexity-row" class:over-budget=move || timings.with(|t| t.within_budget() == Some(false))>
+                {move || match timings.with(|t| t.within_budget()) {
+                    Some(true) => format!("First table within the {FIRST_TABLE_BUDGET_MS:.0} ms budget."),
+                    Some(false) => format!("First table over the {FIRST_TABLE_BUDGET_MS:.0} ms budget."),
+                    None => "No table drawn yet.".to_string(),
+                }}
+            </p>
+        </details>
+    }
+}
@@ -0,0 +1,29 @@
+use leptos::prelude::*;
+
+use crate::analysis::analyze;
+use crate::problem::Problem;
+
+/// Size, correlation and greedy-gap figures for the current instance, with a
+/// heuristic difficulty badge.
+#[component]
+pub fn InstanceStatsCard(#[prop(into)] problem: Signal<Option<Problem>>) -> impl IntoView {
+    let stats = Memo::new(move |_| problem.with(|p| p.as_ref().map(analyze)));
+
+    move || stats.get().map(|s| view! {
+        <section class="stats-card" aria-label="Instance statistics">
+            <span class=s.difficulty.class() title="Heuristic from correlation, greedy gap and table size">
+                {s.difficulty.label()}
+            </span>
+            <span class="stat">"n = "<strong>{s.n}</strong></span>
+            <span class="stat">"W = "<strong>{s.capacity}</strong></span>
+            <span class="stat">"cells ≈ "<strong>{s.cells}</strong></span>
+            <span class="stat" title="Pearson correlation of weights and benefits">
+                "corr = "
+                <strong>{s.correlation.map(|r| format!("{r:.2}")).unwrap_or_else(|| "–".into())}</strong>
+            </span>
+            <span class="stat" title="Greedy by ratio vs. the fractional upper bound">
+                "greedy gap ≤ "<strong>{format!("{:.1}%", s.greedy_gap() * 100.0)}</strong>
+            </span>
+        </section>
+    })
+}
@@ -0,0 +1,121 @@
+use leptos::prelude::*;
+
+use crate::format::Locale;
+use crate::parse::{format_decimal, parse_decimal};
+
+use super::toast::use_toasts;
+
+/// A text input flanked by −/+ buttons that keeps its value within
+/// `[min, max]`; typed or pasted values are clamped once committed. Values
+/// are read and written in `locale`, so `1.000` or `2,5` step like the
+/// numbers the form parses them as.
+#[component]
+pub fn NumberStepper(
+    #[prop(into)] id: String,
+    value: ReadSignal<String>,
+    set_value: WriteSignal<String>,
+    #[prop(into)] min: Signal<usize>,
+    #[prop(into)] max: Signal<usize>,
+    #[prop(into)] locale: Signal<Locale>,
+    #[prop(into)] label: String,
+    #[prop(optional, into)] invalid: Signal<bool>,
+    #[prop(optional)] on_enter: Option<Callback<()>>,
+) -> impl IntoView {
+    let toasts = use_toasts();
+    let label = StoredValue::new(label);
+
+    // The field as a fraction `(num, den)`
+    let current = move || parse_decimal(&value.get(), locale.get()).ok();
+    let below = move |(num, den): (usize, usize), bound: usize| num < bound.saturating_mul(den);
+    let above = move |(num, den): (usize, usize), bound: usize| num > bound.saturating_mul(den);
+
+    // Clamp whatever is in the field, warning when the value had to change
+    let clamp = move || {
+        let Some(v) = current() else { return };
+        let (lo, hi) = (min.get(), max.get());
+        let clamped = if below(v, lo) {
+            lo
+        } else if above(v, hi.max(lo)) {
+            hi.max(lo)
+        } else {
+            return;
+        };
+        set_value.set(clamped.to_string());
+        let bound = if clamped == lo { "minimum" } else { "maximum" };
+        toasts.warn(format!(
+            "{} {} is out of range; clamped to the {bound} of {clamped}.",
+            label.get_value(),
+            format_decimal(v, locale.get())
+        ));
+    };
+
+    // Move by one whole unit, keeping any decimals that were typed
+    let step_by = move |delta: isize| {
+        let (lo, hi) = (min.get(), max.get().max(min.get()));
+        let (num, den) = current().unwrap_or((lo, 1));
+        let stepped = (num.saturating_add_signed(delta.saturating_mul(den as isize)), den);
+        let text = if below(stepped, lo) {
+            lo.to_string()
+        } else if above(stepped, hi) {
+            hi.to_string()
+        } else {
+            format_decimal(stepped, locale.get())
+        };
+        set_value.set(text);
+    };
+
+    view! {
+        <div class="stepper">
+            <button
+                type="button"
+                class="stepper-btn"
+                aria-label="Decrease"
+                on:click=move |_| step_by(-1)
+                disabled=move || current().is_some_and(|v| !above(v, min.get()))
+            >
+                "−"
+            </button>
+            <input
+                id=id
+                type="text"
+                inputmode="decimal"
+                aria-invalid=move || invalid.get().to_string()
+                prop:value=move || value.get()
+                on:input:target=move |ev| set_value.set(ev.target().value())
+                on:change=move |_| clamp()
+                // the input event carrying the pasted text lands first
+                on:paste=move |_| request_animation_frame(clamp)
+                on:keydown=move |ev| {
+                    match ev.key().as_str() {
+                        "Enter" => {
+                            clamp();
+                            if let Some(cb) = on_enter {
+                                ev.prevent_default();
+                                cb.run(());
+                            }
+                        }
+                        // a text input does not step with the arrow keys by itself
+                        "ArrowUp" => {
+                            ev.prevent_default();
+                            step_by(1);
+                        }
+                        "ArrowDown" => {
+                            ev.prevent_default();
+                            step_by(-1);
+                        }
+                        _ => {}
+                    }
+                }
+            />
+            <button
+                type="button"
+                class="stepper-btn"
+                aria-label="Increase"
+                on:click=move |_| step_by(1)
+                disabled=move || current().is_some_and(|v| !below(v, max.get()))
+            >
+                "+"
+            </button>
+        </div>
+    }
+}
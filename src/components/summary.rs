@@ -0,0 +1,63 @@
+use leptos::prelude::*;
+
+use crate::format::NumberFormat;
+use crate::problem::Problem;
+use crate::reference::CrossCheck;
+
+/// Optimal value, chosen items and weight used for a solved instance.
+#[component]
+pub fn SolutionSummary(
+    problem: Problem,
+    best: usize,
+    /// 1-based item indices, ascending.
+    chosen: Vec<usize>,
+    fmt: NumberFormat,
+    #[prop(optional)] note: Option<String>,
+    /// Plain-language account of the answer, shown under the figures.
+    #[prop(optional)] explanation: Option<String>,
+    /// Result of the debug reference check, when enabled.
+    #[prop(default = None)] cross_check: Option<CrossCheck>,
+) -> impl IntoView {
+    let p = problem;
+    let used: usize = chosen.iter().map(|&i| p.weights[i - 1]).sum();
+    let items = if chosen.is_empty() {
+        "none".to_string()
+    } else {
+        chosen.iter()
+            .map(|&i| format!(
+                "#{} ({}, {})",
+                i, p.units.weight(p.weights[i - 1]), p.units.benefit(p.benefits[i - 1], &fmt)
+            ))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    view! {
+        <section class="summary-card">
+            <h2 class="summary-title">
+                "Solution"
+                {cross_check.map(|c| match c {
+                    CrossCheck::Agrees => view! {
+                        <span class="xcheck ok" title="Matches the brute-force / meet-in-the-middle optimum">
+                            "cross-checked ✓"
+                        </span>
+                    }.into_any(),
+                    CrossCheck::Disagrees { expected } => view! {
+                        <span class="xcheck bad">{format!("reference says {expected} ✗")}</span>
+                    }.into_any(),
+                    CrossCheck::Skipped => view! {
+                        <span class="xcheck" title="Too many items for the reference solver">"not cross-checked"</span>
+                    }.into_any(),
+                })}
+            </h2>
+            {note.map(|n| view! { <p class="summary-note">{n}</p> })}
+            <p>"Optimal benefit: "<strong title=best.to_string()>{p.units.benefit(best, &fmt)}</strong></p>
+            <p>"Items taken: "<strong>{items}</strong></p>
+            <p>
+                "Weight used: "<strong>{p.units.weight(used)}</strong>
+                " of "{p.units.weight(p.capacity)}
+            </p>
+            {explanation.map(|e| view! { <p class="summary-explanation">{e}</p> })}
+        </section>
+    }
+}
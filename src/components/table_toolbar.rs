@@ -0,0 +1,168 @@
+use std::collections::HashSet;
+
+use leptos::prelude::*;
+
+use crate::columns::ColumnFilter;
+use crate::order::FillOrder;
+use crate::settings::Settings;
+
+use super::drawing::Stroke;
+
+/// The row of controls above the DP table: display toggles, the fill order
+/// and column filter, capture, the drawing tools and the cell finder.
+#[component]
+pub fn TableToolbar(
+    settings: RwSignal<Settings>,
+    /// Offer the fill order; off when the host fixes the reveal plan.
+    fill_order: bool,
+    /// Cleared when the column filter changes.
+    expanded_cols: RwSignal<HashSet<usize>>,
+    on_capture: Callback<()>,
+    drawing: ReadSignal<bool>,
+    set_drawing: WriteSignal<bool>,
+    strokes: RwSignal<Vec<Stroke>>,
+    /// `2, 5` for a cell or `=9` for a value, looked up by `on_find`.
+    find_query: RwSignal<String>,
+    /// Outcome of the last lookup.
+    #[prop(into)]
+    find_msg: Signal<Option<String>>,
+    on_find: Callback<()>,
+) -> impl IntoView {
+    view! {
+        <div class="table-toolbar">
+            <label class="toggle">
+                <input
+                    type="checkbox"
+                    prop:checked=move || settings.with(|s| s.show_complexity_overlay)
+                    on:change:target=move |ev| {
+                        settings.update(|s| s.show_complexity_overlay = ev.target().checked())
+                    }
+                />
+                "Complexity overlay"
+            </label>
+            <label class="toggle">
+                <input
+                    type="checkbox"
+                    prop:checked=move || settings.with(|s| s.show_fill_ordinals)
+                    on:change:target=move |ev| {
+                        settings.update(|s| s.show_fill_ordinals = ev.target().checked())
+                    }
+                />
+                "Fill-order numbers"
+            </label>
+            <label class="toggle" title="Show each cell as its gain over the cell above">
+                <input
+                    type="checkbox"
+                    prop:checked=move || settings.with(|s| s.show_row_deltas)
+                    on:change:target=move |ev| {
+                        settings.update(|s| s.show_row_deltas = ev.target().checked())
+                    }
+                />
+                "Row deltas"
+            </label>
+            <label class="toggle" title="Shade each cell by its value as a share of the optimum">
+                <input
+                    type="checkbox"
+                    prop:checked=move || settings.with(|s| s.shade_by_optimum)
+                    on:change:target=move |ev| {
+                        settings.update(|s| s.shade_by_optimum = ev.target().checked())
+                    }
+                />
+                "Shade by % of optimum"
+            </label>
+            <label class="toggle" title="Show the weight each cell's best subset actually uses">
+                <input
+                    type="checkbox"
+                    prop:checked=move || settings.with(|s| s.show_weight_used)
+                    on:change:target=move |ev| {
+                        settings.update(|s| s.show_weight_used = ev.target().checked())
+                    }
+                />
+                "Weight used"
+            </label>
+            {fill_order.then(|| view! {
+                <label class="toggle">
+                    "Fill order"
+                    <select on:change:target=move |ev| {
+                        if let Some(o) = FillOrder::from_code(&ev.target().value()) {
+                            settings.update(|s| s.fill_order = o);
+                        }
+                    }>
+                        {FillOrder::ALL.into_iter().map(|o| view! {
+                            <option value=o.code() selected=move || settings.with(|s| s.fill_order) == o>{o.label()}</option>
+                        }).collect_view()}
+                    </select>
+                </label>
+            })}
+            <label class="toggle">
+                "Columns"
+                <select on:change:target=move |ev| {
+                    if let Some(f) = ColumnFilter::from_code(&ev.target().value()) {
+                        expanded_cols.set(Default::default());
+                        settings.update(|s| s.column_filter = f);
+                    }
+                }>
+                    {ColumnFilter::ALL.into_iter().map(|f| view! {
+                        <option value=f.code() selected=move || settings.with(|s| s.column_filter) == f>{f.label()}</option>
+                    }).collect_view()}
+                </select>
+            </label>
+            <label class="toggle">
+                <input
+                    type="checkbox"
+                    prop:checked=move || settings.with(|s| s.freeze_headers)
+                    on:change:target=move |ev| {
+                        settings.update(|s| s.freeze_headers = ev.target().checked())
+                    }
+                />
+                "Freeze headers"
+            </label>
+            <button class="btn-small" on:click=move |_| on_capture.run(()) title="Download the table as a PNG">
+                "📷 Capture"
+            </button>
+            <div class="draw-tools">
+                <label class="toggle">
+                    <input
+                        type="checkbox"
+                        prop:checked=move || drawing.get()
+                        on:change:target=move |ev| set_drawing.set(ev.target().checked())
+                    />
+                    "Draw"
+                </label>
+                <button
+                    class="btn-small"
+                    on:click=move |_| strokes.update(|s| _ = s.pop())
+                    disabled=move || strokes.with(Vec::is_empty)
+                >
+                    "Undo"
+                </button>
+                <button
+                    class="btn-small"
+                    on:click=move |_| strokes.set(Vec::new())
+                    disabled=move || strokes.with(Vec::is_empty)
+                >
+                    "Clear"
+                </button>
+            </div>
+            <div class="find-box">
+                <input
+                    type="text"
+                    class="find-input"
+                    aria-label="Find cell"
+                    placeholder="Find: 2, 5  or  =9"
+                    prop:value=move || find_query.get()
+                    on:input:target=move |ev| find_query.set(ev.target().value())
+                    on:keydown=move |ev| {
+                        if ev.key() == "Enter" {
+                            ev.prevent_default();
+                            on_find.run(());
+                        }
+                    }
+                />
+                <button class="btn-small" on:click=move |_| on_find.run(())>"Go"</button>
+                <span class="toolbar-hint" title="Double-click a cell to add or edit a note">"✎ dbl-click: note"</span>
+                {move || find_msg.get().map(|m| view! { <span class="find-msg">{m}</span> })}
+            </div>
+        </div>
+    }
+}
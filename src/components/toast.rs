@@ -0,0 +1,81 @@
+use std::time::Duration;
+
+use leptos::prelude::*;
+
+const TOAST_DURATION: Duration = Duration::from_secs(4);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ToastKind {
+    Info,
+    Warning,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Toast {
+    id: usize,
+    kind: ToastKind,
+    message: String,
+}
+
+/// Handle to the page's toast queue, shared through context.
+#[derive(Clone, Copy)]
+pub struct Toasts {
+    items: RwSignal<Vec<Toast>>,
+    next_id: StoredValue<usize>,
+}
+
+impl Toasts {
+    /// Show a message that dismisses itself after a few seconds.
+    pub fn push(&self, kind: ToastKind, message: impl Into<String>) {
+        let id = self.next_id.get_value();
+        self.next_id.set_value(id + 1);
+        self.items.update(|v| v.push(Toast { id, kind, message: message.into() }));
+
+        let this = *self;
+        set_timeout(move || this.dismiss(id), TOAST_DURATION);
+    }
+
+    pub fn warn(&self, message: impl Into<String>) {
+        self.push(ToastKind::Warning, message);
+    }
+
+    pub fn info(&self, message: impl Into<String>) {
+        self.push(ToastKind::Info, message);
+    }
+
+    pub fn dismiss(&self, id: usize) {
+        self.items.update(|v| v.retain(|t| t.id != id));
+    }
+}
+
+pub fn provide_toasts() -> Toasts {
+    let toasts = Toasts { items: RwSignal::new(Vec::new()), next_id: StoredValue::new(0) };
+    provide_context(toasts);
+    toasts
+}
+
+pub fn use_toasts() -> Toasts {
+    expect_context::<Toasts>()
+}
+
+/// Renders the queued toasts in the bottom corner of the page.
+#[component]
+pub fn ToastHost() -> impl IntoView {
+    let toasts = use_toasts();
+
+    view! {
+        <div class="toast-host" role="status" aria-live="polite">
+            <For each=move || toasts.items.get() key=|t| t.id let:toast>
+                <div class=match toast.kind {
+                    ToastKind::Info => "toast toast-info",
+                    ToastKind::Warning => "toast toast-warning",
+                }>
+                    <span>{toast.message.clone()}</span>
+                    <button class="toast-close" aria-label="Dismiss" on:click=move |_| toasts.dismiss(toast.id)>
+                        "×"
+                    </button>
+                </div>
+            </For>
+        </div>
+    }
+}
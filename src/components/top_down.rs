@@ -0,0 +1,181 @@
+use leptos::prelude::*;
+
+use crate::components::metrics::use_metrics;
+use crate::metrics::RunMetrics;
+use crate::problem::Problem;
+use crate::solver::{call_stack, requests_after, solve_top_down, TopDownSolution};
+use crate::timing::now_ms;
+
+/// Largest `(n + 1) × (m + 1)` plane drawn as a heatmap.
+const MAX_CELLS: usize = 40_000;
+
+/// Memoized top-down solve drawn as the `(i, w)` plane, each cell shaded by
+/// how many times `kp(i, w)` was requested. Cells asked for more than once
+/// are the overlapping subproblems the memo saves; blank cells were never
+/// reached, unlike the bottom-up table, which fills them all.
+///
+/// Stepping replays the recursion one call at a time: the open frames are
+/// listed as a call stack beside the plane, whose cells are marked and
+/// counted as of that step.
+#[component]
+pub fn TopDownPanel(#[prop(into)] problem: Signal<Option<Problem>>) -> impl IntoView {
+    let metrics = use_metrics();
+    let (result, set_result) = signal(Option::<Result<(Problem, TopDownSolution), String>>::None);
+    // Events replayed so far while stepping; None shows the finished run
+    let step = RwSignal::new(None::<usize>);
+    let events_len = move || result.with(|r| match r {
+        Some(Ok((_, s))) => s.events.len(),
+        _ => 0,
+    });
+
+    let on_run = move |_| {
+        let Some(p) = problem.get_untracked() else { return };
+        if (p.len() + 1) * (p.capacity + 1) > MAX_CELLS {
+            set_result.set(Some(Err(format!("The heatmap is drawn for at most {MAX_CELLS} subproblems."))));
+            return;
+        }
+        let t0 = now_ms();
+        let s = solve_top_down(&p);
+        metrics.record(RunMetrics {
+            solver: "top-down (memo)",
+            items: p.len(),
+            capacity: p.capacity,
+            best: s.best as u128,
+            cells: Some(s.computed),
+            cache_hits: Some(s.cache_hits),
+            elapsed_ms: Some(now_ms() - t0),
+            ..Default::default()
+        });
+        set_result.set(Some(Ok((p, s))));
+        step.set(None);
+    };
+    let on_step = move |_| {
+        let len = events_len();
+        step.update(|k| *k = match *k {
+            None => Some(1.min(len)),
+            Some(k) if k >= len => None,
+            Some(k) => Some(k + 1),
+        });
+    };
+    let on_back = move |_| {
+        step.update(|k| *k = match *k {
+            None => Some(events_len()),
+            Some(k) => Some(k.saturating_sub(1)),
+        });
+    };
+
+    let body = move || {
+        result.get().map(|outcome| match outcome {
+            Err(e) => view! { <p class="field-error">{e}</p> }.into_any(),
+            Ok((p, s)) => {
+                let total = (p.len() + 1) * (p.capacity + 1);
+                let peak = s.requests.iter().flatten().copied().max().unwrap_or(1).max(1);
+                let shown = step.get().map(|k| &s.events[..k.min(s.events.len())]);
+                let requests = match shown {
+                    Some(events) => requests_after(events, p.len(), p.capacity),
+                    None => s.requests.clone(),
+                };
+                let stack = shown.map(call_stack).unwrap_or_default();
+                let current = shown.and_then(|e| e.last()).map(|e| e.cell());
+                let rows = requests.iter().enumerate().map(|(i, row)| {
+                    let cells = row.iter().enumerate().map(|(w, &count)| {
+                        // a frame still on the stack has no value yet
+                        let value = s.memo[i][w].filter(|_| count > 0 && !stack.contains(&(i, w)));
+                        let cls = if current == Some((i, w)) {
+                            "hm-cell hm-current"
+                        } else if stack.contains(&(i, w)) {
+                            "hm-cell hm-on-stack"
+                        } else {
+                            "hm-cell"
+                        };
+                        let style = (count > 0).then(|| format!(
+                            "background: color-mix(in srgb, var(--accent) {:.0}%, transparent)",
+                            15.0 + 85.0 * count as f64 / peak as f64
+                        ));
+                        let title = match (value, count) {
+                            (Some(v), _) => format!("kp({i}, {w}) = {v}, requested {count}×"),
+                            (None, 0) => format!("kp({i}, {w}) never requested"),
+                            (None, _) => format!("kp({i}, {w}) in progress"),
+                        };
+                        view! {
+                            <td class=cls style=style title=title>
+                                {(count > 0).then(|| count.to_string())}
+                            </td>
+                        }
+                    }).collect_view();
+                    view! {
+                        <tr>
+                            <td class="item-header">{format!("i={i}")}</td>
+                            {cells}
+                        </tr>
+                    }
+                }).collect_view();
+                view! {
+                    <p class="complexity-row">
+                        {format!(
+                            "kp({}, {}) = {}: computed {} of {} subproblems ({:.0}%), {} calls answered from the memo.",
+                            p.len(), p.capacity, s.best, s.computed, total,
+                            s.computed as f64 * 100.0 / total as f64, s.cache_hits
+                        )}
+                    </p>
+                    {shown.map(|events| {
+                        let now = events.last().map_or_else(|| "ready to call kp(n, m)".to_string(), |e| e.describe());
+                        view! {
+                            <p class="narration" aria-live="polite">
+                                {format!("Step {} / {}: {now}", events.len(), s.events.len())}
+                            </p>
+                        }
+                    })}
+                    <div class="td-split">
+                        {shown.map(|_| {
+                            let depth = stack.len();
+                            let frames = stack.iter().enumerate().rev().map(|(d, &(i, w))| {
+                                let cls = if d + 1 == depth { "frame deepest" } else { "frame" };
+                                view! { <li class=cls>{format!("kp({i}, {w})")}</li> }
+                            }).collect_view();
+                            view! {
+                                <div class="call-stack" aria-label="Call stack">
+                                    <h3>{format!("Call stack (depth {depth})")}</h3>
+                                    <ol reversed>{frames}</ol>
+                                </div>
+                            }
+                        })}
+                        <div class="table-wrap">
+                            <table class="dp-table heatmap" aria-label="Requests per subproblem">
+                                <thead>
+                                    <tr>
+                                        <th class="corner">"i \\ w"</th>
+                                        {(0..=p.capacity).map(|w| view! { <th>{w}</th> }).collect_view()}
+                                    </tr>
+                                </thead>
+                                <tbody>{rows}</tbody>
+                            </table>
+                        </div>
+                    </div>
+                    <p class="toolbar-hint">{format!("Stronger shading means more requests (at most {peak}×).")}</p>
+                }.into_any()
+            }
+        })
+    };
+
+    view! {
+        <details class="settings-card td-card">
+            <summary class="settings-title">"Top-down (memoized recursion)"</summary>
+            <div class="code-toolbar">
+                <button class="btn-small" on:click=on_run disabled=move || problem.with(Option::is_none)>
+                    "▶ Solve top-down"
+                </button>
+                <button class="btn-small" on:click=on_back disabled=move || events_len() == 0 || step.get() == Some(0)>
+                    "◀ Back"
+                </button>
+                <button class="btn-small" on:click=on_step disabled=move || events_len() == 0>
+                    {move || if step.get().is_some() { "Step ▶" } else { "Step through" }}
+                </button>
+                <button class="btn-small" on:click=move |_| step.set(None) disabled=move || step.get().is_none()>
+                    "Finish"
+                </button>
+            </div>
+            {body}
+        </details>
+    }
+}
@@ -0,0 +1,168 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::analytics::{self, day_of, DayStats, Usage, UsageEvent};
+use crate::components::deferred::Deferred;
+use crate::files::download_text;
+use crate::storage::{self, open_store, Store};
+
+/// Days the charts span.
+const CHART_DAYS: usize = 30;
+
+/// Usage history of this browser, shared through context. Events are only
+/// kept while the user has the dashboard turned on, and once the store has
+/// opened and the earlier history has loaded.
+#[derive(Clone, Copy)]
+pub struct UsageLog {
+    enabled: RwSignal<bool>,
+    usage: RwSignal<Usage>,
+    store: StoredValue<Option<Store>, LocalStorage>,
+}
+
+impl UsageLog {
+    pub fn record(&self, event: UsageEvent) {
+        let Some(store) = self.store.get_value() else { return };
+        if !self.enabled.get_untracked() {
+            return;
+        }
+        let day = day_of(js_sys::Date::now());
+        self.usage.update(|u| u.record(day, event));
+        let usage = self.usage.get_untracked();
+        spawn_local(async move { _ = analytics::save(&store, &usage).await });
+    }
+
+    fn set_enabled(&self, on: bool) {
+        storage::set_analytics_enabled(on);
+        self.enabled.set(on);
+    }
+
+    fn clear(&self) {
+        self.usage.set(Usage::default());
+        if let Some(store) = self.store.get_value() {
+            spawn_local(async move { _ = analytics::clear(&store).await });
+        }
+    }
+}
+
+pub fn provide_usage() -> UsageLog {
+    let log = UsageLog {
+        enabled: RwSignal::new(storage::analytics_enabled()),
+        usage: RwSignal::new(Usage::default()),
+        store: StoredValue::new_local(None),
+    };
+    provide_context(log);
+    if !is_server() {
+        spawn_local(async move {
+            let store = open_store().await;
+            log.usage.set(analytics::load(&store).await);
+            log.store.set_value(Some(store));
+        });
+    }
+    log
+}
+
+/// The log of the enclosing workspace, or a fresh one for a lone visualizer.
+pub fn use_usage() -> UsageLog {
+    use_context().unwrap_or_else(provide_usage)
+}
+
+/// One bar per day; `value` gives its height as a share of the tallest.
+fn day_chart(days: &[DayStats], label: &'static str, value: fn(&DayStats) -> f64, title: fn(&DayStats) -> String) -> impl IntoView {
+    let peak = days.iter().map(value).fold(0.0, f64::max).max(f64::MIN_POSITIVE);
+    let bars = days.iter().map(|d| view! {
+        <div class="mc-bin" title=title(d)>
+            <div class="mc-bar fixed" style=format!("height: {:.1}%", value(d) * 100.0 / peak)></div>
+        </div>
+    }).collect_view();
+    view! {
+        <p class="usage-chart-title">{label}</p>
+        <div class="mc-chart usage-chart" role="img" aria-label=label>{bars}</div>
+    }
+}
+
+/// Solves, steps, graded answers and solvers used in this browser, kept in
+/// its local storage only and only once turned on here.
+#[component]
+pub fn UsageDashboard() -> impl IntoView {
+    let log = use_usage();
+    let (open, set_open) = signal(false);
+
+    let export = move |_| {
+        let json = log.usage.with_untracked(Usage::to_json);
+        _ = download_text("knapsack-usage.json", "application/json", &json);
+    };
+
+    let body = move || {
+        let usage = log.usage.get();
+        if usage.days.is_empty() {
+            return view! {
+                <p class="complexity-row">"Nothing recorded yet: solve or step through an instance."</p>
+            }.into_any();
+        }
+        let total = usage.totals();
+        let days = usage.recent(day_of(js_sys::Date::now()), CHART_DAYS);
+        let day_name = |d: &DayStats| {
+            String::from(js_sys::Date::new(&(d.day as f64 * 86_400_000.0).into()).to_iso_string()).chars().take(10).collect::<String>()
+        };
+        let mut modes: Vec<(String, u32)> = total.modes.clone().into_iter().collect();
+        modes.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        let top = modes.first().map_or(1, |m| m.1).max(1);
+        let mode_rows = modes.into_iter().map(|(mode, n)| view! {
+            <div class="rb-row">
+                <span class="usage-mode">{mode}</span>
+                <div class="rb-bars">
+                    <div class="rb-bar robust" style=format!("width: {:.1}%", n as f64 * 100.0 / top as f64)></div>
+                </div>
+                <span>{n}</span>
+            </div>
+        }).collect_view();
+        let first = day_name(&days[0]);
+        let last = day_name(&days[days.len() - 1]);
+
+        view! {
+            <p class="complexity-row">
+                <strong>{total.solved}</strong>" instances solved, "
+                <strong>{total.steps}</strong>" steps taken, "
+                {match total.accuracy() {
+                    Some(a) => format!("{:.0}% of {} graded answers correct", a * 100.0, total.answered),
+                    None => "no answers graded yet".to_string(),
+                }}
+                {format!(" over {} days of use.", usage.days.len())}
+            </p>
+            {day_chart(&days, "Solved per day", |d| d.solved as f64, |d| format!("{} solved", d.solved))}
+            {day_chart(&days, "Steps per day", |d| d.steps as f64, |d| format!("{} steps", d.steps))}
+            {day_chart(&days, "Answer accuracy per day", |d| d.accuracy().unwrap_or(0.0), |d| match d.accuracy() {
+                Some(a) => format!("{:.0}% of {}", a * 100.0, d.answered),
+                None => "no answers".to_string(),
+            })}
+            <div class="mc-axis"><span>{first}</span><span>{last}</span></div>
+            <p class="usage-chart-title">"Solves per solver"</p>
+            <div class="rb-chart">{mode_rows}</div>
+        }.into_any()
+    };
+
+    view! {
+        <details class="settings-card usage-card" on:toggle:target=move |ev| set_open.set(ev.target().open())>
+            <summary class="settings-title">"Your usage"</summary>
+            <div class="code-toolbar">
+                <label class="toggle">
+                    <input
+                        type="checkbox"
+                        prop:checked=log.enabled
+                        on:change:target=move |ev| log.set_enabled(ev.target().checked())
+                    />
+                    "Record my usage in this browser"
+                </label>
+                <button class="btn-small" on:click=export disabled=move || log.usage.with(|u| u.days.is_empty())>
+                    "Export .json"
+                </button>
+                <button class="btn-small" on:click=move |_| log.clear() disabled=move || log.usage.with(|u| u.days.is_empty())>
+                    "Clear data"
+                </button>
+            </div>
+            <p class="toolbar-hint">"Kept in this browser's storage only; nothing is sent anywhere."</p>
+            // the charts are only built once the card is first opened
+            <Deferred when=open>{body}</Deferred>
+        </details>
+    }
+}
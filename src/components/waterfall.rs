@@ -0,0 +1,84 @@
+use leptos::prelude::*;
+
+use crate::format::NumberFormat;
+use crate::problem::Problem;
+
+/// Waterfall charts of how each chosen item adds to the total benefit and to
+/// the weight used, in item order, ending in a total bar.
+#[component]
+pub fn ContributionWaterfall(
+    problem: Problem,
+    /// 1-based item indices, ascending.
+    chosen: Vec<usize>,
+    fmt: NumberFormat,
+) -> impl IntoView {
+    let p = problem;
+    let benefit: Vec<(usize, usize)> = chosen.iter().map(|&i| (i, p.benefits[i - 1])).collect();
+    let weight: Vec<(usize, usize)> = chosen.iter().map(|&i| (i, p.weights[i - 1])).collect();
+    let best: usize = benefit.iter().map(|&(_, b)| b).sum();
+    let used: usize = weight.iter().map(|&(_, w)| w).sum();
+
+    let units = p.units.clone();
+    let benefit_label = move |v: usize| units.benefit(v, &fmt);
+    let units = p.units.clone();
+    let weight_label = move |v: usize| units.weight(v);
+
+    view! {
+        <section class="summary-card waterfall-card">
+            <h2 class="summary-title">"Contributions"</h2>
+            {if chosen.is_empty() {
+                view! { <p class="summary-note">"No item fits, so nothing contributes."</p> }.into_any()
+            } else {
+                view! {
+                    <div class="waterfall-grid">
+                        <Waterfall title="Benefit" steps=benefit scale=best label=benefit_label />
+                        // weight is drawn against the full capacity to show the slack
+                        <Waterfall title="Weight" steps=weight scale=p.capacity label=weight_label />
+                    </div>
+                    <p class="summary-note">
+                        {format!("Weight used: {} of {}", p.units.weight(used), p.units.weight(p.capacity))}
+                    </p>
+                }.into_any()
+            }}
+        </section>
+    }
+}
+
+#[component]
+fn Waterfall(
+    title: &'static str,
+    /// `(item, amount)` in the order they are stacked.
+    steps: Vec<(usize, usize)>,
+    /// Amount drawn as the full bar width.
+    scale: usize,
+    label: impl Fn(usize) -> String + 'static,
+) -> impl IntoView {
+    let pct = move |v: usize| v as f64 * 100.0 / scale.max(1) as f64;
+    let total: usize = steps.iter().map(|&(_, v)| v).sum();
+    let mut running = 0;
+    let bars = steps.into_iter().map(|(item, v)| {
+        let style = format!("margin-left: {:.2}%; width: {:.2}%", pct(running), pct(v));
+        running += v;
+        view! {
+            <div class="wf-row">
+                <span class="wf-label">{format!("#{item}")}</span>
+                <div class="wf-track"><div class="wf-bar" style=style></div></div>
+                <span class="wf-value">{format!("+{}", label(v))}</span>
+            </div>
+        }
+    }).collect_view();
+
+    view! {
+        <div class="waterfall" role="img" aria-label=format!("{title} waterfall")>
+            <h3 class="wf-title">{title}</h3>
+            {bars}
+            <div class="wf-row wf-total">
+                <span class="wf-label">"Σ"</span>
+                <div class="wf-track">
+                    <div class="wf-bar" style=format!("width: {:.2}%", pct(total))></div>
+                </div>
+                <span class="wf-value">{label(total)}</span>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,59 @@
+use leptos::prelude::*;
+
+use crate::format::NumberFormat;
+use crate::solver::{WideProblem, WideSolution, trace_path};
+
+/// Finished table of a wide (u128) solve. Values are abbreviated with the
+/// chosen number format and exact on hover; the path to the optimum is
+/// highlighted. There is no stepping on this backend.
+#[component]
+pub fn WideTable(problem: WideProblem, solution: WideSolution, fmt: NumberFormat) -> impl IntoView {
+    let units = problem.units.clone();
+    let path: Vec<(usize, usize)> = trace_path(&solution.table, &problem.weights, problem.capacity)
+        .into_iter()
+        .filter(|hop| hop.taken)
+        .map(|hop| (hop.row, hop.col))
+        .collect();
+
+    let rows = solution.table.iter().enumerate().map(|(i, row)| {
+        let label = match i {
+            0 => "—".to_string(),
+            i => format!("{i}  w={}", units.weight(problem.weights[i - 1])),
+        };
+        let cells = row.iter().enumerate().map(|(c, &val)| {
+            let cls = if i == 0 {
+                "cell cell-base"
+            } else if path.contains(&(i, c)) {
+                "cell cell-backtrack"
+            } else {
+                "cell"
+            };
+            view! { <td class=cls title=format!("dp[{i}][{c}] = {val}")>{units.wide_benefit(val, &fmt)}</td> }
+        }).collect_view();
+        view! {
+            <tr>
+                <td class="item-header">{label}</td>
+                {cells}
+            </tr>
+        }
+    }).collect_view();
+
+    view! {
+        <section class="table-wrap wide-table" aria-label="DP table (wide values)">
+            <table class="dp-table">
+                <thead>
+                    <tr>
+                        <th class="corner">"item \\ w"</th>
+                        {(0..=problem.capacity).map(|w| view! { <th>{w}</th> }).collect_view()}
+                    </tr>
+                </thead>
+                <tbody>{rows}</tbody>
+            </table>
+            <p class="wide-answer">
+                "Optimum "<strong title=solution.best.to_string()>{units.wide_benefit(solution.best, &fmt)}</strong>
+                " with items "<strong>{format!("{:?}", solution.chosen)}</strong>
+                " (u128 values)."
+            </p>
+        </section>
+    }
+}
@@ -0,0 +1,118 @@
+use crate::problem::Problem;
+use crate::solver::knapsack_table;
+
+/// How one item position differs between two instances. Items are matched
+/// by position, since position `k` is row `k + 1` of both tables.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ItemChange {
+    Added { item: usize, weight: usize, benefit: usize },
+    Removed { item: usize, weight: usize, benefit: usize },
+    Changed { item: usize, from: (usize, usize), to: (usize, usize) },
+}
+
+impl ItemChange {
+    pub fn describe(self) -> String {
+        match self {
+            ItemChange::Added { item, weight, benefit } => format!("Item {item} added (w={weight}, b={benefit})"),
+            ItemChange::Removed { item, weight, benefit } => {
+                format!("Item {item} removed (was w={weight}, b={benefit})")
+            }
+            ItemChange::Changed { item, from, to } => {
+                let mut parts = Vec::new();
+                if from.0 != to.0 {
+                    parts.push(format!("w {} → {}", from.0, to.0));
+                }
+                if from.1 != to.1 {
+                    parts.push(format!("b {} → {}", from.1, to.1));
+                }
+                format!("Item {item} changed: {}", parts.join(", "))
+            }
+        }
+    }
+}
+
+/// The first table cell, scanning row by row over the columns both tables
+/// have, whose values differ.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Divergence {
+    pub row: usize,
+    pub col: usize,
+    pub values: (usize, usize),
+}
+
+/// What changed from instance `a` to instance `b`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InstanceDiff {
+    pub capacity: (usize, usize),
+    pub items: (usize, usize),
+    pub changes: Vec<ItemChange>,
+    pub optimum: (usize, usize),
+    pub divergence: Option<Divergence>,
+}
+
+impl InstanceDiff {
+    /// `b`'s optimum minus `a`'s.
+    pub fn optimum_delta(&self) -> i128 {
+        self.optimum.1 as i128 - self.optimum.0 as i128
+    }
+
+    pub fn to_markdown(&self, a: &str, b: &str) -> String {
+        let delta = match self.optimum_delta() {
+            0 => "unchanged".to_string(),
+            d => format!("{d:+}"),
+        };
+        let mut md = format!(
+            "# Instance diff: {a} → {b}\n\n\
+             | | {a} | {b} |\n|---|---:|---:|\n\
+             | Capacity | {} | {} |\n| Items | {} | {} |\n| Optimum | {} | {} ({delta}) |\n\n## Items\n\n",
+            self.capacity.0, self.capacity.1, self.items.0, self.items.1, self.optimum.0, self.optimum.1,
+        );
+        if self.changes.is_empty() {
+            md.push_str("No item differs.\n");
+        }
+        for change in &self.changes {
+            md.push_str(&format!("- {}\n", change.describe()));
+        }
+        md.push_str("\n## Tables\n\n");
+        md.push_str(&match self.divergence {
+            Some(d) => format!(
+                "The tables first differ in row {}: dp[{}][{}] is {} in {a} and {} in {b}.\n",
+                d.row, d.row, d.col, d.values.0, d.values.1
+            ),
+            None if self.items.0 == self.items.1 && self.capacity.0 == self.capacity.1 => {
+                "The tables are identical.\n".to_string()
+            }
+            None => "The tables agree on every cell they share.\n".to_string(),
+        });
+        md
+    }
+}
+
+pub fn diff(a: &Problem, b: &Problem) -> InstanceDiff {
+    let item = |p: &Problem, k: usize| (p.weights[k], p.benefits[k]);
+    let changes = (0..a.len().max(b.len()))
+        .filter_map(|k| match (k < a.len(), k < b.len()) {
+            (true, true) if item(a, k) != item(b, k) => {
+                Some(ItemChange::Changed { item: k + 1, from: item(a, k), to: item(b, k) })
+            }
+            (false, true) => Some(ItemChange::Added { item: k + 1, weight: b.weights[k], benefit: b.benefits[k] }),
+            (true, false) => Some(ItemChange::Removed { item: k + 1, weight: a.weights[k], benefit: a.benefits[k] }),
+            _ => None,
+        })
+        .collect();
+
+    let ta = knapsack_table(a.capacity, &a.weights, &a.benefits);
+    let tb = knapsack_table(b.capacity, &b.weights, &b.benefits);
+    let cols = a.capacity.min(b.capacity) + 1;
+    let divergence = ta.iter().zip(&tb).enumerate().find_map(|(row, (ra, rb))| {
+        (0..cols).find(|&col| ra[col] != rb[col]).map(|col| Divergence { row, col, values: (ra[col], rb[col]) })
+    });
+
+    InstanceDiff {
+        capacity: (a.capacity, b.capacity),
+        items: (a.len(), b.len()),
+        changes,
+        optimum: (ta[a.len()][a.capacity], tb[b.len()][b.capacity]),
+        divergence,
+    }
+}
@@ -0,0 +1,202 @@
+use serde::{Deserialize, Serialize};
+
+/// How tightly the DP table is packed: cell size, padding and font size.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Density {
+    #[default]
+    Comfortable,
+    Compact,
+    /// Fits roughly twice as many columns as `Comfortable`.
+    UltraCompact,
+}
+
+impl Density {
+    pub const ALL: [Density; 3] = [Density::Comfortable, Density::Compact, Density::UltraCompact];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Density::Comfortable => "comfortable",
+            Density::Compact => "compact",
+            Density::UltraCompact => "ultra",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|d| d.code() == code)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Density::Comfortable => "Comfortable",
+            Density::Compact => "Compact",
+            Density::UltraCompact => "Ultra-compact",
+        }
+    }
+
+    /// Class added to the table element.
+    pub fn class(self) -> &'static str {
+        match self {
+            Density::Comfortable => "density-comfortable",
+            Density::Compact => "density-compact",
+            Density::UltraCompact => "density-ultra",
+        }
+    }
+}
+
+/// Colour scheme of the whole page.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Theme {
+    #[default]
+    Dark,
+    /// Black background with saturated colours; every cell state keeps a
+    /// text contrast ratio of at least 7:1 (WCAG AAA).
+    HighContrast,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 2] = [Theme::Dark, Theme::HighContrast];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Theme::Dark => "dark",
+            Theme::HighContrast => "high-contrast",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|t| t.code() == code)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Theme::Dark => "Dark",
+            Theme::HighContrast => "High contrast",
+        }
+    }
+}
+
+/// UI font sizes offered in the settings, as a percentage of the default.
+pub const FONT_SCALES: [u16; 6] = [85, 100, 115, 130, 150, 175];
+
+/// Root font size in CSS pixels for a scale percentage.
+pub fn root_font_px(scale: u16) -> f64 {
+    16.0 * scale as f64 / 100.0
+}
+
+/// A section of the visualizer page that can be moved and resized.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Panel {
+    Form,
+    /// Instance statistics and the analysis panels.
+    Analysis,
+    /// Formula, code and loop trace.
+    Formula,
+    Table,
+    /// Solution summary and its charts.
+    Results,
+    Legend,
+}
+
+impl Panel {
+    pub const ALL: [Panel; 6] = [
+        Panel::Form,
+        Panel::Analysis,
+        Panel::Formula,
+        Panel::Table,
+        Panel::Results,
+        Panel::Legend,
+    ];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Panel::Form => "form",
+            Panel::Analysis => "analysis",
+            Panel::Formula => "formula",
+            Panel::Table => "table",
+            Panel::Results => "results",
+            Panel::Legend => "legend",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.code() == code)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Panel::Form => "Instance",
+            Panel::Analysis => "Analysis",
+            Panel::Formula => "Formula and code",
+            Panel::Table => "Table",
+            Panel::Results => "Results",
+            Panel::Legend => "Legend",
+        }
+    }
+}
+
+/// Arrangement of the page's panels: their order, any height the user
+/// dragged them to and which ones are folded away.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Layout {
+    /// Top to bottom. Panels missing from a saved layout go last.
+    pub order: Vec<Panel>,
+    /// Fixed heights in CSS pixels; unlisted panels fit their content.
+    pub heights: Vec<(Panel, u32)>,
+    /// Panels showing only their title bar.
+    pub collapsed: Vec<Panel>,
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Self { order: Panel::ALL.to_vec(), heights: Vec::new(), collapsed: Vec::new() }
+    }
+}
+
+impl Layout {
+    /// Place of `panel` from the top, 0-based.
+    pub fn position(&self, panel: Panel) -> usize {
+        self.order.iter().position(|&p| p == panel).unwrap_or_else(|| {
+            self.order.len() + Panel::ALL.iter().position(|&p| p == panel).unwrap_or(0)
+        })
+    }
+
+    /// Move `panel` to the place of `target`, shifting the panels between them.
+    pub fn move_to(&mut self, panel: Panel, target: Panel) {
+        let mut order: Vec<Panel> = Panel::ALL.to_vec();
+        order.sort_by_key(|&p| self.position(p));
+        let from = order.iter().position(|&p| p == panel).unwrap_or(0);
+        let to = order.iter().position(|&p| p == target).unwrap_or(0);
+        let moved = order.remove(from);
+        order.insert(to, moved);
+        self.order = order;
+    }
+
+    pub fn height(&self, panel: Panel) -> Option<u32> {
+        self.heights.iter().find(|(p, _)| *p == panel).map(|&(_, h)| h)
+    }
+
+    /// Fix `panel` at `height` pixels, or let it fit its content again.
+    pub fn set_height(&mut self, panel: Panel, height: Option<u32>) {
+        self.heights.retain(|(p, _)| *p != panel);
+        if let Some(h) = height {
+            self.heights.push((panel, h));
+        }
+    }
+
+    pub fn is_collapsed(&self, panel: Panel) -> bool {
+        self.collapsed.contains(&panel)
+    }
+
+    /// Fold `panel` to its title bar, or open it again.
+    pub fn toggle_collapsed(&mut self, panel: Panel) {
+        if self.is_collapsed(panel) {
+            self.collapsed.retain(|&p| p != panel);
+        } else {
+            self.collapsed.push(panel);
+        }
+    }
+}
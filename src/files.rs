@@ -0,0 +1,44 @@
+use leptos::prelude::*;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+/// Offer `contents` to the user as a downloaded file called `filename`.
+pub fn download_text(filename: &str, mime: &str, contents: &str) -> Result<(), JsValue> {
+    let parts = js_sys::Array::of1(&JsValue::from_str(contents));
+    let opts = web_sys::BlobPropertyBag::new();
+    opts.set_type(mime);
+    let blob = web_sys::Blob::new_with_str_sequence_and_options(&parts, &opts)?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob)?;
+
+    let a: web_sys::HtmlAnchorElement = document().create_element("a")?.unchecked_into();
+    a.set_href(&url);
+    a.set_download(filename);
+    a.click();
+    web_sys::Url::revoke_object_url(&url)
+}
+
+/// Offer an already encoded resource (e.g. a `data:` URL) as a download.
+pub fn download_url(filename: &str, url: &str) -> Result<(), JsValue> {
+    let a: web_sys::HtmlAnchorElement = document().create_element("a")?.unchecked_into();
+    a.set_href(url);
+    a.set_download(filename);
+    a.click();
+    Ok(())
+}
+
+/// Put `text` on the system clipboard.
+pub async fn copy_text(text: &str) -> Result<(), JsValue> {
+    JsFuture::from(window().navigator().clipboard().write_text(text)).await?;
+    Ok(())
+}
+
+/// Read the first file selected in a file `<input>` as text.
+pub async fn read_selected_file(input: &web_sys::HtmlInputElement) -> Option<Result<String, String>> {
+    let file = input.files()?.get(0)?;
+    let text = JsFuture::from(file.text()).await;
+    Some(
+        text.ok()
+            .and_then(|v| v.as_string())
+            .ok_or_else(|| format!("Could not read '{}'.", file.name())),
+    )
+}
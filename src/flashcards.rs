@@ -0,0 +1,60 @@
+use crate::format::NumberFormat;
+use crate::narration::narrate;
+use crate::problem::Problem;
+
+/// Largest deck exported; bigger tables have far more cards than anyone drills.
+pub const MAX_CARDS: usize = 2_000;
+
+/// One drill: the front gives everything needed to fill `dp[row][col]`, the
+/// back its value and how the recurrence got there.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Card {
+    pub row: usize,
+    pub col: usize,
+    pub front: String,
+    pub back: String,
+}
+
+/// A card for every cell the recurrence fills, `dp[1..=n][1..=m]`, in
+/// row-major order.
+pub fn deck(table: &[Vec<usize>], problem: &Problem, fmt: &NumberFormat) -> Vec<Card> {
+    let units = &problem.units;
+    let mut cards = Vec::new();
+    for row in 1..table.len() {
+        let (w, b) = (problem.weights[row - 1], problem.benefits[row - 1]);
+        for col in 1..table[row].len() {
+            let above = format!("dp[{}][{col}] = {}", row - 1, units.benefit(table[row - 1][col], fmt));
+            let front = if w > col {
+                format!("dp[{row}][{col}] given wt_{row} = {}, b_{row} = {} and {above}", units.weight(w), units.benefit(b, fmt))
+            } else {
+                format!(
+                    "dp[{row}][{col}] given wt_{row} = {}, b_{row} = {}, {above} and dp[{}][{}] = {}",
+                    units.weight(w),
+                    units.benefit(b, fmt),
+                    row - 1,
+                    col - w,
+                    units.benefit(table[row - 1][col - w], fmt),
+                )
+            };
+            let back = format!(
+                "{}. {}",
+                units.benefit(table[row][col], fmt),
+                narrate(table, problem, row, col, fmt)
+            );
+            cards.push(Card { row, col, front, back });
+        }
+    }
+    cards
+}
+
+/// The deck as tab-separated lines, with the header lines Anki reads to pick
+/// the separator; other flashcard tools skip or import them as one card.
+pub fn to_tsv(cards: &[Card]) -> String {
+    // a field can't hold the separator or a line break
+    let field = |s: &str| s.replace(['\t', '\n', '\r'], " ");
+    let mut tsv = String::from("#separator:tab\n#html:false\n");
+    for card in cards {
+        tsv.push_str(&format!("{}\t{}\n", field(&card.front), field(&card.back)));
+    }
+    tsv
+}
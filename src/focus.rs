@@ -0,0 +1,54 @@
+use std::time::Duration;
+
+use leptos::prelude::*;
+use wasm_bindgen::JsCast;
+
+const FLASH_DURATION: Duration = Duration::from_millis(1200);
+
+/// Keeps element ids unique when several visualizers share the page, e.g.
+/// in workspace tabs. The first instance keeps the plain ids.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct IdScope(pub usize);
+
+impl IdScope {
+    pub fn id(self, base: &str) -> String {
+        if self.0 == 0 { base.to_string() } else { format!("{base}-{}", self.0) }
+    }
+}
+
+/// The scope of the enclosing visualizer; unscoped outside one.
+pub fn id_scope() -> IdScope {
+    use_context().unwrap_or_default()
+}
+
+/// Move keyboard focus to the element with `id`, if it exists.
+pub fn focus_by_id(id: &str) {
+    if let Some(el) = document()
+        .get_element_by_id(id)
+        .and_then(|el| el.dyn_into::<web_sys::HtmlElement>().ok())
+    {
+        _ = el.focus();
+    }
+}
+
+/// Focus `id` on the next animation frame, once the view update that
+/// creates or re-renders it has been applied to the DOM.
+pub fn focus_after_render(id: String) {
+    request_animation_frame(move || focus_by_id(&id));
+}
+
+/// Scroll the element with `id` to the middle of the viewport and briefly
+/// add the `flash` class so the eye can find it.
+pub fn scroll_and_flash(id: &str) {
+    let Some(el) = document().get_element_by_id(id) else { return };
+
+    let opts = web_sys::ScrollIntoViewOptions::new();
+    opts.set_behavior(web_sys::ScrollBehavior::Smooth);
+    opts.set_block(web_sys::ScrollLogicalPosition::Center);
+    opts.set_inline(web_sys::ScrollLogicalPosition::Center);
+    el.scroll_into_view_with_scroll_into_view_options(&opts);
+
+    let classes = el.class_list();
+    _ = classes.add_1("flash");
+    set_timeout(move || _ = classes.remove_1("flash"), FLASH_DURATION);
+}
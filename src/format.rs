@@ -0,0 +1,220 @@
+use serde::{Deserialize, Serialize};
+
+// ─── Locale ──────────────────────────────────────────────────────────────────
+
+/// Locale used to pick the digit-group and decimal separators.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Locale {
+    /// `12,500.5`
+    #[default]
+    En,
+    /// `12.500,5`
+    De,
+    /// `12 500,5`
+    Fr,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 3] = [Locale::En, Locale::De, Locale::Fr];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::De => "de",
+            Locale::Fr => "fr",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|l| l.code() == code)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Locale::En => "English",
+            Locale::De => "German",
+            Locale::Fr => "French",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Locale::En => "English (1,000.5)",
+            Locale::De => "German (1.000,5)",
+            Locale::Fr => "French (1 000,5)",
+        }
+    }
+
+    pub fn group_separator(self) -> char {
+        match self {
+            Locale::En => ',',
+            Locale::De => '.',
+            Locale::Fr => '\u{202F}', // narrow no-break space
+        }
+    }
+
+    pub fn decimal_separator(self) -> char {
+        match self {
+            Locale::En => '.',
+            Locale::De | Locale::Fr => ',',
+        }
+    }
+}
+
+// ─── Number formatting ───────────────────────────────────────────────────────
+
+/// How large values are written in cells and summaries.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NumberStyle {
+    /// `12500`
+    #[default]
+    Plain,
+    /// `12,500`
+    Grouped,
+    /// `12.5k`
+    Compact,
+    /// `1.2e12`, for wide values far beyond the `k`…`T` suffixes
+    Scientific,
+}
+
+impl NumberStyle {
+    pub const ALL: [NumberStyle; 4] =
+        [NumberStyle::Plain, NumberStyle::Grouped, NumberStyle::Compact, NumberStyle::Scientific];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            NumberStyle::Plain => "plain",
+            NumberStyle::Grouped => "grouped",
+            NumberStyle::Compact => "compact",
+            NumberStyle::Scientific => "scientific",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| s.code() == code)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            NumberStyle::Plain => "Plain (12500)",
+            NumberStyle::Grouped => "Grouped (12,500)",
+            NumberStyle::Compact => "Compact (12.5k)",
+            NumberStyle::Scientific => "Scientific (1.2e12)",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NumberFormat {
+    pub style: NumberStyle,
+    pub locale: Locale,
+}
+
+impl NumberFormat {
+    pub fn format(&self, value: usize) -> String {
+        self.format_wide(value as u128)
+    }
+
+    /// [`format`](Self::format) for values of the wide (u128) backend.
+    pub fn format_wide(&self, value: u128) -> String {
+        match self.style {
+            NumberStyle::Plain => value.to_string(),
+            NumberStyle::Grouped => group_digits(value, self.locale.group_separator()),
+            NumberStyle::Compact => compact(value, self.locale),
+            NumberStyle::Scientific => scientific(value, self.locale),
+        }
+    }
+}
+
+fn group_digits(value: u128, sep: char) -> String {
+    let digits = value.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, d) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(sep);
+        }
+        out.push(d);
+    }
+    out
+}
+
+/// `999` → `999`, `12500` → `12.5k`, `3000000` → `3M`, one decimal at most.
+fn compact(value: u128, locale: Locale) -> String {
+    const SUFFIXES: [(u128, &str); 4] = [
+        (1_000_000_000_000, "T"),
+        (1_000_000_000, "B"),
+        (1_000_000, "M"),
+        (1_000, "k"),
+    ];
+
+    let Some(mut at) = SUFFIXES.iter().position(|(base, _)| value >= *base) else {
+        return value.to_string();
+    };
+
+    // round to one decimal place in integer arithmetic
+    let tenths_of = |base: u128| value.saturating_mul(10).saturating_add(base / 2) / base;
+    // 999_999 rounds to 1000k, which is written 1M
+    if tenths_of(SUFFIXES[at].0) >= 10_000 && at > 0 {
+        at -= 1;
+    }
+    let (base, suffix) = SUFFIXES[at];
+    let tenths = tenths_of(base);
+    let (whole, frac) = (tenths / 10, tenths % 10);
+    if frac == 0 {
+        format!("{whole}{suffix}")
+    } else {
+        format!("{whole}{}{frac}{suffix}", locale.decimal_separator())
+    }
+}
+
+/// `999` → `999`, `1234` → `1.2e3`, `3000000` → `3e6`, one decimal at most.
+fn scientific(value: u128, locale: Locale) -> String {
+    if value < 1_000 {
+        return value.to_string();
+    }
+    let mut exp = value.ilog10();
+    // leading two digits, rounded half up on the third
+    let mut tenths = (value / 10u128.pow(exp - 2) + 5) / 10;
+    if tenths >= 100 {
+        tenths /= 10;
+        exp += 1;
+    }
+    let (whole, frac) = (tenths / 10, tenths % 10);
+    if frac == 0 {
+        format!("{whole}e{exp}")
+    } else {
+        format!("{whole}{}{frac}e{exp}", locale.decimal_separator())
+    }
+}
+
+/// `912 B`, `1.5 KiB`, `24.0 MiB`.
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compact_promotes_values_that_round_up_to_the_next_suffix() {
+        assert_eq!(compact(999, Locale::En), "999");
+        assert_eq!(compact(12_500, Locale::En), "12.5k");
+        assert_eq!(compact(999_949, Locale::En), "999.9k");
+        assert_eq!(compact(999_999, Locale::En), "1M");
+        assert_eq!(compact(999_999_999, Locale::En), "1B");
+        assert_eq!(compact(1_250_000, Locale::De), "1,3M");
+    }
+}
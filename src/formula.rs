@@ -1,5 +1,14 @@
 use leptos::prelude::*;
 use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+
+use crate::codegen::{Branch, LoopStep};
+use crate::components::active_cell::use_active_cell;
+use crate::problem::Problem;
+
+/// How long to wait for KaTeX's deferred script before showing the source.
+const KATEX_WAIT_MS: i32 = 5_000;
+const KATEX_POLL_MS: i32 = 50;
 
 #[wasm_bindgen]
 extern "C" {
@@ -17,28 +26,117 @@ fn render_latex(latex: &str) -> String {
     katex_render(latex, &opts)
 }
 
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// The LaTeX itself, for when KaTeX is missing.
+fn render_source(latex: &str) -> String {
+    format!("<pre>{}</pre>", escape_html(latex.trim()))
+}
+
+/// Whether KaTeX has loaded. Its script is deferred (and may be blocked or
+/// offline), so it is polled for up to [`KATEX_WAIT_MS`].
+async fn katex_ready() -> bool {
+    let loaded = || js_sys::Reflect::has(&js_sys::global(), &JsValue::from_str("katex")).unwrap_or(false);
+    for _ in 0..KATEX_WAIT_MS / KATEX_POLL_MS {
+        if loaded() {
+            return true;
+        }
+        let pause = js_sys::Promise::new(&mut |resolve, _| {
+            _ = window().set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, KATEX_POLL_MS);
+        });
+        _ = JsFuture::from(pause).await;
+    }
+    loaded()
+}
+
+/// The recurrence, with the case `active` took drawn in the accent colour.
+fn recurrence(active: Option<Branch>) -> String {
+    let mark = |on: bool, tex: &str| if on { format!(r"\color{{#4ae8b0}}{{{tex}}}") } else { tex.to_string() };
+    let heavy = active == Some(Branch::TooHeavy);
+    let max = matches!(active, Some(Branch::Skip | Branch::Take));
+    format!(
+        r"dp[i][w] = \begin{{cases}}
+            0 & \text{{if }} i = 0 \text{{ or }} w = 0 \\[6pt]
+            {} & {} \\[6pt]
+            {} & {}
+        \end{{cases}}",
+        mark(heavy, "dp[i-1][w]"),
+        mark(heavy, r"\text{if } wt_i > w"),
+        mark(max, r"\max\bigl(dp[i-1][w],\ dp[i-1][w - wt_i] + b_i\bigr)"),
+        mark(max, r"\text{if } wt_i \leq w"),
+    )
+}
+
+/// The recurrence for `step` with the instance's numbers filled in.
+fn substituted(table: &[Vec<usize>], p: &Problem, step: LoopStep) -> String {
+    let (i, w) = (step.row, step.col);
+    let (wt, b) = (p.weights[i - 1], p.benefits[i - 1]);
+    let above = table[i - 1][w];
+    match step.branch {
+        Branch::TooHeavy => format!(
+            r"dp[{i}][{w}] = dp[{}][{w}] = {above} \qquad (wt_{{{i}}} = {wt} > {w})",
+            i - 1
+        ),
+        Branch::Skip | Branch::Take => format!(
+            r"dp[{i}][{w}] = \max\bigl(dp[{prev}][{w}],\ dp[{prev}][{w} - {wt}] + {b}\bigr) = \max({above},\ {} + {b}) = {}",
+            table[i - 1][w - wt],
+            table[i][w],
+            prev = i - 1,
+        ),
+    }
+}
+
 #[component]
-pub fn KnapsackFormula() -> impl IntoView {
-    let latex = r#"
-        dp[i][w] = \begin{cases}
-            0 & \text{if } i = 0 \text{ or } w = 0 \\[6pt]
-            dp[i-1][w] & \text{if } wt_i > w \\[6pt]
-            \max\bigl(dp[i-1][w],\ dp[i-1][w - wt_i] + b_i\bigr) & \text{if } wt_i \leq w
-        \end{cases}
-    "#;
+pub fn KnapsackFormula(
+    /// Extra content rendered under the legend, e.g. a complexity readout.
+    #[prop(optional)]
+    children: Option<Children>,
+) -> impl IntoView {
+    // Inside a visualizer the active step picks out its case and numbers
+    let active = use_active_cell();
+    let step = move || active.and_then(|a| a.step.get());
+    let latex = move || recurrence(step().map(|s| s.branch));
+    let numbers = move || {
+        let a = active?;
+        let step = a.step.get()?;
+        a.table.with(|t| t.as_ref().map(|t| a.problem.with(|p| substituted(t, p, step))))
+    };
 
-    let html = render_latex(latex);
+    // Typeset once KaTeX is there; the SSR server has none, so both the
+    // server and the hydrating client show the placeholder until then
+    let katex = LocalResource::new(katex_ready);
+    let html = RwSignal::new(String::new());
+    let numbers_html = RwSignal::new(None::<String>);
+    Effect::new(move |_| {
+        let typeset: fn(&str) -> String = match katex.get() {
+            Some(true) => render_latex,
+            Some(false) => render_source,
+            None => return,
+        };
+        html.set(typeset(&latex()));
+        numbers_html.set(numbers().map(|tex| typeset(&tex)));
+    });
 
     view! {
         <section class="formula-card">
             <h2 class="formula-title">"Recurrent Function"</h2>
-            <div class="formula-body" inner_html=html />
+            <Suspense fallback=|| view! { <div class="formula-body formula-skeleton" aria-busy="true"></div> }>
+                {move || katex.get().map(|_| view! {
+                    <div class="formula-body" inner_html=html />
+                    {move || numbers_html.get().map(|h| view! {
+                        <div class="formula-body formula-numbers" aria-live="polite" inner_html=h />
+                    })}
+                })}
+            </Suspense>
             <div class="formula-legend">
                 <span><strong class="accent">"i"</strong>" — item index"</span>
                 <span><strong class="accent">"w"</strong>" — current capacity"</span>
                 <span><strong class="accent">"wt"</strong><sub>"i"</sub>" — weight of item i"</span>
                 <span><strong class="accent">"b"</strong><sub>"i"</sub>" — benefit of item i"</span>
             </div>
+            {children.map(|c| c())}
         </section>
     }
 }
@@ -0,0 +1,33 @@
+use leptos::prelude::*;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+
+/// Ask the browser to show the whole page full-screen. Browsers may refuse
+/// (e.g. outside a user gesture); the page still works windowed.
+pub fn enter() {
+    if let Some(root) = document().document_element() {
+        _ = root.request_fullscreen();
+    }
+}
+
+pub fn exit() {
+    if is_active() {
+        document().exit_fullscreen();
+    }
+}
+
+pub fn is_active() -> bool {
+    document().fullscreen_element().is_some()
+}
+
+/// Run `f` whenever the page enters or leaves full-screen, including when
+/// the user leaves with Esc (which never reaches our key handlers).
+pub fn on_change(f: impl Fn(bool) + 'static) {
+    if is_server() {
+        return;
+    }
+    let cb = Closure::<dyn Fn()>::new(move || f(is_active()));
+    _ = document().add_event_listener_with_callback("fullscreenchange", cb.as_ref().unchecked_ref());
+    // lives as long as the page
+    cb.forget();
+}
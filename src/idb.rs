@@ -0,0 +1,88 @@
+//! A string key–value store in the browser's IndexedDB, which holds far more
+//! than the few megabytes local storage allows.
+
+use leptos::prelude::*;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{IdbDatabase, IdbObjectStore, IdbRequest, IdbTransactionMode};
+
+use crate::store::StateStore;
+
+const DB_NAME: &str = "knapsack-dp";
+const DB_VERSION: u32 = 1;
+const STORE: &str = "records";
+
+#[derive(Clone)]
+pub struct IdbStore {
+    db: IdbDatabase,
+}
+
+impl IdbStore {
+    /// Open (creating on first use) the app's database. Fails where
+    /// IndexedDB is missing or blocked, e.g. some private windows.
+    pub async fn open() -> Result<Self, String> {
+        let factory = window()
+            .indexed_db()
+            .ok()
+            .flatten()
+            .ok_or_else(|| "IndexedDB is not available.".to_string())?;
+        let request = factory.open_with_u32(DB_NAME, DB_VERSION).map_err(describe)?;
+        let opening = request.clone();
+        let upgrade = Closure::once_into_js(move || {
+            // only runs for a new database, or when DB_VERSION goes up
+            if let Ok(db) = opening.result().and_then(|r| r.dyn_into::<IdbDatabase>()) {
+                _ = db.create_object_store(STORE);
+            }
+        });
+        request.set_onupgradeneeded(Some(upgrade.unchecked_ref()));
+        let db = settle(&request).await?;
+        Ok(IdbStore { db: db.unchecked_into() })
+    }
+
+    fn records(&self, mode: IdbTransactionMode) -> Result<IdbObjectStore, String> {
+        self.db.transaction_with_str_and_mode(STORE, mode).and_then(|tx| tx.object_store(STORE)).map_err(describe)
+    }
+}
+
+impl StateStore for IdbStore {
+    async fn get(&self, key: &str) -> Result<Option<String>, String> {
+        let request = self.records(IdbTransactionMode::Readonly)?.get(&key.into()).map_err(describe)?;
+        Ok(settle(&request).await?.as_string())
+    }
+
+    async fn put(&self, key: &str, value: &str) -> Result<(), String> {
+        let request = self
+            .records(IdbTransactionMode::Readwrite)?
+            .put_with_key(&value.into(), &key.into())
+            .map_err(describe)?;
+        settle(&request).await.map(drop)
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), String> {
+        let request = self.records(IdbTransactionMode::Readwrite)?.delete(&key.into()).map_err(describe)?;
+        settle(&request).await.map(drop)
+    }
+}
+
+/// Wait for a request to succeed or fail, yielding its result.
+async fn settle(request: &IdbRequest) -> Result<JsValue, String> {
+    let promise = js_sys::Promise::new(&mut |resolve, reject| {
+        let done = request.clone();
+        let on_success = Closure::once_into_js(move || {
+            _ = resolve.call1(&JsValue::NULL, &done.result().unwrap_or(JsValue::UNDEFINED));
+        });
+        let failed = request.clone();
+        let on_error = Closure::once_into_js(move || {
+            let message = failed.error().ok().flatten().map(|e| e.message());
+            _ = reject.call1(&JsValue::NULL, &message.unwrap_or_else(|| "request failed".into()).into());
+        });
+        request.set_onsuccess(Some(on_success.unchecked_ref()));
+        request.set_onerror(Some(on_error.unchecked_ref()));
+    });
+    JsFuture::from(promise).await.map_err(describe)
+}
+
+fn describe(e: JsValue) -> String {
+    format!("IndexedDB: {}", e.as_string().unwrap_or_else(|| format!("{e:?}")))
+}
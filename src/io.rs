@@ -0,0 +1,162 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::problem::Problem;
+use crate::schema::SCHEMA_VERSION;
+use crate::settings::Settings;
+
+/// Version written into every `.knap` file; follows the schema, whose
+/// types make up most of [`KnapFile`].
+pub const KNAP_VERSION: u32 = SCHEMA_VERSION;
+
+/// How far the walkthrough had got when the state was saved.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "revealed", rename_all = "snake_case")]
+pub enum StepPosition {
+    /// No table had been built.
+    #[default]
+    Unsolved,
+    /// Step mode with this many data cells revealed.
+    Stepping(usize),
+    /// The table was fully revealed.
+    Solved,
+}
+
+/// Free-text notes keyed by `(row, col)` of the table cell they belong to.
+pub type CellNotes = BTreeMap<(usize, usize), String>;
+
+/// One cell note as stored on disk.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CellNote {
+    pub row: usize,
+    pub col: usize,
+    pub text: String,
+}
+
+/// Complete saved state of the visualizer, stored as JSON in `.knap` files.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct KnapFile {
+    pub version: u32,
+    pub problem: Problem,
+    pub settings: Settings,
+    pub step: StepPosition,
+    #[serde(default)]
+    pub notes: Vec<CellNote>,
+}
+
+impl KnapFile {
+    pub fn new(problem: Problem, settings: Settings, step: StepPosition, notes: &CellNotes) -> Self {
+        let notes = notes
+            .iter()
+            .map(|(&(row, col), text)| CellNote { row, col, text: text.clone() })
+            .collect();
+        Self { version: KNAP_VERSION, problem, settings, step, notes }
+    }
+
+    /// Notes that fall inside the saved problem's table.
+    pub fn cell_notes(&self) -> CellNotes {
+        let (rows, cols) = (self.problem.len(), self.problem.capacity);
+        self.notes
+            .iter()
+            .filter(|n| n.row <= rows && n.col <= cols)
+            .map(|n| ((n.row, n.col), n.text.clone()))
+            .collect()
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("state is always serializable")
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, String> {
+        Self::load(text).map(|loaded| loaded.file)
+    }
+
+    /// Parse a file of this or any older version, upgrading it on the way.
+    pub fn load(text: &str) -> Result<Loaded, String> {
+        let doc: Value = serde_json::from_str(text).map_err(|e| format!("Not a valid .knap file: {e}"))?;
+        let (doc, upgraded_from) = migrate(doc)?;
+        let file: KnapFile = serde_json::from_value(doc).map_err(|e| format!("Not a valid .knap file: {e}"))?;
+        file.problem.validate().map_err(|e| format!("Not a usable .knap file: {e}"))?;
+        Ok(Loaded { file, upgraded_from })
+    }
+}
+
+/// A file as read, with the version it was saved at when that was older.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Loaded {
+    pub file: KnapFile,
+    pub upgraded_from: Option<u32>,
+}
+
+// ─── Migrations ──────────────────────────────────────────────────────────────
+
+/// Rewrites the JSON of a version `v` file into version `v + 1`.
+type Migration = fn(Value) -> Result<Value, String>;
+
+/// `MIGRATIONS[v - 1]` upgrades version `v`; one entry per version bump.
+const MIGRATIONS: [Migration; KNAP_VERSION as usize - 1] = [v1_to_v2];
+
+/// Run every migration between the file's version and [`KNAP_VERSION`].
+pub fn migrate(mut doc: Value) -> Result<(Value, Option<u32>), String> {
+    let version = doc
+        .get("version")
+        .and_then(Value::as_u64)
+        .and_then(|v| u32::try_from(v).ok())
+        .filter(|&v| v >= 1)
+        .ok_or("Not a valid .knap file: missing version.")?;
+    if version > KNAP_VERSION {
+        return Err(format!(
+            "This file is .knap version {version}, from a newer release (this app reads up to {KNAP_VERSION})."
+        ));
+    }
+    for (v, step) in (version..KNAP_VERSION).zip(&MIGRATIONS[version as usize - 1..]) {
+        doc = step(doc).map_err(|e| format!("Could not upgrade the file from version {v}: {e}"))?;
+        doc["version"] = (v + 1).into();
+    }
+    Ok((doc, (version < KNAP_VERSION).then_some(version)))
+}
+
+/// v2 stores the problem as `items` records instead of parallel lists.
+fn v1_to_v2(mut doc: Value) -> Result<Value, String> {
+    let problem = doc.get_mut("problem").and_then(Value::as_object_mut).ok_or("no problem")?;
+    let list = |v: Option<Value>| -> Result<Vec<Value>, String> {
+        match v {
+            Some(Value::Array(a)) => Ok(a),
+            _ => Err("weights and benefits must be lists".into()),
+        }
+    };
+    let weights = list(problem.remove("weights"))?;
+    let benefits = list(problem.remove("benefits"))?;
+    if weights.len() != benefits.len() {
+        return Err("different number of weights and benefits".into());
+    }
+    let items = weights
+        .into_iter()
+        .zip(benefits)
+        .map(|(weight, benefit)| serde_json::json!({ "weight": weight, "benefit": benefit }))
+        .collect();
+    problem.insert("items".into(), Value::Array(items));
+    Ok(doc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn files_whose_benefits_overflow_are_rejected() {
+        let mut file = KnapFile::new(
+            Problem { capacity: 5, weights: vec![1, 2], benefits: vec![usize::MAX, 1], ..Default::default() },
+            Settings::default(),
+            StepPosition::Unsolved,
+            &CellNotes::new(),
+        );
+        let err = KnapFile::load(&file.to_json()).unwrap_err();
+        assert!(err.contains("Benefits add up to more than"), "{err}");
+
+        file.problem.benefits = vec![3, 4];
+        assert_eq!(KnapFile::load(&file.to_json()).map(|l| l.file), Ok(file));
+    }
+}
@@ -1,214 +1,1389 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::channel::mpsc;
+use futures::StreamExt;
 use leptos::prelude::*;
-use crate::formula::KnapsackFormula;
+use leptos::task::spawn_local;
+use wasm_bindgen::JsCast;
+
+use crate::analysis::{simplify, weight_gcd};
+use crate::analytics::UsageEvent;
+use crate::capture::capture_png;
+use crate::codegen::{Branch, LoopStep};
+use crate::columns::column_groups;
+use crate::components::active_cell::provide_active_cell;
+use crate::components::answer_strip::AnswerStrip;
+use crate::components::bag::PackingBag;
+use crate::components::bounded::DuplicateMerge;
+use crate::components::cardinality::CardinalityTable;
+use crate::components::code_panel::CodePanel;
+use crate::components::complexity::ComplexityReadout;
+use crate::components::diagnostics::DegenerateBanners;
+use crate::components::dp_table::{cell_id, DpTable, TraceMarks, WeightUsedTable};
+use crate::components::drawing::Stroke;
+use crate::components::lagrange::LagrangePanel;
+use crate::components::large_input::LargeInputDialog;
+use crate::components::layout::LayoutPanel;
 use crate::components::legend::KnapsackLegend;
+use crate::components::loop_trace::LoopTraceView;
+use crate::components::metrics::use_metrics;
+use crate::components::monte_carlo::MonteCarloPanel;
+use crate::components::precedence::PrecedenceView;
+use crate::components::preprocess::PreprocessView;
+use crate::components::race::RacePanel;
+use crate::components::robust::RobustPanel;
+use crate::components::sandbox::RecurrenceSandbox;
+use crate::components::settings::SettingsPanel;
+use crate::components::stats::InstanceStatsCard;
+use crate::components::stepper::NumberStepper;
+use crate::components::summary::SolutionSummary;
+use crate::components::table_toolbar::TableToolbar;
+use crate::components::toast::{provide_toasts, ToastHost};
+use crate::components::top_down::TopDownPanel;
+use crate::components::usage::use_usage;
+use crate::components::waterfall::ContributionWaterfall;
+use crate::components::wide_table::WideTable;
+use crate::display::{root_font_px, Panel};
+use crate::files::{download_text, download_url, read_selected_file};
+use crate::flashcards::{deck, to_tsv, MAX_CARDS};
+use crate::focus::{focus_after_render, scroll_and_flash, IdScope};
+use crate::formula::KnapsackFormula;
+use crate::fullscreen;
+use crate::io::{CellNotes, KnapFile, StepPosition, KNAP_VERSION};
+use crate::metrics::RunMetrics;
+use crate::narration::{explain, narrate, narrate_hop};
+use crate::parse::{
+    convert_weight, convert_weight_list, decimal_scale, join_list, normalize_pasted, parse_cell_query, parse_dependencies,
+    parse_number, parse_problem, parse_wide_problem, quick_fixes, splice_utf16, validate_fields, CellQuery, Field,
+    FieldErrors, QuickFix,
+};
+use crate::presets::PRESETS;
+use crate::problem::{Problem, Unit, Units, WeightUnit};
+use crate::reference::cross_check;
+use crate::settings::Settings;
+use crate::solver::{
+    allocated_bytes, chosen_items, knapsack_table, solve_cardinality, solve_headless, solve_precedence, solve_wide,
+    subset_sums, CardinalitySolution, HeadlessSolution, HeadlessSolver, Pacer, PrecedenceSolution, Progress, SimEvent,
+    Simulation, SolveProgress, StepPlan, TableSolver, ValueType, WideProblem, WideSolution,
+};
+use crate::sounds::{self, Sound};
+use crate::speech;
+use crate::startup::Mark;
+use crate::steplog::{replay_jsonl, to_jsonl, walkthrough, Replay};
+use crate::streaming::{solve_streaming, STREAM_THRESHOLD_CELLS};
+use crate::theme::Theme;
+use crate::timing::{each_frame, mark_startup, now_ms};
+use crate::workspace::{LinkedStep, StepLink};
+use crate::writeup::WriteUp;
 
-// ─── Domain ──────────────────────────────────────────────────────────────────
-
-/// Solve the 0/1 knapsack problem and return the full DP table.
-/// table[i][w] = best value using items 0..i with capacity w.
-fn knapsack_table(capacity: usize, weights: &[usize], benefits: &[usize]) -> Vec<Vec<usize>> {
-    let n = weights.len();
-    // (n+1) rows × (capacity+1) cols, row 0 is the "no items" baseline
-    let mut table = vec![vec![0usize; capacity + 1]; n + 1];
-
-    for i in 1..=n {
-        let w = weights[i - 1];
-        let b = benefits[i - 1];
-        for c in 0..=capacity {
-            table[i][c] = if w > c {
-                table[i - 1][c]
-            } else {
-                table[i - 1][c].max(table[i - 1][c - w] + b)
-            };
-        }
-    }
-    table
+// ─── Host events ─────────────────────────────────────────────────────────────
+
+/// Payload of `on_solve`: a new instance was solved.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SolveInfo {
+    pub problem: Problem,
+    pub best: usize,
+    /// Whether the table opens for stepping rather than solved.
+    pub stepping: bool,
 }
 
-// ─── Parsing helpers ─────────────────────────────────────────────────────────
+/// Payload of `on_step`: the cell revealed last after a step.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StepInfo {
+    pub row: usize,
+    pub col: usize,
+    pub value: usize,
+    /// Data cells revealed so far, out of `total`.
+    pub revealed: usize,
+    pub total: usize,
+    /// False for a step back.
+    pub forward: bool,
+}
 
-fn parse_list(s: &str) -> Result<Vec<usize>, String> {
-    s.split(',')
-        .map(|t| {
-            t.trim()
-                .parse::<usize>()
-                .map_err(|_| format!("'{}' is not a valid positive integer", t.trim()))
-        })
-        .collect()
+/// Payload of `on_complete`: the answer, once the table is complete.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompleteInfo {
+    pub problem: Problem,
+    pub best: usize,
+    /// 1-based items in the optimum, ascending.
+    pub chosen: Vec<usize>,
 }
 
 // ─── Component ───────────────────────────────────────────────────────────────
 
+const TABLE_ID: &str = "dp-table";
+const CAPTURE_ID: &str = "dp-capture";
+const STEP_BTN_ID: &str = "step-btn";
+const TRACE_HOP_INTERVAL: Duration = Duration::from_millis(900);
+/// Narration sentences are written in English.
+const NARRATION_LANG: &str = "en-US";
+const VALIDATION_DEBOUNCE: Duration = Duration::from_millis(300);
+
 #[component]
-pub fn KnapsackVisualizer() -> impl IntoView {
+pub fn KnapsackVisualizer(
+    /// Distinguishes this visualizer's element ids from other instances on
+    /// the page; the first (or only) instance is 0.
+    #[prop(optional)]
+    instance: usize,
+    /// Whether this instance is the one on screen. Only the active instance
+    /// applies its font size and theme to the document.
+    #[prop(into, default = Signal::stored(true))]
+    active: Signal<bool>,
+    /// State to start from instead of the default instance.
+    #[prop(default = None)]
+    seed: Option<KnapFile>,
+    /// Opens a copy of the current instance elsewhere; the Duplicate button
+    /// only appears when this is set.
+    #[prop(optional)]
+    on_duplicate: Option<Callback<KnapFile>>,
+    /// Mirrors stepping with other visualizers while they are linked.
+    #[prop(optional)]
+    link: Option<StepLink>,
+    /// Reveal order to step through instead of the fill order chosen in the
+    /// table toolbar, which is then hidden.
+    #[prop(optional)]
+    plan: Option<StepPlan>,
+    /// Called when an instance is solved, for stepping or all at once.
+    #[prop(optional)]
+    on_solve: Option<Callback<SolveInfo>>,
+    /// Called after every step forward or back through the table.
+    #[prop(optional)]
+    on_step: Option<Callback<StepInfo>>,
+    /// Called when the answer is known: solved at once or stepped to the end.
+    #[prop(optional)]
+    on_complete: Option<Callback<CompleteInfo>>,
+    /// Instance filled into the form on mount, unsolved.
+    #[prop(optional)]
+    initial_problem: Option<Problem>,
+    /// Hides the form; only the solve and step buttons are left, so the
+    /// instance can't be edited.
+    #[prop(optional)]
+    read_only: bool,
+    /// Hides the recurrence, code and loop trace panel.
+    #[prop(optional)]
+    hide_formula: bool,
+    /// Largest table drawn; bigger instances report only their optimum and
+    /// can't be stepped through.
+    #[prop(optional)]
+    max_table_cells: Option<usize>,
+    /// Colours and fonts to use instead of the stylesheet's, on top of the
+    /// colour scheme picked in the settings.
+    #[prop(into, optional)]
+    theme: Signal<Theme>,
+) -> impl IntoView {
+    let toasts = provide_toasts();
+    let metrics = use_metrics();
+    let usage = use_usage();
+    let scope = IdScope(instance);
+    provide_context(scope);
+    let id = move |base: &str| scope.id(base);
+
     // ── form state ──────────────────────────────────────────────────────────
     let (capacity_input, set_capacity_input) = signal(String::from("6"));
     let (weights_input, set_weights_input) = signal(String::from("2, 3, 4"));
     let (benefits_input, set_benefits_input) = signal(String::from("3, 4, 5"));
+    // Units of the selected preset; plain numbers for custom input
+    let (units_input, set_units_input) = signal(Units::default());
+    // Optional "at most k items" limit; empty means no limit
+    let (max_items_input, set_max_items_input) = signal(String::new());
+    // Optional "B:A" prerequisites; empty means items are independent
+    let (deps_input, set_deps_input) = signal(String::new());
     let (error_msg, set_error_msg) = signal(Option::<String>::None);
+    let settings = RwSignal::new(Settings::default());
+    // inline hints, refreshed shortly after the user stops typing
+    let (field_errors, set_field_errors) = signal(FieldErrors::default());
 
     // ── solver state ────────────────────────────────────────────────────────
     // The full DP table (rows = items+1, cols = capacity+1)
     let (dp_table, set_dp_table) = signal(Option::<Vec<Vec<usize>>>::None);
-    // weights / benefits kept alongside the table for header rendering
-    let (item_weights, set_item_weights) = signal(Vec::<usize>::new());
-    let (item_benefits, set_item_benefits) = signal(Vec::<usize>::new());
-    let (capacity, set_capacity) = signal(0usize);
+    // the instance the table was built from, kept for header rendering
+    let (problem, set_problem) = signal(Problem::default());
 
-    // How many *data* cells have been revealed (row-major, skipping row 0
-    // which is the "0 items" baseline and is always shown).
+    // Stepping state of the table; everything below only draws it. Row 0,
+    // the "0 items" baseline, is always shown.
+    let sim = RwSignal::new(Simulation::default());
+    // How many *data* cells have been revealed in the configured order.
     // A value of None means "all revealed" (Solve was pressed).
-    let (revealed, set_revealed) = signal(Option::<usize>::Some(0));
+    let revealed = Memo::new(move |_| sim.with(Simulation::revealed));
+    // Traceback hops shown once the table is complete; None = whole path
+    let traced = Memo::new(move |_| sim.with(Simulation::traced));
+    // Whether the event changed anything
+    let sim_apply = move |event: SimEvent| sim.try_update(|s| s.apply(event)).unwrap_or(false);
+    let (trace_playing, set_trace_playing) = signal(false);
+    // Auto-play of the fill, paced per animation frame
+    let (playing, set_playing) = signal(false);
+    let trace_timer = StoredValue::new(None::<IntervalHandle>);
+    // `(row, weight)` of the item whose weight is under the pointer
+    let (hover_weight, set_hover_weight) = signal(Option::<(usize, usize)>::None);
+    // Item row singled out with the row it reads from; clicking its header toggles it
+    let (focused_row, set_focused_row) = signal(Option::<usize>::None);
+    let stop_trace = move || {
+        if let Some(handle) = trace_timer.get_value() {
+            handle.clear();
+            trace_timer.set_value(None);
+        }
+        set_trace_playing.set(false);
+    };
+
+    // Headless mode skips the table entirely and only reports the answer
+    let (headless, set_headless) = signal(false);
+    // last headless result with the instance it solved and the time taken (ms)
+    let (headless_result, set_headless_result) =
+        signal(Option::<(Problem, HeadlessSolution, f64)>::None);
+    // An over-sized instance waiting for the user's decision, and whether
+    // it was submitted from Step (true) or Solve (false)
+    let (large_input, set_large_input) = signal(Option::<(Problem, bool)>::None);
+    // Last solve on the wide (u128) backend
+    let (wide_result, set_wide_result) = signal(Option::<(WideProblem, WideSolution)>::None);
+    // Last solve with an item limit, with that limit
+    let (cardinality_result, set_cardinality_result) =
+        signal(Option::<(Problem, usize, CardinalitySolution)>::None);
+    // Last solve with dependencies: instance, prerequisites, solution and
+    // the items of the unconstrained optimum
+    let (precedence_result, set_precedence_result) =
+        signal(Option::<(Problem, Vec<Option<usize>>, PrecedenceSolution, Vec<usize>)>::None);
+    // Teacher notes attached to individual cells of the current instance
+    let notes = RwSignal::new(CellNotes::new());
+    // Freehand strokes drawn over the table, and whether the pen is active
+    let strokes = RwSignal::new(Vec::<Stroke>::new());
+    let (drawing, set_drawing) = signal(false);
+    // Capacity columns the user expanded out of collapsed groups
+    let expanded_cols = RwSignal::new(std::collections::HashSet::<usize>::new());
+    // Rows completed by a solve running in the background, if any
+    let (computing, set_computing) = signal(Option::<SolveProgress>::None);
+    // Raised by the Cancel button, polled by the running solve between rows
+    let cancel_flag = StoredValue::new(Arc::new(AtomicBool::new(false)));
 
     // ── helpers ─────────────────────────────────────────────────────────────
 
-    // Total data cells = n_items × (capacity+1)
-    let total_cells = move || {
-        dp_table
-            .get()
-            .map(|t| (t.len().saturating_sub(1)) * t[0].len())
-            .unwrap_or(0)
+    let current_plan = move || plan.unwrap_or_else(|| settings.with_untracked(|s| s.fill_order).plan());
+    // Swap in a new table (or none) with no cell revealed yet
+    let load_table = move |table: Option<Vec<Vec<usize>>>| {
+        sim.set(match &table {
+            Some(t) => problem.with_untracked(|p| Simulation::new(t, &p.weights, current_plan())),
+            None => Simulation::default(),
+        });
+        set_dp_table.set(table);
     };
 
-    // ── Solve ────────────────────────────────────────────────────────────────
-    let on_solve = move |_| {
-        set_error_msg.set(None);
+    // Weight unit of the problem, when it is one entries can be converted to
+    let weight_unit = Memo::new(move |_| units_input.with(|u| WeightUnit::from_symbol(u.weight_symbol())));
+    // Capacity and weights with entries like `500g` converted to that unit
+    let converted_capacity = Memo::new(move |_| {
+        convert_weight(&capacity_input.get(), weight_unit.get(), settings.get().parse_locale)
+    });
+    let converted_weights = Memo::new(move |_| {
+        convert_weight_list(&weights_input.get(), weight_unit.get(), settings.get().parse_locale)
+    });
+    // The converted weights, shown while any entry carried its own unit
+    let conversion_preview = Memo::new(move |_| {
+        let unit = weight_unit.get()?;
+        let capacity = converted_capacity.get().ok()?;
+        let weights = converted_weights.get().ok()?;
+        (capacity != capacity_input.get().trim() || weights != weights_input.get().trim())
+            .then(|| format!("In {}: capacity {capacity}; weights {weights}", unit.symbol()))
+    });
 
-        let cap_str = capacity_input.get();
-        let w_str = weights_input.get();
-        let b_str = benefits_input.get();
+    let read_problem = move || {
+        parse_problem(
+            &converted_capacity.get().map_err(|e| format!("Capacity: {e}"))?,
+            &converted_weights.get().map_err(|e| format!("Weights: {e}"))?,
+            &benefits_input.get(),
+            units_input.get(),
+            settings.get().parse_locale,
+        )
+    };
 
-        let cap = match cap_str.trim().parse::<usize>() {
-            Ok(v) if v > 0 => v,
-            _ => {
-                set_error_msg.set(Some("Capacity (m) must be a positive integer.".into()));
-                return;
+    let read_wide_problem = move || {
+        parse_wide_problem(
+            &converted_capacity.get().map_err(|e| format!("Capacity: {e}"))?,
+            &converted_weights.get().map_err(|e| format!("Weights: {e}"))?,
+            &benefits_input.get(),
+            units_input.get(),
+            settings.get().parse_locale,
+        )
+    };
+    // Factor that made decimal weights whole, with the capacity it gives
+    let decimal_note = Memo::new(move |_| {
+        let factor =
+            decimal_scale(&converted_capacity.get().ok()?, &converted_weights.get().ok()?, settings.get().parse_locale)?;
+        read_wide_problem().ok().map(|p| (factor, p.capacity))
+    });
+    // Benefits that only the wide backend can hold, while native is selected
+    let needs_wide = Memo::new(move |_| {
+        settings.with(|s| s.value_type == ValueType::Native) && read_problem().is_err() && read_wide_problem().is_ok()
+    });
+
+    // (n, capacity) of the form's instance, for the complexity readout
+    let draft_size = Memo::new(move |_| read_problem().ok().map(|p| (p.len(), p.capacity)));
+    let table_allocation = Memo::new(move |_| dp_table.with(|t| t.as_deref().map(allocated_bytes)));
+
+    // ── Live validation ──────────────────────────────────────────────────────
+    let pending_validation = StoredValue::new(Option::<TimeoutHandle>::None);
+    Effect::new(move |_| {
+        let cap = converted_capacity.get();
+        let ws = converted_weights.get();
+        let bs = benefits_input.get();
+        let locale = settings.with(|s| s.parse_locale);
+        // the stepper bounds the capacity as typed, not once decimals are scaled
+        let scaled =
+            decimal_note.get().and_then(|(factor, c)| settings.with(|s| s.check_capacity(c, Some(factor))).err());
+
+        if let Some(handle) = pending_validation.get_value() {
+            handle.clear();
+        }
+        let handle = set_timeout_with_handle(
+            move || {
+                let mut errors =
+                    validate_fields(cap.as_deref().unwrap_or(""), ws.as_deref().unwrap_or(""), &bs, locale);
+                if let Err(e) = cap {
+                    errors.capacity = Some(e);
+                }
+                if let Err(e) = ws {
+                    errors.weights = Some(e);
+                }
+                if errors.capacity.is_none() {
+                    errors.capacity = scaled;
+                }
+                set_field_errors.set(errors)
+            },
+            VALIDATION_DEBOUNCE,
+        );
+        pending_validation.set_value(handle.ok());
+    });
+
+    // Repairs offered once validation has flagged a field
+    let fixes = Memo::new(move |_| {
+        if field_errors.with(FieldErrors::is_empty) {
+            return Vec::new();
+        }
+        let locale = settings.with(|s| s.parse_locale);
+        quick_fixes(&capacity_input.get(), &weights_input.get(), &benefits_input.get(), locale)
+    });
+
+    // ── Paste normalization ──────────────────────────────────────────────────
+    // Field whose last paste was rewritten, until it is edited again
+    let (pasted, set_pasted) = signal(None::<Field>);
+    let on_paste = move |ev: leptos::ev::ClipboardEvent, field: Field| {
+        let Some(text) = ev.clipboard_data().and_then(|d| d.get_data("text/plain").ok()) else { return };
+        let Some(clean) = normalize_pasted(&text, settings.with_untracked(|s| s.parse_locale)) else { return };
+        let Some(input) = ev.target().and_then(|t| t.dyn_into::<web_sys::HtmlInputElement>().ok()) else { return };
+        ev.prevent_default();
+        // replace the selection, as the paste would have
+        let value = input.value();
+        let start = input.selection_start().ok().flatten().map_or(usize::MAX, |s| s as usize);
+        let end = input.selection_end().ok().flatten().map_or(start, |e| e as usize);
+        let joined = splice_utf16(&value, start, end, &clean);
+        match field {
+            Field::Weights => set_weights_input.set(joined),
+            Field::Benefits => set_benefits_input.set(joined),
+            Field::Capacity => set_capacity_input.set(joined),
+        }
+        set_pasted.set(Some(field));
+    };
+
+    // ── Presets ──────────────────────────────────────────────────────────────
+    // Instance produced by the last gcd scaling, with its divisor
+    let (scaled, set_scaled) = signal(None::<(Problem, usize)>);
+    // Explanations of the items removed by the last "Simplify"
+    let (simplify_report, set_simplify_report) = signal(None::<Vec<String>>);
+    let on_preset = move |idx: String| {
+        let units = match idx.parse::<usize>().ok().and_then(|i| PRESETS.get(i)) {
+            Some(p) => {
+                set_capacity_input.set(p.capacity.to_string());
+                set_weights_input.set(p.weights_input());
+                set_benefits_input.set(p.benefits_input());
+                p.units()
             }
+            None => Units::default(),
         };
+        set_units_input.set(units);
+        set_error_msg.set(None);
+        set_simplify_report.set(None);
+        load_table(None);
+    };
+
+    // Common divisor of the form's capacity and weights, when scaling helps
+    let draft_gcd = Memo::new(move |_| read_problem().ok().map(|p| weight_gcd(&p)).filter(|&g| g > 1));
+    let on_scale = move |_| {
+        let (Ok(p), Some(g)) = (read_problem(), draft_gcd.get_untracked()) else { return };
+        let weights: Vec<usize> = p.weights.iter().map(|w| w / g).collect();
+        set_capacity_input.set((p.capacity / g).to_string());
+        set_weights_input.set(join_list(&weights));
+        set_scaled.set(Some((Problem { capacity: p.capacity / g, weights, ..p }, g)));
+        load_table(None);
+        toasts.info(format!("Weights and capacity divided by {g}; the optimum is unchanged."));
+    };
+    // Capacities reachable as a sum of item weights; row values can only
+    // change there, so the header marks them. Recomputed once per problem.
+    let breakpoints = Memo::new(move |_| problem.with(|p| subset_sums(&p.weights, p.capacity)));
 
-        let ws = match parse_list(&w_str) {
-            Ok(v) if !v.is_empty() => v,
+    // Divisor for the header mapping, while the scaled instance is on screen
+    let column_scale = Memo::new(move |_| {
+        scaled.with(|s| match s {
+            Some((p, g)) if problem.with(|cur| cur.weights == p.weights && cur.capacity == p.capacity) => *g,
+            _ => 1,
+        })
+    });
+
+    // Remove items that cannot change the optimum and explain each removal
+    let on_simplify = move |_| {
+        let p = match read_problem() {
+            Ok(p) => p,
             Err(e) => {
-                set_error_msg.set(Some(format!("Weights: {e}")));
+                set_error_msg.set(Some(e));
                 return;
             }
-            _ => {
-                set_error_msg.set(Some("Enter at least one weight.".into()));
-                return;
+        };
+        let (reduced, removals) = simplify(&p);
+        if removals.is_empty() {
+            toasts.info("Nothing to simplify: every item can matter.");
+            return;
+        }
+        // the weights come back scaled to whole numbers, so the capacity must too
+        set_capacity_input.set(reduced.capacity.to_string());
+        set_weights_input.set(join_list(&reduced.weights));
+        set_benefits_input.set(join_list(&reduced.benefits));
+        set_simplify_report.set(Some(removals.iter().map(|r| r.describe(&p)).collect()));
+        load_table(None);
+    };
+
+    // ── Solve ────────────────────────────────────────────────────────────────
+
+    // Forward progress reports of a streaming solve into `computing` and
+    // return the sender plus a fresh cancellation flag for the new solve
+    let watch_progress = move || {
+        let cancel = Arc::new(AtomicBool::new(false));
+        cancel_flag.set_value(cancel.clone());
+
+        let (tx, mut rx) = mpsc::unbounded::<SolveProgress>();
+        spawn_local(async move {
+            while let Some(p) = rx.next().await {
+                // the final report can arrive after the result is shown
+                if !p.is_done() {
+                    set_computing.set(Some(p));
+                }
             }
+        });
+        set_computing.set(Some(SolveProgress::default()));
+        (tx, cancel)
+    };
+
+    // Abandon the background solve; whatever was shown before stays put
+    let on_cancel = move |_| {
+        cancel_flag.get_value().store(true, Ordering::Relaxed);
+    };
+    let cancelled = move || {
+        set_computing.set(None);
+        toasts.info("Solve cancelled; the previous result is unchanged.");
+    };
+
+    // ── Host events ──────────────────────────────────────────────────────────
+    let emit_complete = move || {
+        let Some(callback) = on_complete else { return };
+        let info = dp_table.with_untracked(|t| {
+            let t = t.as_ref()?;
+            problem.with_untracked(|p| Some(CompleteInfo {
+                problem: p.clone(),
+                best: t[p.len()][p.capacity],
+                chosen: chosen_items(t, &p.weights, p.capacity),
+            }))
+        });
+        if let Some(info) = info {
+            callback.run(info);
+        }
+    };
+    let emit_step = move |forward: bool| {
+        let Some(callback) = on_step else { return };
+        let info = sim.with_untracked(|s| {
+            let (row, col) = s.active_cell()?;
+            let Progress { filled, cells, .. } = s.progress();
+            let value = dp_table.with_untracked(|t| t.as_ref().map(|t| t[row][col]))?;
+            Some(StepInfo { row, col, value, revealed: filled, total: cells, forward })
+        });
+        if let Some(info) = info {
+            callback.run(info);
+        }
+    };
+
+    // Where an opened file leaves the stepper once its table is shown
+    let resume = StoredValue::new(None::<Resume>);
+
+    // Show a finished table: fully revealed, or ready to step from the first cell
+    let show_table = move |p: Problem, table: Vec<Vec<usize>>, stepping: bool| {
+        expanded_cols.set(Default::default());
+        if problem.with_untracked(|old| *old != p) {
+            notes.set(CellNotes::new());
+            strokes.set(Vec::new());
+        }
+        stop_trace();
+        set_problem.set(p);
+        load_table(Some(table));
+        mark_startup(Mark::FirstSolve);
+        request_animation_frame(|| mark_startup(Mark::FirstTable));
+        set_headless_result.set(None);
+        set_wide_result.set(None);
+        set_cardinality_result.set(None);
+        set_precedence_result.set(None);
+        if stepping {
+            sim_apply(SimEvent::Forward); // reveal first cell
+            focus_after_render(id(STEP_BTN_ID));
+        } else {
+            sim_apply(SimEvent::Finish); // reveal everything immediately
+            focus_after_render(id(TABLE_ID));
+        }
+        if let Some(callback) = on_solve {
+            let best = dp_table.with_untracked(|t| t.as_ref().and_then(|t| t.last()?.last().copied()));
+            callback.run(SolveInfo { problem: problem.get_untracked(), best: best.unwrap_or(0), stepping });
+        }
+        if !stepping {
+            emit_complete();
+        }
+        // a file opened for a truncated or different instance does not resume
+        if let Some(r) = resume.try_update_value(Option::take).flatten()
+            && problem.with_untracked(|p| *p == r.problem)
+        {
+            for event in r.events {
+                sim_apply(event);
+            }
+            if let Some(saved) = r.notes {
+                notes.set(saved);
+            }
+        }
+    };
+
+    // Build the table for `p`, in the background when it is large
+    let load = move |p: Problem, stepping: bool| {
+        let Some((cols, cells)) = table_size(&p) else {
+            set_error_msg.set(Some("This instance is too large to tabulate.".into()));
+            return;
+        };
+        let t0 = now_ms();
+        let record = move |p: &Problem, table: &[Vec<usize>]| {
+            metrics.record(RunMetrics {
+                solver: "table DP",
+                items: p.len(),
+                capacity: p.capacity,
+                best: table[p.len()][p.capacity] as u128,
+                cells: Some(cells),
+                elapsed_ms: Some(now_ms() - t0),
+                ..Default::default()
+            });
         };
+        if cells <= STREAM_THRESHOLD_CELLS {
+            let table = knapsack_table(p.capacity, &p.weights, &p.benefits);
+            record(&p, &table);
+            show_table(p, table, stepping);
+            return;
+        }
 
-        let bs = match parse_list(&b_str) {
-            Ok(v) => v,
-            Err(e) => {
-                set_error_msg.set(Some(format!("Benefits: {e}")));
+        let (progress, cancel) = watch_progress();
+        spawn_local(async move {
+            let solver = TableSolver::new(p.capacity, &p.weights, &p.benefits);
+            let Some(table) = solve_streaming(solver, cols, progress, &cancel).await else {
+                cancelled();
                 return;
+            };
+            set_computing.set(None);
+            record(&p, &table);
+            show_table(p, table, stepping);
+        });
+    };
+
+    // Report only the optimum, never allocating or drawing the table
+    let solve_without_table = move |p: Problem| {
+        let show = move |p: Problem, solution: HeadlessSolution, elapsed: f64| {
+            metrics.record(RunMetrics {
+                solver: "headless DP",
+                items: p.len(),
+                capacity: p.capacity,
+                best: solution.best as u128,
+                cells: Some(solution.cells),
+                elapsed_ms: Some(elapsed),
+                ..Default::default()
+            });
+            load_table(None);
+            mark_startup(Mark::FirstSolve);
+            if let Some(callback) = on_solve {
+                callback.run(SolveInfo { problem: p.clone(), best: solution.best, stepping: false });
             }
+            if let Some(callback) = on_complete {
+                callback.run(CompleteInfo { problem: p.clone(), best: solution.best, chosen: solution.chosen.clone() });
+            }
+            set_headless_result.set(Some((p, solution, elapsed)));
+            set_wide_result.set(None);
+            set_cardinality_result.set(None);
+            set_precedence_result.set(None);
         };
 
-        if ws.len() != bs.len() {
-            set_error_msg.set(Some(format!(
-                "Number of weights ({}) must equal number of benefits ({}).",
-                ws.len(),
-                bs.len()
-            )));
+        let t0 = now_ms();
+        let Some((cols, cells)) = table_size(&p) else {
+            set_error_msg.set(Some("This instance is too large to tabulate.".into()));
+            return;
+        };
+        if cells <= STREAM_THRESHOLD_CELLS {
+            let solution = solve_headless(&p);
+            show(p, solution, now_ms() - t0);
             return;
         }
 
-        let table = knapsack_table(cap, &ws, &bs);
-        set_capacity.set(cap);
-        set_item_weights.set(ws);
-        set_item_benefits.set(bs);
-        set_dp_table.set(Some(table));
-        set_revealed.set(None); // reveal everything immediately
+        let (progress, cancel) = watch_progress();
+        spawn_local(async move {
+            let solver = HeadlessSolver::new(&p);
+            let Some(solution) = solve_streaming(solver, cols, progress, &cancel).await else {
+                cancelled();
+                return;
+            };
+            set_computing.set(None);
+            show(p, solution, now_ms() - t0);
+        });
     };
 
-    // ── Step-by-step ─────────────────────────────────────────────────────────
-    let on_step = move |_| {
+    // Load `p` within the table limits, asking first when it has too many items
+    let admit = move |p: Problem, stepping: bool| {
+        // an overflowing count is over any budget
+        let cells = table_size(&p).map_or(usize::MAX, |(_, cells)| cells);
+        if let Some(max) = max_table_cells
+            && cells > max
+        {
+            if stepping {
+                set_error_msg.set(Some(format!("This table has {cells} cells; stepping is limited to {max}.")));
+            } else {
+                solve_without_table(p);
+            }
+            return;
+        }
+        if p.len() > settings.with(|s| s.max_visual_items) {
+            set_large_input.set(Some((p, stepping)));
+            return;
+        }
+        load(p, stepping);
+    };
+
+    // Parse the form and load it, asking first when it has too many items
+    let start = move |stepping: bool| {
+        set_playing.set(false);
         set_error_msg.set(None);
+        resume.set_value(None);
+        if computing.get().is_some() {
+            return;
+        }
+        if let Some((factor, capacity)) = decimal_note.get_untracked()
+            && let Err(e) = settings.with_untracked(|s| s.check_capacity(capacity, Some(factor)))
+        {
+            set_error_msg.set(Some(format!("Capacity: {e}.")));
+            return;
+        }
 
-        // If no table yet, parse inputs and initialise (reveal = 0)
-        if dp_table.get().is_none() {
-            let cap_str = capacity_input.get();
-            let w_str = weights_input.get();
-            let b_str = benefits_input.get();
-
-            let cap = match cap_str.trim().parse::<usize>() {
-                Ok(v) if v > 0 => v,
-                _ => {
-                    set_error_msg.set(Some("Capacity (m) must be a positive integer.".into()));
-                    return;
-                }
-            };
-            let ws = match parse_list(&w_str) {
-                Ok(v) if !v.is_empty() => v,
+        let max_items = match max_items_input.with(|s| s.trim().to_string()) {
+            s if s.is_empty() => None,
+            s => match parse_number(&s, settings.with(|s| s.parse_locale)) {
+                Ok(k) => Some(k),
                 Err(e) => {
-                    set_error_msg.set(Some(format!("Weights: {e}")));
+                    set_error_msg.set(Some(format!("Item limit: {e}")));
                     return;
                 }
-                _ => {
-                    set_error_msg.set(Some("Enter at least one weight.".into()));
-                    return;
+            },
+        };
+        let deps = deps_input.with(|s| s.trim().to_string());
+        if !deps.is_empty() {
+            if max_items.is_some() {
+                set_error_msg.set(Some("An item limit and dependencies cannot be combined.".into()));
+                return;
+            }
+            if stepping || settings.with(|s| s.value_type == ValueType::Wide) {
+                set_error_msg.set(Some(
+                    "Dependencies work on native values and show the finished table only.".into(),
+                ));
+                return;
+            }
+            let parsed = read_problem().and_then(|p| {
+                let parents = parse_dependencies(&deps, p.len()).map_err(|e| format!("Dependencies: {e}"))?;
+                Ok((p, parents))
+            });
+            match parsed {
+                Ok((p, _)) if p.len() > settings.with(|s| s.max_visual_items) => set_error_msg.set(Some(format!(
+                    "Dependency tables are drawn for at most {} items.",
+                    settings.with(|s| s.max_visual_items)
+                ))),
+                Ok((p, parents)) => {
+                    let t0 = now_ms();
+                    let solution = solve_precedence(&p, &parents);
+                    metrics.record(RunMetrics {
+                        solver: "tree DP (dependencies)",
+                        items: p.len(),
+                        capacity: p.capacity,
+                        best: solution.best as u128,
+                        cells: Some(p.len() * (p.capacity + 1)),
+                        elapsed_ms: Some(now_ms() - t0),
+                        ..Default::default()
+                    });
+                    let unconstrained = solve_headless(&p).chosen;
+                    load_table(None);
+                    set_headless_result.set(None);
+                    set_wide_result.set(None);
+                    set_cardinality_result.set(None);
+                    set_precedence_result.set(Some((p, parents, solution, unconstrained)));
                 }
-            };
-            let bs = match parse_list(&b_str) {
-                Ok(v) => v,
-                Err(e) => {
-                    set_error_msg.set(Some(format!("Benefits: {e}")));
-                    return;
+                Err(e) => set_error_msg.set(Some(e)),
+            }
+            return;
+        }
+
+        if let Some(k) = max_items {
+            if stepping || settings.with(|s| s.value_type == ValueType::Wide) {
+                set_error_msg.set(Some(
+                    "The item limit works on native values and shows the finished layers only.".into(),
+                ));
+                return;
+            }
+            match read_problem() {
+                Ok(p) if p.len() > settings.with(|s| s.max_visual_items) => set_error_msg.set(Some(format!(
+                    "Layered tables are drawn for at most {} items.",
+                    settings.with(|s| s.max_visual_items)
+                ))),
+                Ok(p) => {
+                    // more than n items is no limit, and each allowed item costs a layer
+                    let k = k.min(p.len());
+                    let t0 = now_ms();
+                    let solution = solve_cardinality(&p, k);
+                    metrics.record(RunMetrics {
+                        solver: "layered DP (item limit)",
+                        items: p.len(),
+                        capacity: p.capacity,
+                        best: solution.best as u128,
+                        cells: Some(k * p.len() * (p.capacity + 1)),
+                        elapsed_ms: Some(now_ms() - t0),
+                        ..Default::default()
+                    });
+                    load_table(None);
+                    set_headless_result.set(None);
+                    set_wide_result.set(None);
+                    set_precedence_result.set(None);
+                    set_cardinality_result.set(Some((p, k, solution)));
                 }
-            };
-            if ws.len() != bs.len() {
-                set_error_msg.set(Some(format!(
-                    "Number of weights ({}) must equal number of benefits ({}).",
-                    ws.len(),
-                    bs.len()
-                )));
+                Err(e) => set_error_msg.set(Some(e)),
+            }
+            return;
+        }
+
+        if settings.with(|s| s.value_type == ValueType::Wide) {
+            if stepping {
+                set_error_msg.set(Some("Stepping needs native values; wide values only show the finished table.".into()));
                 return;
             }
+            match read_wide_problem() {
+                Ok(p) if p.weights.len() > settings.with(|s| s.max_visual_items) => set_error_msg.set(Some(format!(
+                    "Wide tables are drawn for at most {} items.",
+                    settings.with(|s| s.max_visual_items)
+                ))),
+                Ok(p) => {
+                    let t0 = now_ms();
+                    let solution = solve_wide(&p);
+                    metrics.record(RunMetrics {
+                        solver: "wide DP",
+                        items: p.weights.len(),
+                        capacity: p.capacity,
+                        best: solution.best,
+                        cells: Some(p.weights.len() * (p.capacity + 1)),
+                        elapsed_ms: Some(now_ms() - t0),
+                        ..Default::default()
+                    });
+                    load_table(None);
+                    set_headless_result.set(None);
+                    set_cardinality_result.set(None);
+                    set_precedence_result.set(None);
+                    set_wide_result.set(Some((p, solution)));
+                }
+                Err(e) => set_error_msg.set(Some(e)),
+            }
+            return;
+        }
+
+        let p = match read_problem() {
+            Ok(p) => p,
+            Err(e) => {
+                set_error_msg.set(Some(e));
+                return;
+            }
+        };
+
+        if headless.get() && !stepping {
+            solve_without_table(p);
+            return;
+        }
+        admit(p, stepping);
+    };
+
+    let solve = move || start(false);
+    let on_solve_click = move |_| solve();
+
+    // Enter in any form field behaves like the Solve button
+    let on_enter = move |ev: leptos::ev::KeyboardEvent| {
+        if ev.key() == "Enter" {
+            ev.prevent_default();
+            solve();
+        }
+    };
+
+    // ── Step-by-step ─────────────────────────────────────────────────────────
+    let step_forward = move || {
+        set_error_msg.set(None);
+
+        // If no (visible) table yet, parse inputs and start stepping
+        if dp_table.get().is_none() {
+            start(true);
+            return;
+        }
 
-            let table = knapsack_table(cap, &ws, &bs);
-            set_capacity.set(cap);
-            set_item_weights.set(ws);
-            set_item_benefits.set(bs);
-            set_dp_table.set(Some(table));
-            set_revealed.set(Some(1)); // reveal first cell
+        // Table exists – advance one cell; past the last the traceback starts,
+        // and a fully revealed table resets to step-by-step from scratch
+        if revealed.get().is_none() {
+            stop_trace();
+        }
+        sim_apply(SimEvent::Forward);
+        if revealed.get_untracked().is_none() {
+            emit_complete();
+        } else {
+            usage.record(UsageEvent::Steps(1));
+            emit_step(true);
+        }
+    };
+
+    // Undo one reveal; from a fully revealed table, go back to the last cell
+    let step_back = move || {
+        if dp_table.with(Option::is_none) {
             return;
         }
+        set_playing.set(false);
+        sim_apply(SimEvent::Back);
+        emit_step(false);
+    };
 
-        // Table exists – advance one cell, or wrap around to reset
-        match revealed.get() {
-            None => {
-                // Already fully revealed – reset to step-by-step from scratch
-                set_revealed.set(Some(1));
+    // ── Auto-play ────────────────────────────────────────────────────────────
+    // Each frame reveals every cell the speed has made due as one Advance,
+    // so fast playback costs one update per frame, not one per cell
+    let toggle_play = move || {
+        if playing.get_untracked() {
+            set_playing.set(false);
+            return;
+        }
+        if dp_table.with_untracked(Option::is_none) {
+            start(true);
+        } else if revealed.get_untracked().is_none() {
+            sim_apply(SimEvent::Seek(1));
+        }
+        stop_trace();
+        set_playing.set(true);
+        let mut speed = 0;
+        let mut pacer = Pacer::new(0.0);
+        let mut last = now_ms();
+        each_frame(move |now| {
+            // a closed tab disposes the signal and ends the loop
+            if !playing.try_get_untracked().unwrap_or(false) {
+                return false;
+            }
+            let elapsed = now - last;
+            last = now;
+            if dp_table.with_untracked(Option::is_none) {
+                // a large table may still be computing
+                let waiting = computing.with_untracked(Option::is_some);
+                if !waiting {
+                    set_playing.set(false);
+                }
+                return waiting;
+            }
+            let wanted = settings.with_untracked(|s| s.play_speed);
+            if wanted != speed {
+                speed = wanted;
+                pacer = Pacer::new(speed as f64);
+            }
+            let due = pacer.advance(elapsed);
+            if due == 0 {
+                return true;
             }
-            Some(r) => {
-                let next = r + 1;
-                if next > total_cells() {
-                    set_revealed.set(None); // done – mark all revealed
-                } else {
-                    set_revealed.set(Some(next));
+            let before = revealed.get_untracked().unwrap_or(0);
+            sim_apply(SimEvent::Advance(due));
+            match revealed.get_untracked() {
+                Some(r) => {
+                    usage.record(UsageEvent::Steps((r - before) as u32));
+                    emit_step(true);
+                    true
                 }
+                None => {
+                    set_playing.set(false);
+                    emit_complete();
+                    false
+                }
+            }
+        });
+    };
+
+    // Font scale and theme apply to the whole document, not just this view
+    Effect::new(move |_| {
+        if !active.get() {
+            return;
+        }
+        let (scale, theme) = settings.with(|s| (s.font_scale, s.theme));
+        let Some(root) = document().document_element() else { return };
+        _ = root.set_attribute("data-theme", theme.code());
+        if let Some(root) = root.dyn_ref::<web_sys::HtmlElement>() {
+            _ = root.style().set_property("font-size", &format!("{}px", root_font_px(scale)));
+        }
+    });
+
+    // ── Traceback animation ──────────────────────────────────────────────────
+    let trace = Memo::new(move |_| sim.with(|s| s.path().to_vec()));
+    let trace_next = move || {
+        if !sim_apply(SimEvent::TraceForward) {
+            stop_trace();
+        }
+    };
+    let toggle_trace_play = move || {
+        if trace_playing.get_untracked() {
+            stop_trace();
+            return;
+        }
+        // playing from the full path or the end starts over
+        if sim.with_untracked(|s| s.walked().len() >= s.path().len()) {
+            sim_apply(SimEvent::TraceSeek(0));
+        }
+        if let Ok(handle) = set_interval_with_handle(trace_next, TRACE_HOP_INTERVAL) {
+            trace_timer.set_value(Some(handle));
+            set_trace_playing.set(true);
+        }
+    };
+    // Items packed so far by the traceback, in the order they were found
+    let packed = Signal::derive(move || {
+        sim.with(|s| s.walked().iter().filter(|hop| hop.taken).map(|hop| hop.row).collect::<Vec<_>>())
+    });
+    on_cleanup(stop_trace);
+
+    // ── Reveal order ─────────────────────────────────────────────────────────
+    if plan.is_some() {
+        let violation = Memo::new(move |_| sim.with(|s| s.order().first_violation()));
+        Effect::new(move |_| {
+            if let Some((row, col)) = violation.get() {
+                toasts.warn(format!("The reveal order shows dp[{row}][{col}] before a cell of the row above it reads."));
             }
+        });
+    } else {
+        // a new fill order relays the cells out, keeping the position
+        let fill_order = Memo::new(move |_| settings.with(|s| s.fill_order));
+        Effect::new(move |_| {
+            let order = fill_order.get();
+            sim.update(|s| s.set_plan(order.plan()));
+        });
+    }
+
+    // ── Narration ────────────────────────────────────────────────────────────
+    // Sentence explaining the cell just revealed in step mode
+    // `(row, col)` of the cell revealed last in step mode
+    let active_cell = Memo::new(move |_| sim.with(Simulation::active_cell));
+    let narration = Memo::new(move |_| {
+        let (row, col) = active_cell.get()?;
+        let fmt = settings.with(|s| s.number_format);
+        dp_table.with(|t| t.as_ref().map(|t| problem.with(|p| narrate(t, p, row, col, &fmt))))
+    });
+    let loop_step = Memo::new(move |_| {
+        let (row, col) = active_cell.get()?;
+        dp_table.with(|t| {
+            let branch = Branch::of(t.as_ref()?, row, col, problem.with(|p| p.weights[row - 1]));
+            Some(LoopStep { row, col, branch })
+        })
+    });
+    // Formula, code, loop trace and table all mark this step
+    let active = provide_active_cell(loop_step.into(), dp_table.into(), problem.into());
+    let (read_aloud, set_read_aloud) = signal(false);
+
+    // Sound cues follow step transitions only, not solving straight through
+    Effect::new(move |prev: Option<Option<usize>>| {
+        let now = revealed.get();
+        let Some(prev) = prev else { return now };
+        if now == prev || !settings.with_untracked(|s| s.sound_effects) {
+            return now;
+        }
+        let sound = match (prev, now) {
+            (Some(_), None) => Some(Sound::Complete),
+            (_, Some(r)) => r.checked_sub(1).and_then(|ordinal| {
+                dp_table.with_untracked(|t| {
+                    let t = t.as_ref()?;
+                    let (row, col) = sim.with_untracked(|s| s.order().cell(ordinal));
+                    Some(if t[row][col] != t[row - 1][col] { Sound::Take } else { Sound::Step })
+                })
+            }),
+            (None, None) => None,
+        };
+        if let Some(sound) = sound {
+            sounds::play(sound);
+        }
+        now
+    });
+    Effect::new(move |_| {
+        if let Some(text) = narration.get()
+            && read_aloud.get_untracked()
+        {
+            speech::speak(&text, NARRATION_LANG);
         }
+    });
+    let toggle_read_aloud = move |_| {
+        let on = !read_aloud.get_untracked();
+        set_read_aloud.set(on);
+        match narration.get_untracked() {
+            Some(text) if on => speech::speak(&text, NARRATION_LANG),
+            _ => speech::stop(),
+        }
+    };
+
+    // ── Linked stepping ──────────────────────────────────────────────────────
+    // Steps the user takes here are repeated by linked visualizers, and
+    // theirs here. Only the user's own steps move focus.
+    let send_link = move |step: LinkedStep| {
+        if let Some(link) = link {
+            link.send(instance, step);
+        }
+    };
+    let next_step = move || {
+        step_forward();
+        send_link(LinkedStep::Forward);
+        // keep focus on the step button so Space/Enter keeps stepping
+        focus_after_render(id(STEP_BTN_ID));
     };
+    let prev_step = move || {
+        step_back();
+        send_link(LinkedStep::Back);
+    };
+    let play_trace = move || {
+        toggle_trace_play();
+        send_link(LinkedStep::TracePlay);
+    };
+    let on_step = move |_| next_step();
+    if let Some(link) = link {
+        link.on_step(instance, move |step| match step {
+            LinkedStep::Forward => step_forward(),
+            LinkedStep::Back => step_back(),
+            LinkedStep::TracePlay => toggle_trace_play(),
+        });
+    }
+
+    // ── Presentation mode ────────────────────────────────────────────────────
+    // Hides the form and enlarges the table; arrow keys and clicker
+    // PageUp/PageDown step through the fill.
+    let (presenting, set_presenting) = signal(false);
+    let start_presenting = move |_| {
+        set_presenting.set(true);
+        fullscreen::enter();
+    };
+    let stop_presenting = move || {
+        set_presenting.set(false);
+        fullscreen::exit();
+    };
+    fullscreen::on_change(move |active| {
+        if !active {
+            set_presenting.set(false);
+        }
+    });
+    let keys = window_event_listener(leptos::ev::keydown, move |ev| {
+        if !presenting.get_untracked() {
+            return;
+        }
+        match ev.key().as_str() {
+            "ArrowRight" | "ArrowDown" | "PageDown" | " " => next_step(),
+            "ArrowLeft" | "ArrowUp" | "PageUp" => prev_step(),
+            "Escape" => stop_presenting(),
+            _ => return,
+        }
+        ev.prevent_default();
+    });
+    on_cleanup(move || keys.remove());
 
     // ── Cell visibility predicate ─────────────────────────────────────────────
     // row here is 1-based item row (row 0 is always shown)
-    let is_visible = move |row: usize, col: usize, n_cols: usize| -> bool {
-        match revealed.get() {
-            None => true,
-            Some(r) => {
-                // linear index in row-major order starting from (row=1, col=0)
-                let linear = (row - 1) * n_cols + col;
-                linear < r
+    let is_visible = move |row: usize, col: usize| -> bool { sim.with(|s| s.is_revealed(row, col)) };
+
+    // ── Per-step table state ──────────────────────────────────────────────────
+    // Read by each cell rather than by the table, which is only rebuilt when
+    // the instance or the display settings change
+    let trace_marks = Memo::new(move |_| trace.with(|hops| TraceMarks::of(hops, traced.get())));
+    let active_pos = Memo::new(move |_| active.cell());
+    let groups = Memo::new(move |_| {
+        let filter = settings.with(|s| s.column_filter);
+        problem.with(|p| {
+            let kept = filter.kept_columns(p.capacity, &p.weights);
+            column_groups(p.capacity, |c| kept[c], &expanded_cols.get())
+        })
+    });
+
+    // ── Find cell ────────────────────────────────────────────────────────────
+    let find_query = RwSignal::new(String::new());
+    let (find_msg, set_find_msg) = signal(Option::<String>::None);
+
+    let find_cell = move || {
+        let Some(table) = dp_table.get() else { return };
+        let n = table.len() - 1;
+        let n_cols = table[0].len();
+
+        let target = match parse_cell_query(&find_query.get()) {
+            Ok(CellQuery::Cell(i, w)) if i <= n && w < n_cols => Ok((i, w)),
+            Ok(CellQuery::Cell(i, w)) => Err(format!(
+                "dp[{i}][{w}] is outside the table (items 0..={n}, capacity 0..={}).",
+                n_cols - 1
+            )),
+            Ok(CellQuery::Value(v)) => (0..=n)
+                .flat_map(|i| (0..n_cols).map(move |c| (i, c)))
+                .find(|&(i, c)| (i == 0 || is_visible(i, c)) && table[i][c] == v)
+                .ok_or_else(|| format!("No revealed cell holds {v}.")),
+            Err(e) => Err(e),
+        };
+
+        match target {
+            Ok((i, c)) => {
+                // un-collapse the column first if it is folded away
+                expanded_cols.update(|cols| _ = cols.insert(c));
+                request_animation_frame(move || scroll_and_flash(&cell_id(scope, i, c)));
+                set_find_msg.set(Some(format!("→ dp[{i}][{c}] = {}", table[i][c])));
+            }
+            Err(e) => set_find_msg.set(Some(e)),
+        }
+    };
+
+    // ── Save / open ──────────────────────────────────────────────────────────
+    let on_save = move |_| {
+        let (p, step) = match (dp_table.with(Option::is_some), revealed.get()) {
+            (true, None) => (problem.get(), StepPosition::Solved),
+            (true, Some(r)) => (problem.get(), StepPosition::Stepping(r)),
+            (false, _) => match read_problem() {
+                Ok(p) => (p, StepPosition::Unsolved),
+                Err(e) => {
+                    set_error_msg.set(Some(format!("Cannot save: {e}")));
+                    return;
+                }
+            },
+        };
+        let file = notes.with(|n| KnapFile::new(p, settings.get(), step, n));
+        if download_text("knapsack.knap", "application/json", &file.to_json()).is_err() {
+            toasts.warn("Could not start the download.");
+        }
+    };
+
+    // Render the table (with any drawings) to a PNG and download it
+    let on_capture = move || {
+        let Some(host) = document().get_element_by_id(&id(CAPTURE_ID)) else { return };
+        let png = strokes.with_untracked(|s| capture_png(&host, s));
+        if png.and_then(|url| download_url("knapsack-table.png", &url)).is_err() {
+            toasts.warn("Could not capture the table.");
+        }
+    };
+
+    let restore = move |file: KnapFile| {
+        // the size limits stay this browser's, not the file's
+        let limits = settings.get_untracked();
+        let saved_notes = file.cell_notes();
+        let p = file.problem;
+        if let Err(e) = limits.check_size(&p) {
+            set_error_msg.set(Some(format!("Cannot open this file: {e}")));
+            return;
+        }
+        set_capacity_input.set(p.capacity.to_string());
+        set_weights_input.set(join_list(&p.weights));
+        set_benefits_input.set(join_list(&p.benefits));
+        set_units_input.set(p.units.clone());
+        settings.set(Settings {
+            capacity_min: limits.capacity_min,
+            capacity_max: limits.capacity_max,
+            max_visual_items: limits.max_visual_items,
+            ..file.settings
+        });
+        set_error_msg.set(None);
+        set_headless_result.set(None);
+
+        let (stepping, events) = match file.step {
+            StepPosition::Unsolved => {
+                load_table(None);
+                notes.set(saved_notes);
+                return;
             }
+            StepPosition::Solved => (false, Vec::new()),
+            StepPosition::Stepping(r) => (true, vec![SimEvent::Seek(r)]),
+        };
+        resume.set_value(Some(Resume { problem: p.clone(), events, notes: Some(saved_notes) }));
+        admit(p, stepping);
+    };
+
+    // Double-clicking a cell edits its note; an empty note removes it
+    let edit_note = move |i: usize, c: usize| {
+        let current = notes.with(|n| n.get(&(i, c)).cloned()).unwrap_or_default();
+        let Ok(Some(text)) = window()
+            .prompt_with_message_and_default(&format!("Note for dp[{i}][{c}]"), &current)
+        else {
+            return;
+        };
+        let text = text.trim().to_string();
+        notes.update(|n| {
+            if text.is_empty() {
+                n.remove(&(i, c));
+            } else {
+                n.insert((i, c), text);
+            }
+        });
+    };
+
+    // ── Step log ─────────────────────────────────────────────────────────────
+    let on_export_steps = move |_| {
+        if plan.is_some() {
+            toasts.warn("Step logs record one of the built-in fill orders, not a custom reveal order.");
+            return;
         }
+        let Some(events) = dp_table.with(|t| t.as_ref().map(|t| {
+            let (order, fmt) = settings.with(|s| (s.fill_order, s.number_format));
+            let traced = traced.get().unwrap_or_else(|| trace.with(Vec::len));
+            problem.with(|p| walkthrough(t, p, order, &fmt, revealed.get(), traced))
+        })) else {
+            toasts.warn("Solve or step through a problem before exporting its steps.");
+            return;
+        };
+        if download_text("knapsack-steps.jsonl", "application/jsonl", &to_jsonl(&events)).is_err() {
+            toasts.warn("Could not start the download.");
+        }
+    };
+
+    // ── Write-up ─────────────────────────────────────────────────────────────
+    let export_writeup = move |name: &str, mime: &str, render: fn(&WriteUp) -> String| {
+        let Some(writeup) = dp_table.with(|t| t.as_ref().filter(|_| revealed.get().is_none()).map(|t| {
+            let fmt = settings.with(|s| s.number_format);
+            problem.with(|p| WriteUp::new(t, p, &fmt))
+        })) else {
+            toasts.warn("Solve the problem before exporting a write-up.");
+            return;
+        };
+        if download_text(name, mime, &render(&writeup)).is_err() {
+            toasts.warn("Could not start the download.");
+        }
+    };
+
+    let on_export_cards = move |_| {
+        let Some(cards) = dp_table.with(|t| t.as_ref().filter(|_| revealed.get().is_none()).map(|t| {
+            let fmt = settings.with(|s| s.number_format);
+            problem.with(|p| deck(t, p, &fmt))
+        })) else {
+            toasts.warn("Solve the problem before exporting flashcards.");
+            return;
+        };
+        if cards.len() > MAX_CARDS {
+            toasts.warn(format!("The deck is limited to {MAX_CARDS} cards; this table fills {}.", cards.len()));
+            return;
+        }
+        if download_text("knapsack-cards.tsv", "text/tab-separated-values", &to_tsv(&cards)).is_err() {
+            toasts.warn("Could not start the download.");
+        }
+    };
+
+    let replay = move |r: Replay| {
+        let p = r.problem;
+        set_capacity_input.set(p.capacity.to_string());
+        set_weights_input.set(join_list(&p.weights));
+        set_benefits_input.set(join_list(&p.benefits));
+        set_units_input.set(p.units.clone());
+        settings.update(|s| s.fill_order = r.fill_order);
+        set_error_msg.set(None);
+
+        let events = match r.revealed {
+            Some(n) => vec![SimEvent::Seek(n)],
+            None => vec![SimEvent::Finish, SimEvent::TraceSeek(r.traced)],
+        };
+        resume.set_value(Some(Resume { problem: p.clone(), events, notes: None }));
+        admit(p, true);
+    };
+
+    // Which file picker is still reading, shown on its button meanwhile
+    let (reading, set_reading) = signal(None::<&'static str>);
+
+    let on_import_steps = move |ev: leptos::ev::Event| {
+        let input: web_sys::HtmlInputElement = event_target(&ev);
+        set_reading.set(Some("steps"));
+        spawn_local(async move {
+            let file = read_selected_file(&input).await;
+            set_reading.set(None);
+            match file {
+                Some(Ok(text)) => match settings.with_untracked(|s| replay_jsonl(&text, s)) {
+                    Ok(r) => replay(r),
+                    Err(e) => set_error_msg.set(Some(e)),
+                },
+                Some(Err(e)) => set_error_msg.set(Some(e)),
+                None => {}
+            }
+            input.set_value("");
+        });
+    };
+
+    let on_open = move |ev: leptos::ev::Event| {
+        let input: web_sys::HtmlInputElement = event_target(&ev);
+        set_reading.set(Some("knap"));
+        spawn_local(async move {
+            let file = read_selected_file(&input).await;
+            set_reading.set(None);
+            match file {
+                Some(Ok(text)) => match KnapFile::load(&text) {
+                    Ok(loaded) => {
+                        if let Some(v) = loaded.upgraded_from {
+                            toasts.info(format!(
+                                "This file was saved in format v{v}; it was upgraded to v{KNAP_VERSION}."
+                            ));
+                        }
+                        restore(loaded.file);
+                    }
+                    Err(e) => set_error_msg.set(Some(e)),
+                },
+                Some(Err(e)) => set_error_msg.set(Some(e)),
+                None => {}
+            }
+            // allow re-opening the same file
+            input.set_value("");
+        });
+    };
+
+    // The instance on screen, or the form's instance before solving
+    let current_problem = Signal::derive(move || {
+        if dp_table.with(Option::is_some) { Some(problem.get()) } else { read_problem().ok() }
+    });
+
+    if let Some(link) = link {
+        link.report_problem(instance, current_problem);
+    }
+
+    // A duplicate starts unsolved, with the weights ready to edit
+    if let Some(file) = seed {
+        restore(file);
+        focus_after_render(id("weights"));
+    } else if let Some(p) = initial_problem {
+        set_capacity_input.set(p.capacity.to_string());
+        set_weights_input.set(join_list(&p.weights));
+        set_benefits_input.set(join_list(&p.benefits));
+        set_units_input.set(p.units);
+    }
+    let on_duplicate_click = move |_| {
+        let (Some(on_duplicate), Some(p)) = (on_duplicate, current_problem.get_untracked()) else {
+            set_error_msg.set(Some("Fix the instance before duplicating it.".into()));
+            return;
+        };
+        on_duplicate.run(KnapFile::new(p, settings.get_untracked(), StepPosition::Unsolved, &CellNotes::new()));
     };
 
     // ── View ─────────────────────────────────────────────────────────────────
     view! {
-        <div class="page">
+        <div
+            class=move || if presenting.get() { "page arranged presenting" } else { "page arranged" }
+            style=move || theme.with(Theme::to_style)
+        >
+            {move || presenting.get().then(|| view! {
+                <div class="present-bar">
+                    <span class="present-hint">"→ / PgDn: step  ·  ← / PgUp: back  ·  Esc: exit"</span>
+                    <button class="btn-small" on:click=move |_| stop_presenting()>"Exit presentation"</button>
+                </div>
+            })}
+            <a class="skip-link" href=format!("#{}", id(TABLE_ID))>"Skip to table"</a>
 
             // ── Header ──────────────────────────────────────────────────────
             <header>
@@ -218,189 +1393,704 @@ pub fn KnapsackVisualizer() -> impl IntoView {
             </header>
 
             // ── Form ────────────────────────────────────────────────────────
+            {read_only.then(|| view! {
+                <div class="btn-row readonly-controls">
+                    <button
+                        class="btn btn-solve"
+                        on:click=on_solve_click
+                        disabled=move || computing.get().is_some()
+                    >
+                        "Solve"
+                    </button>
+                    <button
+                        class="btn btn-ghost"
+                        on:click=move |_| prev_step()
+                        disabled=move || revealed.get().is_none_or(|r| r <= 1) || dp_table.with(Option::is_none)
+                    >
+                        "←  Back"
+                    </button>
+                    <button
+                        id=id(STEP_BTN_ID)
+                        class="btn btn-step"
+                        on:click=on_step
+                        disabled=move || computing.get().is_some()
+                    >
+                        {move || match revealed.get() {
+                            None if dp_table.get().is_some() => "↺  Reset steps",
+                            _ => "Next step  →",
+                        }}
+                    </button>
+                </div>
+                {move || error_msg.get().map(|e| view! {
+                    <p class="error">"⚠  "{e}</p>
+                })}
+            })}
+            <Show when=move || !read_only>
+            <LayoutPanel panel=Panel::Form settings=settings>
             <section class="form-card">
                 <div class="field">
-                    <label for="cap">"Capacity  "<span class="mono">"m"</span></label>
-                    <input
-                        id="cap"
-                        type="number"
-                        min="1"
-                        prop:value=move || capacity_input.get()
-                        on:input:target=move |ev| set_capacity_input.set(ev.target().value())
-                        placeholder="e.g. 6"
+                    <label for=id("preset")>"Scenario"</label>
+                    <select id=id("preset") on:change:target=move |ev| on_preset(ev.target().value())>
+                        <option value="">"Custom (no units)"</option>
+                        {PRESETS.iter().enumerate().map(|(i, p)| view! {
+                            <option value=i.to_string() title=p.description>{p.name}</option>
+                        }).collect_view()}
+                    </select>
+                </div>
+                <div class="field">
+                    <label for=id("cap")>
+                        "Capacity  "<span class="mono">"m"</span>
+                        {move || unit_hint(units_input.get().weight_symbol())}
+                    </label>
+                    <NumberStepper
+                        id=id("cap")
+                        label="Capacity"
+                        value=capacity_input
+                        set_value=set_capacity_input
+                        min=Signal::derive(move || settings.with(|s| s.capacity_min))
+                        max=Signal::derive(move || settings.with(|s| s.capacity_max))
+                        locale=Signal::derive(move || settings.with(|s| s.parse_locale))
+                        invalid=Signal::derive(move || field_errors.with(|e| e.capacity.is_some()))
+                        on_enter=Callback::new(move |_| solve())
                     />
+                    {move || field_errors.get().capacity.map(|e| view! {
+                        <p class="field-error">{e}</p>
+                    })}
                 </div>
                 <div class="field">
-                    <label for="weights">"Weights  "<span class="mono">"w₁, w₂, …"</span></label>
+                    <label for=id("weights")>
+                        "Weights  "<span class="mono">"w₁, w₂, …"</span>
+                        {move || unit_hint(units_input.get().weight_symbol())}
+                    </label>
                     <input
-                        id="weights"
+                        id=id("weights")
+                        aria-invalid=move || field_errors.with(|e| e.weights.is_some()).to_string()
                         type="text"
                         prop:value=move || weights_input.get()
-                        on:input:target=move |ev| set_weights_input.set(ev.target().value())
+                        on:input:target=move |ev| {
+                            set_pasted.set(None);
+                            set_weights_input.set(ev.target().value())
+                        }
+                        on:paste=move |ev| on_paste(ev, Field::Weights)
+                        on:keydown=on_enter
                         placeholder="e.g. 2, 3, 4"
                     />
+                    {move || (pasted.get() == Some(Field::Weights)).then(|| view! {
+                        <p class="paste-hint">"Input normalized"</p>
+                    })}
+                    <div class="unit-row">
+                        <label for=id("weight-unit")>"Unit"</label>
+                        <select
+                            id=id("weight-unit")
+                            title="Entries such as 500g or 1.5 lb are converted to this unit before solving"
+                            prop:value=move || weight_unit.get().map(|u| u.symbol()).unwrap_or("")
+                            on:change:target=move |ev| {
+                                let unit = WeightUnit::from_symbol(&ev.target().value());
+                                set_units_input.update(|u| u.weight = unit.map(|w| Unit::suffix(w.symbol())));
+                            }
+                        >
+                            <option value="">"—"</option>
+                            {WeightUnit::ALL.iter().map(|u| view! {
+                                <option value=u.symbol()>{u.symbol()}</option>
+                            }).collect_view()}
+                        </select>
+                        {move || conversion_preview.get().map(|text| view! {
+                            <span class="conversion-preview mono">{text}</span>
+                        })}
+                    </div>
+                    {move || field_errors.get().weights.map(|e| view! {
+                        <p class="field-error">{e}</p>
+                    })}
                 </div>
                 <div class="field">
-                    <label for="benefits">"Benefits  "<span class="mono">"b₁, b₂, …"</span></label>
+                    <label for=id("benefits")>
+                        "Benefits  "<span class="mono">"b₁, b₂, …"</span>
+                        {move || unit_hint(
+                            units_input.get().benefit.as_ref().map(|u| u.symbol.as_str()).unwrap_or("")
+                        )}
+                    </label>
                     <input
-                        id="benefits"
+                        id=id("benefits")
+                        aria-invalid=move || field_errors.with(|e| e.benefits.is_some()).to_string()
                         type="text"
                         prop:value=move || benefits_input.get()
-                        on:input:target=move |ev| set_benefits_input.set(ev.target().value())
+                        on:input:target=move |ev| {
+                            set_pasted.set(None);
+                            set_benefits_input.set(ev.target().value())
+                        }
+                        on:paste=move |ev| on_paste(ev, Field::Benefits)
+                        on:keydown=on_enter
                         placeholder="e.g. 3, 4, 5"
                     />
+                    {move || (pasted.get() == Some(Field::Benefits)).then(|| view! {
+                        <p class="paste-hint">"Input normalized"</p>
+                    })}
+                    {move || field_errors.get().benefits.map(|e| view! {
+                        <p class="field-error">{e}</p>
+                    })}
+                </div>
+                {move || {
+                    let fixes = fixes.get();
+                    (!fixes.is_empty()).then(|| view! {
+                        <div class="quick-fixes" role="group" aria-label="Suggested fixes">
+                            <span class="toolbar-hint">"Quick fix:"</span>
+                            {fixes.into_iter().map(|fix| {
+                                let QuickFix { label, field, value } = fix;
+                                let apply = move |_| match field {
+                                    Field::Capacity => set_capacity_input.set(value.clone()),
+                                    Field::Weights => set_weights_input.set(value.clone()),
+                                    Field::Benefits => set_benefits_input.set(value.clone()),
+                                };
+                                view! { <button class="btn-small" on:click=apply>{label}</button> }
+                            }).collect_view()}
+                        </div>
+                    })
+                }}
+
+                <div class="field">
+                    <label for=id("max-items")>
+                        "At most  "<span class="mono">"k"</span>"  items"
+                    </label>
+                    <input
+                        id=id("max-items")
+                        type="number"
+                        min="0"
+                        max=move || draft_size.get().map(|(n, _)| n)
+                        prop:value=move || max_items_input.get()
+                        on:input:target=move |ev| set_max_items_input.set(ev.target().value())
+                        on:keydown=on_enter
+                        placeholder="no limit"
+                    />
+                </div>
+                <div class="field">
+                    <label for=id("deps")>
+                        "Requires  "<span class="mono">"B:A, …"</span>
+                    </label>
+                    <input
+                        id=id("deps")
+                        type="text"
+                        title="Item B can only be packed together with item A"
+                        prop:value=move || deps_input.get()
+                        on:input:target=move |ev| set_deps_input.set(ev.target().value())
+                        on:keydown=on_enter
+                        placeholder="e.g. 3:1, 4:3"
+                    />
                 </div>
 
+                <label class="toggle">
+                    <input
+                        type="checkbox"
+                        prop:checked=move || headless.get()
+                        on:change:target=move |ev| set_headless.set(ev.target().checked())
+                    />
+                    "Solve only (optimum, timing and the last row; no table)"
+                </label>
+
                 <div class="btn-row">
-                    <button class="btn btn-solve" on:click=on_solve>"Solve"</button>
-                    <button class="btn btn-step"  on:click=on_step>
+                    <button
+                        class="btn btn-solve"
+                        on:click=on_solve_click
+                        disabled=move || {
+                            !field_errors.with(FieldErrors::is_empty) || computing.get().is_some()
+                        }
+                    >
+                        "Solve"
+                    </button>
+                    <button
+                        id=id(STEP_BTN_ID)
+                        class="btn btn-step"
+                        on:click=on_step
+                        disabled=move || computing.get().is_some()
+                    >
                         {move || match revealed.get() {
                             None if dp_table.get().is_some() => "↺  Reset steps",
                             _ => "Next step  →",
                         }}
                     </button>
+                    <button
+                        class="btn btn-ghost"
+                        aria-pressed=move || playing.get().to_string()
+                        title=move || format!("Reveal {} cells a second; the speed is in the settings", settings.with(|s| s.play_speed))
+                        on:click=move |_| toggle_play()
+                        disabled=move || computing.get().is_some() && !playing.get()
+                    >
+                        {move || if playing.get() { "⏸ Pause" } else { "▶ Play" }}
+                    </button>
+                    <button
+                        class="btn btn-ghost"
+                        title="Remove items that cannot change the optimum"
+                        on:click=on_simplify
+                    >
+                        "Simplify"
+                    </button>
+                    <button
+                        class="btn btn-ghost"
+                        aria-pressed=move || read_aloud.get().to_string()
+                        title="Read each step aloud"
+                        on:click=toggle_read_aloud
+                    >
+                        {move || if read_aloud.get() { "🔊 Narration on" } else { "🔇 Narration off" }}
+                    </button>
+                    <button
+                        class="btn btn-ghost"
+                        title="Read the current step again"
+                        disabled=move || narration.with(Option::is_none)
+                        on:click=move |_| {
+                            if let Some(text) = narration.get_untracked() {
+                                speech::speak(&text, NARRATION_LANG);
+                            }
+                        }
+                    >
+                        "↻ Replay"
+                    </button>
+                </div>
+
+                <div class="btn-row file-row">
+                    <button class="btn btn-ghost" on:click=start_presenting>"▶ Present"</button>
+                    {on_duplicate.is_some().then(|| view! {
+                        <button
+                            class="btn btn-ghost"
+                            title="Open a copy of this instance in a new tab"
+                            on:click=on_duplicate_click
+                        >
+                            "⧉ Duplicate"
+                        </button>
+                    })}
+                    <button class="btn btn-ghost" on:click=on_save>"Save .knap"</button>
+                    <label class="btn btn-ghost file-btn" aria-busy=move || (reading.get() == Some("knap")).to_string()>
+                        {move || if reading.get() == Some("knap") { "Opening…" } else { "Open .knap" }}
+                        <input type="file" accept=".knap,application/json" on:change=on_open />
+                    </label>
+                    <button class="btn btn-ghost" on:click=on_export_steps title="Download the walkthrough so far as JSON lines">
+                        "Export steps"
+                    </button>
+                    <button
+                        class="btn btn-ghost"
+                        title="Download the solved instance as a Markdown write-up"
+                        on:click=move |_| export_writeup("knapsack-solution.md", "text/markdown", WriteUp::to_markdown)
+                    >
+                        "Export .md"
+                    </button>
+                    <button
+                        class="btn btn-ghost"
+                        title="Download the solved instance as a LaTeX document"
+                        on:click=move |_| export_writeup("knapsack-solution.tex", "application/x-tex", WriteUp::to_latex)
+                    >
+                        "Export .tex"
+                    </button>
+                    <button
+                        class="btn btn-ghost"
+                        title="Download a flashcard deck (TSV, imports into Anki) with one card per table cell"
+                        on:click=on_export_cards
+                    >
+                        "Export flashcards"
+                    </button>
+                    <label
+                        class="btn btn-ghost file-btn"
+                        title="Replay an exported walkthrough"
+                        aria-busy=move || (reading.get() == Some("steps")).to_string()
+                    >
+                        {move || if reading.get() == Some("steps") { "Reading…" } else { "Replay steps" }}
+                        <input type="file" accept=".jsonl,application/jsonl" on:change=on_import_steps />
+                    </label>
                 </div>
 
                 {move || error_msg.get().map(|e| view! {
                     <p class="error">"⚠  "{e}</p>
                 })}
+                {move || decimal_note.get().map(|(factor, capacity)| view! {
+                    <p class="scale-note">
+                        {format!(
+                            "Decimal weights are scaled ×{factor} to whole numbers: the table uses capacity {capacity}."
+                        )}
+                    </p>
+                })}
+                <Show when=move || needs_wide.get()>
+                    <p class="scale-offer">
+                        "These benefits are too large for native values."
+                        <button
+                            class="btn-small"
+                            on:click=move |_| {
+                                settings.update(|s| s.value_type = ValueType::Wide);
+                                solve();
+                            }
+                        >
+                            "Use wide (u128) values"
+                        </button>
+                    </p>
+                </Show>
+                {move || draft_gcd.get().map(|g| view! {
+                    <p class="scale-offer">
+                        {format!("Capacity and weights are all multiples of {g}: scaling them down shrinks the table {g}×.")}
+                        <button class="btn-small" on:click=on_scale>{format!("Scale ÷{g}")}</button>
+                    </p>
+                })}
+                {move || simplify_report.get().map(|lines| view! {
+                    <div class="simplify-report">
+                        <p>
+                            {format!("Removed {} item{}; the optimum is unchanged.",
+                                lines.len(), if lines.len() == 1 { "" } else { "s" })}
+                        </p>
+                        <ul>{lines.into_iter().map(|l| view! { <li>{l}</li> }).collect_view()}</ul>
+                    </div>
+                })}
             </section>
+            <DegenerateBanners problem=current_problem />
+            </LayoutPanel>
+            </Show>
+
+            <LayoutPanel panel=Panel::Analysis settings=settings defer=true>
+            <InstanceStatsCard problem=current_problem />
+            <PreprocessView problem=current_problem />
+            <DuplicateMerge
+                problem=current_problem
+                fmt=Signal::derive(move || settings.with(|s| s.number_format))
+            />
+            <MonteCarloPanel
+                problem=current_problem
+                locale=Signal::derive(move || settings.with(|s| s.parse_locale))
+                fmt=Signal::derive(move || settings.with(|s| s.number_format))
+            />
+            <RobustPanel
+                problem=current_problem
+                locale=Signal::derive(move || settings.with(|s| s.parse_locale))
+                fmt=Signal::derive(move || settings.with(|s| s.number_format))
+            />
+            <LagrangePanel problem=current_problem />
+            <RacePanel problem=current_problem />
+            <TopDownPanel problem=current_problem />
+
+            // ── Background solve progress ────────────────────────────────────
+            {move || computing.get().map(|p| {
+                let pct = (p.rows_done * 100).checked_div(p.rows_total).unwrap_or(0);
+                view! {
+                    <section class="computing-card" aria-busy="true">
+                        <div class="progress-wrap">
+                            <div class="progress-bar" style=format!("width: {}%", pct)></div>
+                            <span class="progress-label">
+                                {format!("Computing rows {} / {}", p.rows_done, p.rows_total)}
+                            </span>
+                        </div>
+                        <div class="btn-row">
+                            <button class="btn btn-ghost" on:click=on_cancel>"Cancel"</button>
+                        </div>
+                    </section>
+                }
+            })}
+
+            <SettingsPanel settings=settings />
+
+            <RecurrenceSandbox
+                problem=current_problem
+                fmt=Signal::derive(move || settings.with(|s| s.number_format))
+            />
+            </LayoutPanel>
 
-            {KnapsackFormula()}
+            // ── Split view: formula, code and loop trace beside the table ────
+            <div
+                class="split-view"
+                class:split=move || !hide_formula && dp_table.with(Option::is_some)
+                style=move || settings.with(|s| {
+                    format!("order: {}", s.layout.position(Panel::Formula).min(s.layout.position(Panel::Table)))
+                })
+            >
+            <Show when=move || !hide_formula>
+            <LayoutPanel panel=Panel::Formula settings=settings class="split-side" defer=true>
+                <KnapsackFormula>
+                    <ComplexityReadout draft=draft_size allocated=table_allocation />
+                </KnapsackFormula>
+
+                <CodePanel problem=current_problem open=true />
+                <LoopTraceView open=true />
+            </LayoutPanel>
+            </Show>
 
             // ── Table ────────────────────────────────────────────────────────
+            <LayoutPanel panel=Panel::Table settings=settings class="split-main">
             {move || dp_table.get().map(|table| {
-                let cap  = capacity.get();
-                let ws   = item_weights.get();
-                let bs   = item_benefits.get();
-                let n    = ws.len();          // number of items
-                let n_cols = cap + 1;
-
-                let backtrack: std::collections::HashSet<(usize, usize)> = if revealed.get().is_none() {
-                    let mut path = std::collections::HashSet::new();
-                    let mut w = cap;
-                    for i in (1..=n).rev() {
-                        if table[i][w] != table[i - 1][w] {
-                            path.insert((i, w));
-                            w -= ws[i - 1];
-                        }
-                    }
-                    path
-                } else {
-                    std::collections::HashSet::new()
-                };
+                let table = Arc::new(table);
+                let p = problem.get();
+                let shown = settings.get();
+                let (frozen, show_used, density) = (shown.freeze_headers, shown.show_weight_used, shown.density);
+                let weights = p.weights.clone();
+                view! {
+                    <section
+                        id=id(TABLE_ID)
+                        class=if frozen { "table-wrap frozen" } else { "table-wrap" }
+                        tabindex="-1"
+                        aria-label="DP table"
+                    >
+                        <TableToolbar
+                            settings
+                            fill_order=plan.is_none()
+                            expanded_cols
+                            on_capture=Callback::new(move |()| on_capture())
+                            drawing
+                            set_drawing
+                            strokes
+                            find_query
+                            find_msg
+                            on_find=Callback::new(move |()| find_cell())
+                        />
 
-                // Current "active" cell for highlighting (last revealed - 1)
-                let active_linear: Option<usize> = revealed.get()
-                    .and_then(|r| r.checked_sub(1));
+                        {move || narration.get().map(|text| view! {
+                            <p class="narration" aria-live="polite">{text}</p>
+                        })}
 
-                view! {
-                    <section class="table-wrap">
-                        <table class="dp-table">
-                            <thead>
-                                <tr>
-                                    // top-left corner: "item \ w"
-                                    <th class="corner">"item \\ w"</th>
-                                    // one column per capacity value 0..=m
-                                    {(0..=cap).map(|w| view! {
-                                        <th class="w-header">{w}</th>
-                                    }).collect_view()}
-                                </tr>
-                            </thead>
-                            <tbody>
-                                // Row 0: the "no items" baseline (always fully visible)
-                                <tr class="row-base">
-                                    <td class="item-header">
-                                        <span class="item-badge">"—"</span>
-                                        <span class="item-meta">"base"</span>
-                                    </td>
-                                    {(0..=cap).map(|_| view! {
-                                        <td class="cell cell-base">"0"</td>
-                                    }).collect_view()}
-                                </tr>
-
-                                // Rows 1..=n: one per item
-                                {(1..=n).map(|i| {
-                                    let wi = ws[i - 1];
-                                    let bi = bs[i - 1];
-                                    view! {
-                                        <tr>
-                                            // item header column
-                                            <td class="item-header">
-                                                <span class="item-badge">{i}</span>
-                                                <span class="item-meta">
-                                                    "w="<strong>{wi}</strong>
-                                                    " b="<strong>{bi}</strong>
-                                                </span>
-                                            </td>
-                                            // data cells
-                                            {(0..n_cols).map(|c| {
-                                                let linear = (i - 1) * n_cols + c;
-                                                let visible = is_visible(i, c, n_cols);
-                                                let is_active = active_linear == Some(linear);
-                                                let is_backtrack = backtrack.contains(&(i,c));
-                                                let val = table[i][c];
-
-                                                // Did we take the item in this cell?
-                                                let took_item = visible
-                                                    && wi <= c
-                                                    && val == table[i-1][c - wi] + bi
-                                                    && val > table[i-1][c];
-
-                                                let cls = if !visible {
-                                                    "cell cell-hidden"
-                                                } else if is_active {
-                                                    "cell cell-active"
-                                                }else if is_backtrack {
-                                                    "cell cell-backtrack"
-                                                } else if took_item {
-                                                    "cell cell-took"
-                                                } else {
-                                                    "cell"
-                                                };
-
-                                                view! {
-                                                    <td class=cls>
-
-                                                    {if visible { val.to_string() } else { String::new() }}
-                                                    {is_backtrack.then(|| { view!{<span class="star">"★"</span>} })}//
-
-
-                                                    </td>
-                                                }
-                                            }).collect_view()}
-                                        </tr>
-                                    }
-                                }).collect_view()}
-                            </tbody>
-                        </table>
+                        <DpTable
+                            table=table.clone()
+                            problem=p
+                            settings=shown
+                            groups
+                            scale=column_scale.get()
+                            breakpoints=breakpoints.get()
+                            sim
+                            trace_marks
+                            active_pos
+                            hover_weight
+                            set_hover_weight
+                            focused_row
+                            set_focused_row
+                            expanded_cols
+                            notes
+                            on_edit_note=Callback::new(move |(i, c)| edit_note(i, c))
+                            drawing
+                            strokes
+                            capture_id=id(CAPTURE_ID)
+                        />
+
+                        // ── Weight used: same cells, the weight of each best subset ─
+                        {show_used.then(|| view! {
+                            <WeightUsedTable table weights density groups sim />
+                        })}
 
-                        // ── Progress bar ──────────────────────────────────
+                        // ── Progress bar: fill phase, then traceback phase ─
                         {move || {
-                            let total = total_cells();
-                            let done  = revealed.get().unwrap_or(total);
-                            let pct   = if total > 0 { done * 100 / total } else { 0 };
+                            let Progress { filled: done, cells: total, walked: hops_done, hops } = sim.with(Simulation::progress);
+                            let pct   = (done * 100).checked_div(total).unwrap_or(0);
+                            let trace_pct = (hops_done * 100).checked_div(hops).unwrap_or(0);
                             let label = if total == 0 {
                                 String::new()
-                            } else if done >= total {
-                                "✓ Complete".to_string()
+                            } else if done < total {
+                                format!("filling {} / {}", done, total)
+                            } else if hops_done < hops {
+                                format!("tracing {} / {}", hops_done, hops)
                             } else {
-                                format!("{} / {} cells", done, total)
+                                "✓ Complete".to_string()
                             };
                             view! {
-                                <div class="progress-wrap">
-                                    <div class="progress-bar" style=format!("width: {}%", pct)></div>
+                                <div class="progress-wrap two-phase">
+                                    <div class="progress-segment fill-phase" title="Filling the table">
+                                        <div class="progress-bar" style=format!("width: {}%", pct)></div>
+                                    </div>
+                                    <div class="progress-segment trace-phase" title="Tracing back the solution">
+                                        <div class="progress-bar" style=format!("width: {}%", trace_pct)></div>
+                                    </div>
                                     <span class="progress-label">{label}</span>
                                 </div>
                             }
                         }}
+
+                        // ── Traceback controls ────────────────────────────
+                        {move || (revealed.get().is_none() && !trace.with(Vec::is_empty)).then(|| {
+                            let len = trace.with(Vec::len);
+                            let explanation = traced.get()
+                                .and_then(|k| k.checked_sub(1))
+                                .and_then(|k| trace.with(|hops| hops.get(k).copied()))
+                                .and_then(|hop| dp_table.with(|t| t.as_ref().map(|t| {
+                                    let fmt = settings.with(|s| s.number_format);
+                                    problem.with(|p| narrate_hop(t, p, hop, &fmt))
+                                })));
+                            view! {
+                                <div class="trace-bar">
+                                    <span class="trace-title">"Traceback"</span>
+                                    <button
+                                        class="btn-small"
+                                        on:click=move |_| {
+                                            stop_trace();
+                                            sim_apply(SimEvent::TraceSeek(0));
+                                        }
+                                    >
+                                        "⏮ Start"
+                                    </button>
+                                    <button
+                                        class="btn-small"
+                                        disabled=move || traced.get().is_none_or(|k| k >= len)
+                                        on:click=move |_| trace_next()
+                                    >
+                                        "Next hop"
+                                    </button>
+                                    <button class="btn-small" on:click=move |_| play_trace()>
+                                        {move || if trace_playing.get() { "⏸ Pause" } else { "▶ Play" }}
+                                    </button>
+                                    <button
+                                        class="btn-small"
+                                        disabled=move || traced.get().is_none()
+                                        on:click=move |_| {
+                                            stop_trace();
+                                            sim_apply(SimEvent::TraceAll);
+                                        }
+                                    >
+                                        "Show all"
+                                    </button>
+                                </div>
+                                {explanation.map(|text| view! {
+                                    <p class="narration trace-explain" aria-live="polite">{text}</p>
+                                })}
+                                {traced.get().map(|k| {
+                                    // capacity left once the first k hops are walked
+                                    let (cap, weights) = problem.with(|p| (p.capacity, p.weights.clone()));
+                                    let walked = trace.with(|hops| hops[..k.min(len)].to_vec());
+                                    let left = walked.last().map_or(cap, |hop| {
+                                        if hop.taken { hop.col - weights[hop.row - 1] } else { hop.col }
+                                    });
+                                    let pct = |w: usize| (w * 100) as f64 / cap.max(1) as f64;
+                                    let step = walked.last().filter(|hop| hop.taken).map(|hop| {
+                                        format!("w = {} − {} = {left}", hop.col, weights[hop.row - 1])
+                                    });
+                                    view! {
+                                        <div class="slack-meter" aria-label="Remaining capacity">
+                                            <div class="slack-track">
+                                                {walked.iter().filter(|hop| hop.taken).map(|hop| {
+                                                    let w = weights[hop.row - 1];
+                                                    view! {
+                                                        <div
+                                                            class="slack-item"
+                                                            style=format!("width: {:.2}%", pct(w))
+                                                            title=format!("item {}: weight {w}", hop.row)
+                                                        >
+                                                            {hop.row}
+                                                        </div>
+                                                    }
+                                                }).collect_view()}
+                                                <div class="slack-left" style=format!("width: {:.2}%", pct(left))></div>
+                                            </div>
+                                            <span class="slack-label">
+                                                {format!("{left} of {cap} left")}
+                                                {step.map(|s| format!(" ({s})"))}
+                                            </span>
+                                        </div>
+                                    }
+                                })}
+                            }
+                        })}
                     </section>
                 }
             })}
+            </LayoutPanel>
+            </div>
+
+            // ── Solution summary ─────────────────────────────────────────────
+            <LayoutPanel panel=Panel::Results settings=settings defer=true>
+            {move || dp_table.get().filter(|_| revealed.get().is_none()).map(|table| {
+                let p = problem.get();
+                let chosen = chosen_items(&table, &p.weights, p.capacity);
+                let best = table[p.len()][p.capacity];
+                let fmt = settings.get().number_format;
+                let check = settings.with(|s| s.cross_check).then(|| cross_check(&p, best));
+                view! {
+                    <SolutionSummary
+                        problem=p.clone()
+                        best=best
+                        chosen=chosen.clone()
+                        fmt=fmt
+                        explanation=explain(&table, &p, &fmt)
+                        cross_check=check
+                    />
+                    <ContributionWaterfall problem=p.clone() chosen=chosen.clone() fmt=fmt />
+                    <PackingBag problem=p packed=packed />
+                }
+            })}
+            {move || headless_result.get().map(|(p, s, elapsed)| {
+                let note = format!(
+                    "Solved without table: {} items, {} cells evaluated in {:.2} ms.",
+                    p.len(), s.cells, elapsed
+                );
+                let fmt = settings.get().number_format;
+                let check = settings.with(|st| st.cross_check).then(|| cross_check(&p, s.best));
+                view! {
+                    <SolutionSummary
+                        problem=p.clone()
+                        best=s.best
+                        chosen=s.chosen.clone()
+                        fmt=fmt
+                        note=note
+                        cross_check=check
+                    />
+                    <AnswerStrip problem=p.clone() row=s.last_row fmt=fmt />
+                    <ContributionWaterfall problem=p.clone() chosen=s.chosen.clone() fmt=fmt />
+                    <PackingBag problem=p packed=Signal::stored(s.chosen) />
+                }
+            })}
+
+            {move || wide_result.get().map(|(p, s)| view! {
+                <WideTable problem=p solution=s fmt=settings.get().number_format />
+            })}
+            {move || cardinality_result.get().map(|(p, k, s)| view! {
+                <CardinalityTable problem=p limit=k solution=s fmt=settings.get().number_format />
+            })}
+            {move || precedence_result.get().map(|(p, parents, s, unconstrained)| view! {
+                <PrecedenceView
+                    problem=p
+                    parents=parents
+                    solution=s
+                    unconstrained=unconstrained
+                    fmt=settings.get().number_format
+                />
+            })}
 
+            </LayoutPanel>
 
+            <LayoutPanel panel=Panel::Legend settings=settings defer=true>
             {KnapsackLegend()}
+            </LayoutPanel>
 
+            {move || large_input.get().map(|(p, stepping)| {
+                let limit = settings.with(|s| s.max_visual_items);
+                let count = p.len();
+                let truncated = p.truncated(limit);
+                view! {
+                    <LargeInputDialog
+                        count=count
+                        limit=limit
+                        on_truncate=Callback::new(move |_| {
+                            set_large_input.set(None);
+                            load(truncated.clone(), stepping);
+                        })
+                        on_solve_only=Callback::new(move |_| {
+                            set_large_input.set(None);
+                            solve_without_table(p.clone());
+                        })
+                        on_cancel=Callback::new(move |_| set_large_input.set(None))
+                    />
+                }
+            })}
+
+            <ToastHost />
         </div>
     }
 }
+
+/// Where a file opened mid-walkthrough picks up once its table is shown,
+/// which may only be after streaming or the large-input dialog.
+struct Resume {
+    problem: Problem,
+    events: Vec<SimEvent>,
+    /// Cell notes to restore; `None` keeps the current ones.
+    notes: Option<CellNotes>,
+}
+
+/// Columns of `p`'s table and the cells filled below its baseline row;
+/// `None` when the count overflows.
+fn table_size(p: &Problem) -> Option<(usize, usize)> {
+    let cols = p.capacity.checked_add(1)?;
+    Some((cols, p.len().checked_mul(cols)?))
+}
+
+/// Small "(kg)" suffix for form labels when a unit is selected.
+fn unit_hint(symbol: &str) -> Option<impl IntoView + use<>> {
+    (!symbol.is_empty()).then(|| {
+        let text = format!("  ({symbol})");
+        view! { <span class="unit">{text}</span> }
+    })
+}
@@ -0,0 +1,47 @@
+use crate::problem::Problem;
+
+// ─── Lagrangian relaxation ───────────────────────────────────────────────────
+
+/// The capacity constraint moved into the objective with multiplier `λ`:
+/// `L(λ) = λ·m + Σ max(0, b_i − λ·w_i)`. Every `L(λ)` with `λ ≥ 0` is an
+/// upper bound on the optimum.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Relaxation {
+    pub lambda: f64,
+    pub bound: f64,
+    /// Items with a positive reduced benefit `b_i − λ·w_i`, 1-based.
+    pub selection: Vec<usize>,
+    /// Total weight of `selection`; it may exceed the capacity.
+    pub weight: usize,
+}
+
+pub fn relax(p: &Problem, lambda: f64) -> Relaxation {
+    let selection: Vec<usize> = (1..=p.len())
+        .filter(|&i| p.benefits[i - 1] as f64 - lambda * p.weights[i - 1] as f64 > 0.0)
+        .collect();
+    let reduced: f64 = selection.iter().map(|&i| p.benefits[i - 1] as f64 - lambda * p.weights[i - 1] as f64).sum();
+    let weight = selection.iter().map(|&i| p.weights[i - 1]).sum();
+    Relaxation { lambda, bound: lambda * p.capacity as f64 + reduced, selection, weight }
+}
+
+/// Multipliers where `L` changes slope: 0 and every ratio `b_i / w_i`,
+/// ascending. `L` is convex and linear in between, so its minimum lies on
+/// one of them.
+pub fn breakpoints(p: &Problem) -> Vec<f64> {
+    let mut points: Vec<f64> = std::iter::once(0.0)
+        .chain(p.weights.iter().zip(&p.benefits).filter(|&(&w, _)| w > 0).map(|(&w, &b)| b as f64 / w as f64))
+        .collect();
+    points.sort_by(f64::total_cmp);
+    points.dedup();
+    points
+}
+
+/// The multiplier with the tightest bound. That bound equals the
+/// fractional (LP) relaxation, since the knapsack has a single constraint.
+pub fn tightest(p: &Problem) -> Relaxation {
+    breakpoints(p)
+        .into_iter()
+        .map(|lambda| relax(p, lambda))
+        .min_by(|a, b| a.bound.total_cmp(&b.bound))
+        .unwrap_or_else(|| relax(p, 0.0))
+}
@@ -1,19 +1,96 @@
+#![recursion_limit = "256"]
+
+#[cfg(all(feature = "core-only", feature = "web"))]
+compile_error!("the `core-only` feature excludes the web UI: depend with `default-features = false`");
+
+#[cfg(feature = "web")]
 use leptos::prelude::*;
+#[cfg(feature = "web")]
 use leptos_meta::*;
-use leptos_router::{components::*, path};
+#[cfg(feature = "web")]
+use leptos_router::{components::*, path, Lazy};
 
-// Modules
+// Core modules: plain Rust, usable without the web UI
+pub mod analysis;
+pub mod analytics;
+pub mod assignment;
+pub mod codegen;
+pub mod columns;
+pub mod diff;
+pub mod display;
+pub mod flashcards;
+pub mod format;
+pub mod io;
+pub mod lagrange;
+pub mod metrics;
+pub mod narration;
+pub mod order;
+pub mod parse;
+pub mod playlist;
+pub mod presets;
+pub mod problem;
+pub mod reference;
+pub mod robust;
+pub mod schema;
+pub mod settings;
+pub mod simulation;
+pub mod solver;
+pub mod startup;
+pub mod steplog;
+pub mod store;
+pub mod theme;
+pub mod writeup;
+
+// Web UI modules
+#[cfg(feature = "web")]
+mod capture;
+#[cfg(feature = "web")]
 mod components;
-mod pages;
+#[cfg(feature = "web")]
+mod files;
+#[cfg(feature = "web")]
+mod focus;
+#[cfg(feature = "web")]
 mod formula;
+#[cfg(feature = "web")]
+mod fullscreen;
+#[cfg(feature = "web")]
+mod idb;
+#[cfg(feature = "web")]
 pub mod knapsack;
+#[cfg(feature = "web")]
+mod pages;
+#[cfg(feature = "web")]
+mod sounds;
+#[cfg(feature = "web")]
+mod speech;
+#[cfg(feature = "web")]
+mod storage;
+#[cfg(feature = "web")]
+mod streaming;
+#[cfg(feature = "web")]
+mod timing;
+#[cfg(feature = "web")]
+mod workspace;
 
 // Top-Level pages
-use crate::pages::home::Home;
+#[cfg(feature = "web")]
+use crate::pages::{
+    example::{Example, ExampleIndex},
+    home::HomeRoute,
+    theory::TheoryRoute,
+};
+#[cfg(feature = "web")]
 pub use formula::KnapsackFormula;
+#[cfg(feature = "web")]
 pub use knapsack::KnapsackVisualizer;
+#[cfg(feature = "web")]
+pub use timing::mark_startup;
+#[cfg(feature = "web")]
+pub use workspace::Workspace;
 
 /// An app router which renders the homepage and handles 404's
+#[cfg(feature = "web")]
 #[component]
 pub fn App() -> impl IntoView {
     // Provides context that manages stylesheets, titles, meta tags, etc.
@@ -31,8 +108,77 @@ pub fn App() -> impl IntoView {
 
         <Router>
             <Routes fallback=|| view! { NotFound }>
-                <Route path=path!("/") view=Home />
+                <Route path=path!("/") view=Workspace />
+                <Route path=path!("/theory") view={Lazy::<TheoryRoute>::new()} />
+                <Route path=path!("/examples") view=ExampleIndex />
+                <Route path=path!("/examples/:slug") view=Example />
+                <Route path=path!("/welcome") view={Lazy::<HomeRoute>::new()} />
             </Routes>
         </Router>
     }
 }
+
+/// The full HTML document the SSR server wraps around [`App`]
+#[cfg(feature = "ssr")]
+pub fn shell(options: LeptosOptions) -> impl IntoView {
+    view! {
+        <!DOCTYPE html>
+        // `App` sets the lang/theme attributes and the charset/viewport tags
+        <html>
+            <head>
+                <AutoReload options=options.clone() />
+                <HydrationScripts options />
+                <link rel="stylesheet" id="leptos" href="/pkg/mochila-leptos.css" />
+                <link
+                    rel="stylesheet"
+                    href="https://cdn.jsdelivr.net/npm/katex@0.16.9/dist/katex.min.css"
+                />
+                <script defer src="https://cdn.jsdelivr.net/npm/katex@0.16.9/dist/katex.min.js"></script>
+                <MetaTags />
+            </head>
+            <body>
+                <App />
+            </body>
+        </html>
+    }
+}
+
+/// Every preset as a standalone HTML page with its table already solved,
+/// keyed by the path it should be written to under the site root.
+#[cfg(feature = "ssr")]
+pub fn prerender_examples() -> Vec<(String, String)> {
+    use crate::pages::example::SolvedExample;
+
+    let page = |title: &str, body: AnyView| {
+        let body = Owner::new().with(|| body.to_html());
+        format!(
+            "<!DOCTYPE html><html lang=\"en\"><head><meta charset=\"utf-8\">\
+             <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\
+             <title>{title} · Knapsack DP</title>\
+             <link rel=\"stylesheet\" href=\"/pkg/mochila-leptos.css\"></head>\
+             <body>{body}</body></html>"
+        )
+    };
+
+    let mut pages = vec![(
+        "examples/index.html".to_string(),
+        page("Solved examples", view! { <ExampleIndex /> }.into_any()),
+    )];
+    for preset in presets::PRESETS {
+        pages.push((
+            format!("examples/{}/index.html", preset.slug()),
+            page(preset.name, view! { <SolvedExample preset /> }.into_any()),
+        ));
+    }
+    pages
+}
+
+/// Entry point of the `hydrate` build: attaches to the server-rendered page
+#[cfg(feature = "hydrate")]
+#[wasm_bindgen::prelude::wasm_bindgen]
+pub fn hydrate() {
+    mark_startup(startup::Mark::WasmInit);
+    console_error_panic_hook::set_once();
+    leptos::mount::hydrate_body(App);
+    mark_startup(startup::Mark::Mounted);
+}
@@ -1,15 +1,21 @@
 use leptos::prelude::*;
-use mochila_leptos::App;
-use mochila_leptos::knapsack::KnapsackVisualizer;
+use leptos_router::components::Router;
+use mochila_leptos::startup::Mark;
+use mochila_leptos::{mark_startup, Workspace};
 
 fn main() {
+    mark_startup(Mark::WasmInit);
     // set up logging
     _ = console_log::init_with_level(log::Level::Debug);
     console_error_panic_hook::set_once();
 
+    // the router supplies the query the workspace reads `?playlist=` from
     mount_to_body(|| {
         view! {
-            <KnapsackVisualizer />
+            <Router>
+                <Workspace />
+            </Router>
         }
-    })
+    });
+    mark_startup(Mark::Mounted);
 }
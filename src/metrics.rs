@@ -0,0 +1,113 @@
+use std::cmp::Ordering;
+
+// ─── Solver runs ─────────────────────────────────────────────────────────────
+
+/// Counters of one solver run. Each solver fills the counters it has; the
+/// others stay `None` and show as a dash.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RunMetrics {
+    /// 1-based position in the session, assigned when recorded.
+    pub run: usize,
+    pub solver: &'static str,
+    pub items: usize,
+    pub capacity: usize,
+    pub best: u128,
+    /// DP cells filled.
+    pub cells: Option<usize>,
+    /// Search nodes explored (branch and bound).
+    pub nodes: Option<usize>,
+    /// Nodes discarded by their bound.
+    pub pruned: Option<usize>,
+    /// Subproblems answered from the memo (top-down).
+    pub cache_hits: Option<usize>,
+    pub elapsed_ms: Option<f64>,
+}
+
+/// Column a list of runs can be sorted by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortKey {
+    Run,
+    Solver,
+    Items,
+    Capacity,
+    Best,
+    Cells,
+    Nodes,
+    Pruned,
+    CacheHits,
+    Time,
+}
+
+impl SortKey {
+    pub const ALL: [SortKey; 10] = [
+        SortKey::Run,
+        SortKey::Solver,
+        SortKey::Items,
+        SortKey::Capacity,
+        SortKey::Best,
+        SortKey::Cells,
+        SortKey::Nodes,
+        SortKey::Pruned,
+        SortKey::CacheHits,
+        SortKey::Time,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SortKey::Run => "#",
+            SortKey::Solver => "solver",
+            SortKey::Items => "n",
+            SortKey::Capacity => "W",
+            SortKey::Best => "optimum",
+            SortKey::Cells => "cells",
+            SortKey::Nodes => "nodes",
+            SortKey::Pruned => "pruned",
+            SortKey::CacheHits => "cache hits",
+            SortKey::Time => "ms",
+        }
+    }
+
+    /// The column's value in `m`, as shown in the table.
+    pub fn cell(self, m: &RunMetrics) -> String {
+        let count = |v: Option<usize>| v.map_or_else(|| "–".to_string(), |v| v.to_string());
+        match self {
+            SortKey::Run => m.run.to_string(),
+            SortKey::Solver => m.solver.to_string(),
+            SortKey::Items => m.items.to_string(),
+            SortKey::Capacity => m.capacity.to_string(),
+            SortKey::Best => m.best.to_string(),
+            SortKey::Cells => count(m.cells),
+            SortKey::Nodes => count(m.nodes),
+            SortKey::Pruned => count(m.pruned),
+            SortKey::CacheHits => count(m.cache_hits),
+            SortKey::Time => m.elapsed_ms.map_or_else(|| "–".to_string(), |t| format!("{t:.2}")),
+        }
+    }
+
+    fn compare(self, a: &RunMetrics, b: &RunMetrics) -> Ordering {
+        match self {
+            SortKey::Run => a.run.cmp(&b.run),
+            SortKey::Solver => a.solver.cmp(b.solver),
+            SortKey::Items => a.items.cmp(&b.items),
+            SortKey::Capacity => a.capacity.cmp(&b.capacity),
+            SortKey::Best => a.best.cmp(&b.best),
+            SortKey::Cells => a.cells.cmp(&b.cells),
+            SortKey::Nodes => a.nodes.cmp(&b.nodes),
+            SortKey::Pruned => a.pruned.cmp(&b.pruned),
+            SortKey::CacheHits => a.cache_hits.cmp(&b.cache_hits),
+            SortKey::Time => {
+                let t = |m: &RunMetrics| m.elapsed_ms.unwrap_or(f64::NEG_INFINITY);
+                t(a).total_cmp(&t(b))
+            }
+        }
+    }
+}
+
+/// Sort `runs` by `key`, ties broken by run order. Missing counters sort
+/// before any value.
+pub fn sort_runs(runs: &mut [RunMetrics], key: SortKey, descending: bool) {
+    runs.sort_by(|a, b| {
+        let order = key.compare(a, b).then(a.run.cmp(&b.run));
+        if descending { order.reverse() } else { order }
+    });
+}
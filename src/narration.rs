@@ -0,0 +1,112 @@
+use crate::format::NumberFormat;
+use crate::problem::Problem;
+use crate::solver::{chosen_items, TraceHop};
+
+/// One plain-English sentence explaining how `table[row][col]` was filled,
+/// suitable for captions and text-to-speech. `row` is the 1-based item.
+pub fn narrate(table: &[Vec<usize>], problem: &Problem, row: usize, col: usize, fmt: &NumberFormat) -> String {
+    let units = &problem.units;
+    let (w, b) = (problem.weights[row - 1], problem.benefits[row - 1]);
+    let skip = table[row - 1][col];
+    let head = format!("Item {row}, capacity {}.", units.weight(col));
+
+    if w > col {
+        return format!(
+            "{head} Item {row} weighs {}, too heavy, so we keep {} from the row above.",
+            units.weight(w),
+            units.benefit(skip, fmt),
+        );
+    }
+
+    let take = b + table[row - 1][col - w];
+    let verdict = if take > skip {
+        format!("Taking it is better: {}.", units.benefit(take, fmt))
+    } else {
+        format!("Leaving it is at least as good: {}.", units.benefit(skip, fmt))
+    };
+    format!(
+        "{head} Leaving item {row} gives {}; taking it gives {} plus {}, which is {}. {verdict}",
+        units.benefit(skip, fmt),
+        units.benefit(b, fmt),
+        units.benefit(table[row - 1][col - w], fmt),
+        units.benefit(take, fmt),
+    )
+}
+
+/// Why the traceback took or skipped the item at `hop`.
+pub fn narrate_hop(table: &[Vec<usize>], problem: &Problem, hop: TraceHop, fmt: &NumberFormat) -> String {
+    let TraceHop { row, col, taken } = hop;
+    let units = &problem.units;
+    let here = units.benefit(table[row][col], fmt);
+    let above = units.benefit(table[row - 1][col], fmt);
+    if taken {
+        let w = problem.weights[row - 1];
+        format!(
+            "dp[{row}][{col}] = {here} differs from dp[{}][{col}] = {above}, so item {row} was taken; \
+             continue at capacity {col} − {} = {}.",
+            row - 1,
+            units.weight(w),
+            units.weight(col - w),
+        )
+    } else {
+        format!(
+            "dp[{row}][{col}] = {here} equals dp[{}][{col}] = {above}, so item {row} was left out; \
+             continue straight up.",
+            row - 1,
+        )
+    }
+}
+
+/// A short paragraph explaining a solved instance: which items make the
+/// optimum, how much of the capacity they use and how the traceback found
+/// them. Numbers follow `fmt`, so they read in the chosen locale.
+pub fn explain(table: &[Vec<usize>], problem: &Problem, fmt: &NumberFormat) -> String {
+    let units = &problem.units;
+    let (n, m) = (problem.len(), problem.capacity);
+    let best = table[n][m];
+    let chosen = chosen_items(table, &problem.weights, m);
+    if chosen.is_empty() {
+        return format!(
+            "The optimum is {}: no item both fits in the capacity {} and adds value.",
+            units.benefit(0, fmt),
+            units.weight(m),
+        );
+    }
+
+    let used: usize = chosen.iter().map(|&i| problem.weights[i - 1]).sum();
+    let left_out: Vec<usize> = (1..=n).filter(|i| !chosen.contains(i)).collect();
+    let mut text = format!(
+        "The optimum {} is achieved by {} {}, using {} of the capacity {}",
+        units.benefit(best, fmt),
+        if chosen.len() == 1 { "item" } else { "items" },
+        join_words(&chosen),
+        units.weight(used),
+        units.weight(m),
+    );
+    text += &if used < m {
+        format!(" and leaving {} unused.", units.weight(m - used))
+    } else {
+        ", a perfect fit.".to_string()
+    };
+    text += &format!(
+        " Reading up from dp[{n}][{m}], each cell that differs from the one above marks a taken item; {}.",
+        match left_out.len() {
+            0 => "every item was taken".to_string(),
+            1 => format!("item {} was left out", left_out[0]),
+            _ => format!("items {} were left out", join_words(&left_out)),
+        }
+    );
+    text
+}
+
+/// `1`, `1 and 2`, `1, 2 and 3`.
+fn join_words(items: &[usize]) -> String {
+    match items {
+        [] => String::new(),
+        [one] => one.to_string(),
+        [rest @ .., last] => format!(
+            "{} and {last}",
+            rest.iter().map(usize::to_string).collect::<Vec<_>>().join(", ")
+        ),
+    }
+}
@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+use crate::solver::StepPlan;
+
+/// Order in which the data cells (item rows 1..=n × capacities 0..=W) are
+/// computed and revealed. Any order works as long as row `i - 1` is done up
+/// to column `w` before `(i, w)`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FillOrder {
+    /// Item by item, each row left to right (the textbook loop nest).
+    #[default]
+    RowMajor,
+    /// Capacity by capacity, each column top to bottom.
+    ColumnMajor,
+}
+
+impl FillOrder {
+    pub const ALL: [FillOrder; 2] = [FillOrder::RowMajor, FillOrder::ColumnMajor];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            FillOrder::RowMajor => "row",
+            FillOrder::ColumnMajor => "column",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|o| o.code() == code)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            FillOrder::RowMajor => "Row by row",
+            FillOrder::ColumnMajor => "Column by column",
+        }
+    }
+
+    /// The reveal plan this order stands for.
+    pub fn plan(self) -> StepPlan {
+        match self {
+            FillOrder::RowMajor => StepPlan::RowMajor,
+            FillOrder::ColumnMajor => StepPlan::ColumnMajor,
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use crate::format::NumberFormat;
+use crate::pages::not_found::NotFound;
+use crate::presets::{PRESETS, Preset, find_preset};
+use crate::schema::{Solution, Table};
+use crate::solver::backtrack;
+use leptos::prelude::*;
+use leptos_router::hooks::use_params_map;
+
+/// Links to every pre-solved preset
+#[component]
+pub fn ExampleIndex() -> impl IntoView {
+    view! {
+        <main class="page example-page">
+            <h1>"Solved examples"</h1>
+            <ul class="example-list">
+                {PRESETS.iter().map(|p| view! {
+                    <li>
+                        <a href=format!("/examples/{}", p.slug())>{p.name}</a>
+                        " — "{p.description}
+                    </li>
+                }).collect_view()}
+            </ul>
+        </main>
+    }
+}
+
+/// `/examples/:slug`
+#[component]
+pub fn Example() -> impl IntoView {
+    let params = use_params_map();
+    move || match params.with(|p| p.get("slug")).as_deref().and_then(find_preset) {
+        Some(preset) => view! { <SolvedExample preset /> }.into_any(),
+        None => view! { <NotFound /> }.into_any(),
+    }
+}
+
+/// A preset with its finished table, the backtracked path and the answer,
+/// all computed while rendering so the page reads without any wasm.
+#[component]
+pub fn SolvedExample(preset: &'static Preset) -> impl IntoView {
+    let p = preset.problem();
+    let fmt = NumberFormat::default();
+    let table = Table::solve(&p);
+    let path = backtrack(&table.values, &p.weights, p.capacity);
+    let Solution { best, items: chosen, weight: used } = Solution::from_table(&table, &p);
+    let units = p.units.clone();
+
+    let header = (0..=p.capacity).map(|w| view! { <th title=units.weight(w)>{w}</th> }).collect_view();
+    let rows = table.values.iter().enumerate().map(|(i, row)| {
+        let label = if i == 0 {
+            view! {
+                <span class="item-badge">"—"</span>
+                <span class="item-meta">"base"</span>
+            }.into_any()
+        } else {
+            view! {
+                <span class="item-badge">{i}</span>
+                <span class="item-meta">
+                    "w="<strong>{units.weight(p.weights[i - 1])}</strong>
+                    " b="<strong>{units.benefit(p.benefits[i - 1], &fmt)}</strong>
+                </span>
+            }.into_any()
+        };
+        let cells = row.iter().enumerate().map(|(c, &val)| {
+            let cls = if i == 0 {
+                "cell cell-base"
+            } else if path.contains(&(i, c)) {
+                "cell cell-backtrack"
+            } else {
+                "cell"
+            };
+            view! { <td class=cls>{units.benefit(val, &fmt)}</td> }
+        }).collect_view();
+        view! {
+            <tr class=(i == 0).then_some("row-base")>
+                <td class="item-header">{label}</td>
+                {cells}
+            </tr>
+        }
+    }).collect_view();
+
+    view! {
+        <main class="page example-page">
+            <h1>{preset.name}</h1>
+            <p class="subtitle">{preset.description}</p>
+            <div class="table-wrap">
+                <table class="dp-table">
+                    <thead>
+                        <tr>
+                            <th class="corner">"item \\ w"</th>
+                            {header}
+                        </tr>
+                    </thead>
+                    <tbody>{rows}</tbody>
+                </table>
+            </div>
+            <p class="example-answer">
+                "Best benefit "<strong>{units.benefit(best, &fmt)}</strong>
+                " from items "<strong>{format!("{chosen:?}")}</strong>
+                ", using "{units.weight(used)}" of "{units.weight(p.capacity)}"."
+            </p>
+        </main>
+    }
+}
@@ -1,5 +1,6 @@
 use crate::components::counter_btn::Button;
 use leptos::prelude::*;
+use leptos_router::{lazy_route, LazyRoute};
 
 /// Default Home Page
 #[component]
@@ -50,3 +51,18 @@ pub fn Home() -> impl IntoView {
         </ErrorBoundary>
     }
 }
+
+/// [`Home`] as a lazy route, loaded on its first visit like
+/// [`TheoryRoute`](crate::pages::theory::TheoryRoute).
+pub struct HomeRoute;
+
+#[lazy_route]
+impl LazyRoute for HomeRoute {
+    fn data() -> Self {
+        HomeRoute
+    }
+
+    fn view(_this: Self) -> AnyView {
+        view! { <Home /> }.into_any()
+    }
+}
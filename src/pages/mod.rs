@@ -1,2 +1,4 @@
+pub mod example;
 pub mod home;
 pub mod not_found;
+pub mod theory;
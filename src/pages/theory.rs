@@ -0,0 +1,36 @@
+use crate::formula::KnapsackFormula;
+use leptos::prelude::*;
+use leptos_router::{lazy_route, LazyRoute};
+
+/// The recurrence on its own, without the interactive table
+#[component]
+pub fn Theory() -> impl IntoView {
+    view! {
+        <main class="page theory-page">
+            <KnapsackFormula>
+                <p class="theory-note">
+                    "Each cell holds the best benefit reachable with the first "
+                    <strong>"i"</strong>" items and capacity "<strong>"w"</strong>
+                    ". Row i only reads row i − 1, so the table fills top to bottom "
+                    "and the answer sits in the bottom-right cell."
+                </p>
+            </KnapsackFormula>
+            <a href="./">"Try it on an instance →"</a>
+        </main>
+    }
+}
+
+/// [`Theory`] as a lazy route: a split build (`cargo leptos --split`) loads
+/// its code only when the page is first visited.
+pub struct TheoryRoute;
+
+#[lazy_route]
+impl LazyRoute for TheoryRoute {
+    fn data() -> Self {
+        TheoryRoute
+    }
+
+    fn view(_this: Self) -> AnyView {
+        view! { <Theory /> }.into_any()
+    }
+}
@@ -0,0 +1,635 @@
+use std::str::FromStr;
+
+use crate::analysis::gcd;
+use crate::format::Locale;
+use crate::problem::{Problem, Units, WeightUnit};
+use crate::solver::WideProblem;
+
+// ─── Numbers ─────────────────────────────────────────────────────────────────
+
+/// Parse one whole number written with the digit grouping of `locale`
+/// (`12,500` in English, `12.500` in German, `12 500` in French).
+pub fn parse_number(token: &str, locale: Locale) -> Result<usize, String> {
+    parse_whole(token, locale)
+}
+
+/// [`parse_number`] for benefits on the wide (u128) backend.
+pub fn parse_wide_number(token: &str, locale: Locale) -> Result<u128, String> {
+    parse_whole(token, locale)
+}
+
+fn parse_whole<T: FromStr>(token: &str, locale: Locale) -> Result<T, String> {
+    let t = token.trim();
+    if t.is_empty() {
+        return Err("empty entry (check for a trailing or doubled separator)".into());
+    }
+
+    let group = locale.group_separator();
+    let decimal = locale.decimal_separator();
+    let is_group = |c: char| c == group || (locale == Locale::Fr && c.is_whitespace());
+
+    // A decimal separator followed by exactly three digits is the classic
+    // "1.000 vs 1,000" mix-up between locales – call it out explicitly.
+    if let Some((int, frac)) = t.split_once(decimal) {
+        if frac.len() == 3 && frac.chars().all(|c| c.is_ascii_digit()) {
+            return Err(format!(
+                "'{t}' is ambiguous: '{decimal}' is the decimal separator in {}, so this reads as {int} \
+                 with a fraction rather than {int}{frac}; change the parsing locale or drop the separator",
+                locale.name()
+            ));
+        }
+        return Err(format!("'{t}' has a decimal part; only whole numbers are allowed"));
+    }
+
+    if t.chars().any(|c| !c.is_ascii_digit() && !is_group(c)) {
+        return Err(format!("'{t}' is not a valid positive integer"));
+    }
+
+    let groups: Vec<&str> = t.split(is_group).collect();
+    if groups.len() > 1
+        && (groups[0].is_empty() || groups[0].len() > 3 || groups[1..].iter().any(|g| g.len() != 3))
+    {
+        return Err(format!(
+            "'{t}' is ambiguous: '{group}' groups thousands in {}, so every group after the first needs exactly 3 digits",
+            locale.name()
+        ));
+    }
+
+    groups
+        .concat()
+        .parse::<T>()
+        .map_err(|_| format!("'{t}' is too large"))
+}
+
+/// Decimal places accepted in capacity and weights. Every place multiplies
+/// the scaled capacity, and so the table's width, by ten.
+pub const MAX_DECIMALS: usize = 4;
+
+/// A non-negative decimal such as `2.25` as a reduced fraction `(9, 4)`;
+/// whole numbers come back over 1. Used for capacity and weights, which
+/// are then scaled to integers.
+pub fn parse_decimal(token: &str, locale: Locale) -> Result<(usize, usize), String> {
+    let t = token.trim();
+    let Some((int, frac)) = t.split_once(locale.decimal_separator()) else {
+        return parse_number(t, locale).map(|v| (v, 1));
+    };
+    if frac.is_empty() || frac.len() > MAX_DECIMALS || !frac.chars().all(|c| c.is_ascii_digit()) {
+        return Err(format!("'{t}' is not a valid decimal (at most {MAX_DECIMALS} decimal places)"));
+    }
+    let int = if int.trim().is_empty() { 0 } else { parse_number(int, locale)? };
+    let den = 10usize.pow(frac.len() as u32);
+    let num = int
+        .checked_mul(den)
+        .and_then(|v| v.checked_add(frac.parse::<usize>().unwrap_or(0)))
+        .ok_or_else(|| format!("'{t}' is too large"))?;
+    let g = gcd(num, den).max(1);
+    Ok((num / g, den / g))
+}
+
+/// Inverse of [`parse_decimal`]: `(9, 4)` is `2.25`, or `2,25` where the
+/// comma is the decimal separator. Denominators other than powers of ten
+/// (and their divisors) are rounded to [`MAX_DECIMALS`] places.
+pub fn format_decimal((num, den): (usize, usize), locale: Locale) -> String {
+    if den <= 1 {
+        return num.to_string();
+    }
+    let places = (1..=MAX_DECIMALS as u32).find(|&k| 10usize.pow(k) % den == 0).unwrap_or(MAX_DECIMALS as u32);
+    let unit = 10usize.pow(places);
+    let scaled = num.saturating_mul(unit) / den;
+    let frac = format!("{:0width$}", scaled % unit, width = places as usize);
+    match frac.trim_end_matches('0') {
+        "" => (scaled / unit).to_string(),
+        frac => format!("{}{}{frac}", scaled / unit, locale.decimal_separator()),
+    }
+}
+
+/// The factor that turns a decimal capacity and weights into whole numbers,
+/// i.e. the least common denominator (`1.5, 2.25` → 4). `None` when they
+/// are already whole or do not parse.
+pub fn decimal_scale(cap_str: &str, w_str: &str, locale: Locale) -> Option<usize> {
+    let capacity = parse_decimal(cap_str, locale).ok()?;
+    let weights = parse_list_with(w_str, locale, parse_decimal).ok()?;
+    let lcd = std::iter::once(capacity)
+        .chain(weights)
+        .try_fold(1usize, |l, (_, den)| (l / gcd(l, den)).checked_mul(den))?;
+    (lcd > 1).then_some(lcd)
+}
+
+// ─── Weight units ────────────────────────────────────────────────────────────
+
+/// Decimal places kept when an entry is converted to another unit. More
+/// would make the decimal scaling (and so the table) needlessly wide.
+const CONVERTED_DECIMALS: i32 = 2;
+
+/// Rewrite one entry such as `500g` or `1.5 lb` as a plain number in `base`;
+/// entries without a unit are already in `base` and come back unchanged.
+pub fn convert_weight(token: &str, base: Option<WeightUnit>, locale: Locale) -> Result<String, String> {
+    let t = token.trim();
+    let number = t.trim_end_matches(|c: char| c.is_alphabetic()).trim_end();
+    let symbol = t[number.len()..].trim();
+    if symbol.is_empty() {
+        return Ok(t.to_string());
+    }
+    let unit =
+        WeightUnit::from_symbol(symbol).ok_or_else(|| format!("'{symbol}' is not a known weight unit (g, kg, lb)"))?;
+    let Some(base) = base else {
+        return Err(format!("'{t}' has a unit; choose a weight unit to convert it to"));
+    };
+    let (num, den) = parse_decimal(number, locale)?;
+    let factor = 10f64.powi(CONVERTED_DECIMALS);
+    let value = (unit.convert(num as f64 / den as f64, base) * factor).round() / factor;
+    let text = format!("{value:.prec$}", prec = CONVERTED_DECIMALS as usize);
+    let text = if text.contains('.') { text.trim_end_matches('0').trim_end_matches('.') } else { &text };
+    Ok(text.replace('.', &locale.decimal_separator().to_string()))
+}
+
+/// [`convert_weight`] over a weight list. The result is joined with `;`
+/// when the locale writes decimals with a comma.
+pub fn convert_weight_list(s: &str, base: Option<WeightUnit>, locale: Locale) -> Result<String, String> {
+    let converted = parse_list_with(s, locale, |t, _| Ok(t.to_string()))?
+        .iter()
+        .map(|t| convert_weight(t, base, locale))
+        .collect::<Result<Vec<_>, _>>()?;
+    let sep = if locale.decimal_separator() == ',' { "; " } else { ", " };
+    Ok(converted.join(sep))
+}
+
+// ─── Lists ───────────────────────────────────────────────────────────────────
+
+/// Items are separated by `;` when one is present, otherwise by `,`.
+/// The semicolon form lets English users write `1,000; 2,500`.
+pub fn parse_list(s: &str, locale: Locale) -> Result<Vec<usize>, String> {
+    parse_list_with(s, locale, parse_number)
+}
+
+pub fn parse_wide_list(s: &str, locale: Locale) -> Result<Vec<u128>, String> {
+    parse_list_with(s, locale, parse_wide_number)
+}
+
+fn parse_list_with<T>(s: &str, locale: Locale, parse: fn(&str, Locale) -> Result<T, String>) -> Result<Vec<T>, String> {
+    let sep = if s.contains(';') { ';' } else { ',' };
+    let pieces: Vec<&str> = s.split(sep).collect();
+    pieces
+        .iter()
+        .enumerate()
+        .map(|(k, t)| {
+            // "1,500": a comma right between a digit and exactly three digits
+            // may be grouping or a decimal point rather than two items
+            if sep == ','
+                && k > 0
+                && t.len() == 3
+                && t.chars().all(|c| c.is_ascii_digit())
+                && pieces[k - 1].ends_with(|c: char| c.is_ascii_digit())
+            {
+                let before = pieces[k - 1].trim();
+                let role = if locale.group_separator() == ',' { "thousands" } else { "decimal" };
+                return Err(format!(
+                    "'{before},{t}' is ambiguous: is ',' a {role} separator here? Write '{before}, {t}' for two \
+                     items, or separate the items with ';' to read it as one number"
+                ));
+            }
+            let t = t.trim();
+            if sep == ',' && locale.group_separator() == ',' && t.len() == 3 && t.starts_with('0') {
+                return Err(format!(
+                    "'{t}' looks like a thousands group; separate items with ';' to use ',' for thousands"
+                ));
+            }
+            parse(t, locale)
+        })
+        .collect()
+}
+
+/// Pasted text such as `[2, 3, 4]`, one number per line or `2 3 4`
+/// rewritten as a list [`parse_list`] reads, or `None` when it already is
+/// one. Commas inside entries are kept as digit grouping or decimals, and
+/// the entries are then separated by `;`.
+pub fn normalize_pasted(text: &str, locale: Locale) -> Option<String> {
+    let stripped: String = text.chars().filter(|c| !"[](){}'\"".contains(*c)).collect();
+    let tokens: Vec<&str> = if stripped.contains(['\n', ';']) {
+        stripped.split(['\n', '\r', ';']).collect()
+    } else if stripped.contains(',') {
+        stripped.split(',').collect()
+    } else if locale != Locale::Fr {
+        // French groups thousands with spaces, so they cannot separate entries
+        stripped.split_whitespace().collect()
+    } else {
+        vec![stripped.as_str()]
+    };
+    let tokens: Vec<&str> = tokens.into_iter().map(str::trim).filter(|t| !t.is_empty()).collect();
+    let sep = if tokens.iter().any(|t| t.contains(',')) { "; " } else { ", " };
+    let normalized = tokens.join(sep);
+    (normalized != text.trim()).then_some(normalized)
+}
+
+/// `value` with the text between `start` and `end` replaced by `insert`.
+/// The offsets count UTF-16 code units, as an input's selection does;
+/// ones past the end or inside a character are moved to the next boundary.
+pub fn splice_utf16(value: &str, start: usize, end: usize, insert: &str) -> String {
+    let byte = |offset: usize| {
+        let mut units = 0;
+        value
+            .char_indices()
+            .find(|&(_, c)| {
+                let reached = units >= offset;
+                units += c.len_utf16();
+                reached
+            })
+            .map_or(value.len(), |(i, _)| i)
+    };
+    let (start, end) = (byte(start), byte(end.max(start)));
+    format!("{}{insert}{}", &value[..start], &value[end..])
+}
+
+/// Inverse of [`parse_list`] for plain numbers: `2, 3, 4`.
+pub fn join_list(values: &[usize]) -> String {
+    values.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(", ")
+}
+
+pub fn parse_problem(
+    cap_str: &str,
+    w_str: &str,
+    b_str: &str,
+    units: Units,
+    locale: Locale,
+) -> Result<Problem, String> {
+    let (capacity, weights) = parse_shape(cap_str, w_str, locale)?;
+    let benefits = parse_list(b_str, locale).map_err(|e| format!("Benefits: {e}"))?;
+    check_counts(&weights, benefits.len())?;
+    // every table value is a sum of benefits, so the total must fit
+    if benefits.iter().try_fold(0usize, |sum, &b| sum.checked_add(b)).is_none() {
+        return Err(format!(
+            "Benefits add up to more than {}, the largest native value; use wide (u128) values.",
+            usize::MAX
+        ));
+    }
+    Ok(Problem { capacity, weights, benefits, units })
+}
+
+/// [`parse_problem`] with u128 benefits, for the wide backend.
+pub fn parse_wide_problem(
+    cap_str: &str,
+    w_str: &str,
+    b_str: &str,
+    units: Units,
+    locale: Locale,
+) -> Result<WideProblem, String> {
+    let (capacity, weights) = parse_shape(cap_str, w_str, locale)?;
+    let benefits = parse_wide_list(b_str, locale).map_err(|e| format!("Benefits: {e}"))?;
+    check_counts(&weights, benefits.len())?;
+    if benefits.iter().try_fold(0u128, |sum, &b| sum.checked_add(b)).is_none() {
+        return Err("Benefits add up to more than even wide (u128) values can hold.".into());
+    }
+    Ok(WideProblem { capacity, weights, benefits, units })
+}
+
+/// Capacity and weights, shared by both value backends. Decimals are
+/// scaled by [`decimal_scale`] so the DP sees whole numbers.
+fn parse_shape(cap_str: &str, w_str: &str, locale: Locale) -> Result<(usize, Vec<usize>), String> {
+    let capacity = match parse_decimal(cap_str, locale) {
+        Ok(v) if v.0 > 0 => v,
+        Err(e) if !cap_str.trim().is_empty() => return Err(format!("Capacity: {e}")),
+        _ => return Err("Capacity (m) must be a positive number.".into()),
+    };
+
+    let weights = match parse_list_with(w_str, locale, parse_decimal) {
+        Ok(v) if !v.is_empty() => v,
+        Err(e) => return Err(format!("Weights: {e}")),
+        _ => return Err("Enter at least one weight.".into()),
+    };
+
+    let scale = decimal_scale(cap_str, w_str, locale).unwrap_or(1);
+    let whole = |(num, den): (usize, usize)| {
+        num.checked_mul(scale / den).ok_or("Capacity and weights are too large once scaled to whole numbers.")
+    };
+    let capacity = whole(capacity)?;
+    let weights = weights.into_iter().map(whole).collect::<Result<_, _>>()?;
+    Ok((capacity, weights))
+}
+
+fn check_counts(weights: &[usize], benefits: usize) -> Result<(), String> {
+    if weights.len() != benefits {
+        return Err(format!(
+            "Number of weights ({}) must equal number of benefits ({benefits}).",
+            weights.len(),
+        ));
+    }
+    Ok(())
+}
+
+// ─── Live validation ─────────────────────────────────────────────────────────
+
+/// Per-field problems with the current form contents, shown inline while typing.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FieldErrors {
+    pub capacity: Option<String>,
+    pub weights: Option<String>,
+    pub benefits: Option<String>,
+}
+
+impl FieldErrors {
+    pub fn is_empty(&self) -> bool {
+        self.capacity.is_none() && self.weights.is_none() && self.benefits.is_none()
+    }
+}
+
+pub fn validate_fields(cap_str: &str, w_str: &str, b_str: &str, locale: Locale) -> FieldErrors {
+    let capacity = match parse_decimal(cap_str, locale) {
+        Ok((0, _)) => Some("must be positive".to_string()),
+        Ok(_) => None,
+        Err(_) if cap_str.trim().is_empty() => Some("required".to_string()),
+        Err(e) => Some(e),
+    };
+
+    let weights = parse_list_with(w_str, locale, parse_decimal);
+    // any benefit the wide backend can hold is fine here; whether native
+    // values suffice is settled when solving
+    let benefits = parse_wide_list(b_str, locale);
+
+    let benefits_err = match (&weights, &benefits) {
+        (_, Err(e)) => Some(e.clone()),
+        (Ok(ws), Ok(bs)) if ws.len() != bs.len() => Some(format!(
+            "{} benefits for {} weights",
+            bs.len(),
+            ws.len()
+        )),
+        _ => None,
+    };
+
+    FieldErrors {
+        capacity,
+        weights: weights.err(),
+        benefits: benefits_err,
+    }
+}
+
+// ─── Quick fixes ─────────────────────────────────────────────────────────────
+
+/// Form field a [`QuickFix`] rewrites.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Field {
+    Capacity,
+    Weights,
+    Benefits,
+}
+
+/// A one-click repair for a field that does not parse: the button label and
+/// the field's new contents.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QuickFix {
+    pub label: String,
+    pub field: Field,
+    pub value: String,
+}
+
+/// Repairs on offer for the current form contents, at most a few per field;
+/// empty when every field parses and the counts match.
+pub fn quick_fixes(cap_str: &str, w_str: &str, b_str: &str, locale: Locale) -> Vec<QuickFix> {
+    let mut fixes = Vec::new();
+    for (field, text) in [(Field::Capacity, cap_str), (Field::Weights, w_str), (Field::Benefits, b_str)] {
+        if field != Field::Capacity {
+            fixes.extend(drop_empty_entries(field, text));
+        }
+    }
+
+    // benefits must be whole; capacity and weights already accept decimals
+    let decimal = locale.decimal_separator();
+    let tokens = list_tokens(b_str);
+    if let Some(first) = tokens.iter().find(|t| t.contains(decimal) && parse_decimal(t, locale).is_ok_and(|(_, den)| den > 1)) {
+        let to_whole = |(num, den): (usize, usize), round: bool| if round { (2 * num + den) / (2 * den) } else { num / den };
+        let whole = |round: bool| {
+            let values: Vec<String> = tokens
+                .iter()
+                .filter(|t| !t.is_empty())
+                .map(|t| match parse_decimal(t, locale) {
+                    Ok(v) if v.1 > 1 => to_whole(v, round).to_string(),
+                    _ => t.to_string(),
+                })
+                .collect();
+            values.join(&format!("{} ", list_separator(b_str)))
+        };
+        let value = parse_decimal(first, locale).unwrap_or((0, 1));
+        fixes.push(QuickFix {
+            label: format!("Convert '{first}' to '{}'", to_whole(value, false)),
+            field: Field::Benefits,
+            value: whole(false),
+        });
+        if to_whole(value, true) != to_whole(value, false) {
+            fixes.push(QuickFix {
+                label: format!("Round '{first}' to '{}'", to_whole(value, true)),
+                field: Field::Benefits,
+                value: whole(true),
+            });
+        }
+    }
+
+    // counts only compare once both lists parse
+    let weights = parse_list_with(w_str, locale, parse_decimal);
+    let benefits = parse_wide_list(b_str, locale);
+    if let (Ok(ws), Ok(bs)) = (weights, benefits) {
+        let (n, m) = (ws.len(), bs.len());
+        let keep = |text: &str, k: usize| {
+            let sep = list_separator(text);
+            let kept: Vec<&str> = list_tokens(text).into_iter().filter(|t| !t.is_empty()).take(k).collect();
+            kept.join(&format!("{sep} "))
+        };
+        if m < n {
+            let sep = list_separator(b_str);
+            let pad = std::iter::repeat_n("0", n - m).collect::<Vec<_>>().join(&format!("{sep} "));
+            let value = if b_str.trim().is_empty() { pad } else { format!("{}{sep} {pad}", keep(b_str, m)) };
+            fixes.push(QuickFix { label: format!("Pad benefits to match {n} weights"), field: Field::Benefits, value });
+            fixes.push(QuickFix {
+                label: format!("Keep only the first {m} weights"),
+                field: Field::Weights,
+                value: keep(w_str, m),
+            });
+        } else if m > n {
+            fixes.push(QuickFix {
+                label: format!("Keep only the first {n} benefits"),
+                field: Field::Benefits,
+                value: keep(b_str, n),
+            });
+        }
+    }
+    fixes
+}
+
+fn list_separator(s: &str) -> char {
+    if s.contains(';') { ';' } else { ',' }
+}
+
+fn list_tokens(s: &str) -> Vec<&str> {
+    if s.trim().is_empty() {
+        return Vec::new();
+    }
+    s.split(list_separator(s)).map(str::trim).collect()
+}
+
+/// "Remove trailing ','" for `2, 3,`, or a general clean-up for doubled
+/// separators, when dropping the empty entries leaves something.
+fn drop_empty_entries(field: Field, text: &str) -> Option<QuickFix> {
+    let tokens = list_tokens(text);
+    let empty = tokens.iter().filter(|t| t.is_empty()).count();
+    if empty == 0 || empty == tokens.len() {
+        return None;
+    }
+    let sep = list_separator(text);
+    let trailing = empty == 1 && tokens.last().is_some_and(|t| t.is_empty());
+    Some(QuickFix {
+        label: if trailing { format!("Remove trailing '{sep}'") } else { "Remove empty entries".to_string() },
+        field,
+        value: tokens.into_iter().filter(|t| !t.is_empty()).collect::<Vec<_>>().join(&format!("{sep} ")),
+    })
+}
+
+// ─── Dependencies ────────────────────────────────────────────────────────────
+
+/// Prerequisites written as `B:A` pairs, read "item B requires item A",
+/// e.g. `3:1; 4:3`. Each item may require at most one other, so the
+/// dependencies form a forest. Returns the 0-based prerequisite of each item.
+pub fn parse_dependencies(s: &str, n: usize) -> Result<Vec<Option<usize>>, String> {
+    let item = |t: &str| match t.trim().parse::<usize>() {
+        Ok(i) if (1..=n).contains(&i) => Ok(i - 1),
+        _ => Err(format!("'{}' is not an item number between 1 and {n}", t.trim())),
+    };
+
+    let mut parents = vec![None; n];
+    for pair in s.split([',', ';']).map(str::trim).filter(|t| !t.is_empty()) {
+        let (b, a) = pair
+            .split_once(':')
+            .ok_or_else(|| format!("'{pair}' should read B:A (item B requires item A)"))?;
+        let (b, a) = (item(b)?, item(a)?);
+        if a == b {
+            return Err(format!("item {} cannot require itself", b + 1));
+        }
+        match parents[b] {
+            Some(prev) if prev != a => {
+                return Err(format!(
+                    "item {} already requires item {}; each item may have one prerequisite",
+                    b + 1,
+                    prev + 1
+                ));
+            }
+            _ => parents[b] = Some(a),
+        }
+    }
+
+    // following prerequisites from any item must reach one without any
+    for start in 0..n {
+        let mut at = start;
+        for _ in 0..=n {
+            match parents[at] {
+                Some(p) => at = p,
+                None => break,
+            }
+        }
+        if parents[at].is_some() {
+            return Err(format!("the dependencies of item {} form a cycle", start + 1));
+        }
+    }
+    Ok(parents)
+}
+
+// ─── Cell search ─────────────────────────────────────────────────────────────
+
+/// What the table search box is looking for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CellQuery {
+    /// `2, 5` or `(2, 5)`: the cell of item row 2 at capacity 5.
+    Cell(usize, usize),
+    /// `=9`: the first revealed cell whose value is 9.
+    Value(usize),
+}
+
+pub fn parse_cell_query(s: &str) -> Result<CellQuery, String> {
+    let s = s.trim();
+    if let Some(v) = s.strip_prefix('=') {
+        return v
+            .trim()
+            .parse::<usize>()
+            .map(CellQuery::Value)
+            .map_err(|_| format!("'{}' is not a value to look for", v.trim()));
+    }
+
+    let inner = s.trim_start_matches(['(', '[']).trim_end_matches([')', ']']);
+    let parts: Vec<&str> = inner
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|t| !t.is_empty())
+        .collect();
+    match parts.as_slice() {
+        [i, w] => match (i.parse::<usize>(), w.parse::<usize>()) {
+            (Ok(i), Ok(w)) => Ok(CellQuery::Cell(i, w)),
+            _ => Err(format!("'{s}' is not a cell; use item, capacity")),
+        },
+        _ => Err("Type 'item, capacity' (e.g. 2, 5) or '=value' (e.g. =9).".into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn english_lists_group_with_commas_between_semicolons() {
+        assert_eq!(parse_list("2, 3,4", Locale::En), Ok(vec![2, 3, 4]));
+        assert_eq!(parse_list("12,500; 3", Locale::En), Ok(vec![12_500, 3]));
+        assert_eq!(parse_list("1, 500", Locale::En), Ok(vec![1, 500]));
+        assert!(parse_list("1.5", Locale::En).is_err());
+    }
+
+    #[test]
+    fn german_lists_group_with_dots() {
+        assert_eq!(parse_list("12.500; 3", Locale::De), Ok(vec![12_500, 3]));
+        assert_eq!(parse_list("2, 3,4", Locale::De), Ok(vec![2, 3, 4]));
+        assert!(parse_list("1,5; 2", Locale::De).is_err());
+    }
+
+    #[test]
+    fn french_lists_group_with_spaces() {
+        assert_eq!(parse_list("12 500; 3", Locale::Fr), Ok(vec![12_500, 3]));
+        assert_eq!(parse_list("12\u{202F}500", Locale::Fr), Ok(vec![12_500]));
+        assert_eq!(parse_list("2,3", Locale::Fr), Ok(vec![2, 3]));
+    }
+
+    #[test]
+    fn a_comma_before_three_digits_is_flagged_in_every_locale() {
+        for (locale, role) in [(Locale::En, "thousands"), (Locale::De, "decimal"), (Locale::Fr, "decimal")] {
+            for list in ["1,000", "1,500", "7, 100,200"] {
+                let err = parse_list(list, locale).unwrap_err();
+                assert!(err.contains(&format!("a {role} separator")), "{list} in {locale:?}: {err}");
+            }
+        }
+        // with ';' between items the comma can only be part of a number
+        assert_eq!(parse_list("1,500; 2", Locale::En), Ok(vec![1_500, 2]));
+    }
+
+    #[test]
+    fn wide_lists_read_the_same_way() {
+        assert_eq!(parse_wide_list("1; 12,500", Locale::En), Ok(vec![1, 12_500]));
+        assert!(parse_list_with("1,000", Locale::En, parse_wide_number).is_err());
+    }
+
+    #[test]
+    fn pastes_replace_the_selection_counted_in_utf16() {
+        assert_eq!(splice_utf16("1, 2", 1, 1, "0"), "10, 2");
+        // '𝟙' is two UTF-16 units and '\u{202F}' one, so byte and char offsets differ
+        assert_eq!(splice_utf16("𝟙\u{202F}2; 3", 3, 4, "5"), "𝟙\u{202F}5; 3");
+        assert_eq!(splice_utf16("ab", 9, 9, "c"), "abc");
+    }
+
+    #[test]
+    fn decimals_format_back_in_their_locale() {
+        for (text, locale) in [("2.25", Locale::En), ("2,25", Locale::De), ("0,5", Locale::Fr), ("7", Locale::De)] {
+            assert_eq!(format_decimal(parse_decimal(text, locale).unwrap(), locale), text);
+        }
+    }
+
+    #[test]
+    fn decimals_are_capped_so_scaling_stays_bounded() {
+        assert_eq!(parse_decimal("0.0001", Locale::En), Ok((1, 10_000)));
+        assert!(parse_decimal("0.000000001", Locale::En).unwrap_err().contains("at most 4 decimal places"));
+        let p = parse_problem("10", "0.0001", "1", Units::default(), Locale::En).unwrap();
+        assert_eq!(p.capacity, 100_000);
+        let err = crate::settings::Settings::default().check_capacity(p.capacity, Some(10_000)).unwrap_err();
+        assert!(err.contains("scaling ×10000 makes the capacity 100000"), "{err}");
+    }
+}
@@ -0,0 +1,48 @@
+use crate::problem::Problem;
+
+/// Query parameter a share link carries its playlist in.
+pub const PLAYLIST_PARAM: &str = "playlist";
+/// Most instances one link may hold.
+pub const MAX_INSTANCES: usize = 50;
+
+/// A set of instances as one query value: `capacity:weights:benefits` per
+/// instance, lists comma-separated, instances separated by `;`, e.g.
+/// `10:2,3,4:3,4,5;7:1,2:1,3`. Only digits and `,:;` appear, so the value
+/// needs no escaping. Units are not carried.
+pub fn encode(problems: &[Problem]) -> String {
+    let list = |values: &[usize]| values.iter().map(usize::to_string).collect::<Vec<_>>().join(",");
+    problems
+        .iter()
+        .map(|p| format!("{}:{}:{}", p.capacity, list(&p.weights), list(&p.benefits)))
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+/// Inverse of [`encode`].
+pub fn decode(text: &str) -> Result<Vec<Problem>, String> {
+    let entries: Vec<&str> = text.split(';').map(str::trim).filter(|e| !e.is_empty()).collect();
+    if entries.is_empty() {
+        return Err("The playlist is empty.".into());
+    }
+    if entries.len() > MAX_INSTANCES {
+        return Err(format!("A playlist holds at most {MAX_INSTANCES} instances, not {}.", entries.len()));
+    }
+    entries
+        .iter()
+        .enumerate()
+        .map(|(k, entry)| decode_one(entry).map_err(|e| format!("Playlist instance {}: {e}", k + 1)))
+        .collect()
+}
+
+fn decode_one(entry: &str) -> Result<Problem, String> {
+    let [capacity, weights, benefits] = entry.split(':').collect::<Vec<_>>()[..] else {
+        return Err("expected capacity:weights:benefits.".into());
+    };
+    let number = |s: &str| s.trim().parse::<usize>().map_err(|_| format!("'{s}' is not a whole number."));
+    let list = |s: &str| s.split(',').map(number).collect::<Result<Vec<_>, _>>();
+    let p = Problem { capacity: number(capacity)?, weights: list(weights)?, benefits: list(benefits)?, ..Default::default() };
+    if p.weights.len() != p.benefits.len() {
+        return Err(format!("{} weights but {} benefits.", p.weights.len(), p.benefits.len()));
+    }
+    Ok(p)
+}
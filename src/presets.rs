@@ -0,0 +1,79 @@
+use crate::parse::join_list;
+use crate::problem::{Problem, Unit, Units};
+
+/// A themed example instance that can be loaded into the form.
+pub struct Preset {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub capacity: usize,
+    pub weights: &'static [usize],
+    pub benefits: &'static [usize],
+    units: fn() -> Units,
+}
+
+impl Preset {
+    pub fn units(&self) -> Units {
+        (self.units)()
+    }
+
+    pub fn weights_input(&self) -> String {
+        join_list(self.weights)
+    }
+
+    pub fn benefits_input(&self) -> String {
+        join_list(self.benefits)
+    }
+
+    /// URL-safe form of the name, e.g. `thiefs-loot`.
+    pub fn slug(&self) -> String {
+        let mut slug = String::new();
+        for c in self.name.chars() {
+            if c.is_ascii_alphanumeric() {
+                slug.push(c.to_ascii_lowercase());
+            } else if c == ' ' && !slug.ends_with('-') {
+                slug.push('-');
+            }
+        }
+        slug
+    }
+
+    pub fn problem(&self) -> Problem {
+        Problem {
+            capacity: self.capacity,
+            weights: self.weights.to_vec(),
+            benefits: self.benefits.to_vec(),
+            units: self.units(),
+        }
+    }
+}
+
+pub fn find_preset(slug: &str) -> Option<&'static Preset> {
+    PRESETS.iter().find(|p| p.slug() == slug)
+}
+
+pub const PRESETS: &[Preset] = &[
+    Preset {
+        name: "Hiking trip",
+        description: "Pack gear for a day hike: weights in kg, value in $.",
+        capacity: 10,
+        weights: &[4, 3, 2, 1, 5],
+        benefits: &[40, 25, 15, 10, 35],
+        units: || Units { weight: Some(Unit::suffix("kg")), benefit: Some(Unit::prefix("$")) },
+    },
+    Preset {
+        name: "Cargo loading",
+        description: "Fill a container: volumes in m³, freight revenue in €.",
+        capacity: 20,
+        weights: &[8, 6, 5, 4, 3, 7],
+        benefits: &[1200, 900, 700, 600, 450, 1000],
+        units: || Units { weight: Some(Unit::suffix("m³")), benefit: Some(Unit::suffix("€")) },
+    },
+    Preset {
+        name: "Thief's loot",
+        description: "A burglar's sack holds 8 kg: which valuables are worth it?",
+        capacity: 8,
+        weights: &[1, 3, 4, 5],
+        benefits: &[1500, 2000, 3000, 4500],
+        units: || Units { weight: Some(Unit::suffix("kg")), benefit: Some(Unit::prefix("$")) },
+    },
+];
@@ -0,0 +1,182 @@
+use serde::{Deserialize, Serialize};
+
+use crate::format::NumberFormat;
+
+// ─── Units ───────────────────────────────────────────────────────────────────
+
+/// Where a unit symbol is written relative to the number it labels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnitPosition {
+    /// `$12`
+    Prefix,
+    /// `12 kg`
+    Suffix,
+}
+
+/// A unit label such as `kg`, `m³` or `$`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Unit {
+    pub symbol: String,
+    pub position: UnitPosition,
+}
+
+impl Unit {
+    pub fn prefix(symbol: &str) -> Self {
+        Self { symbol: symbol.into(), position: UnitPosition::Prefix }
+    }
+
+    pub fn suffix(symbol: &str) -> Self {
+        Self { symbol: symbol.into(), position: UnitPosition::Suffix }
+    }
+
+    /// Attach the unit to an already formatted number.
+    pub fn apply(&self, value: &str) -> String {
+        match self.position {
+            UnitPosition::Prefix => format!("{}{}", self.symbol, value),
+            UnitPosition::Suffix => format!("{} {}", value, self.symbol),
+        }
+    }
+}
+
+/// Optional unit labels for the weights and the benefits of a problem.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Units {
+    pub weight: Option<Unit>,
+    pub benefit: Option<Unit>,
+}
+
+impl Units {
+    pub fn weight(&self, value: usize) -> String {
+        Self::label(self.weight.as_ref(), value)
+    }
+
+    /// Benefit with its unit, formatted according to `fmt` (e.g. `$12.5k`).
+    pub fn benefit(&self, value: usize, fmt: &NumberFormat) -> String {
+        self.wide_benefit(value as u128, fmt)
+    }
+
+    pub fn wide_benefit(&self, value: u128, fmt: &NumberFormat) -> String {
+        let text = fmt.format_wide(value);
+        match &self.benefit {
+            Some(u) => u.apply(&text),
+            None => text,
+        }
+    }
+
+    /// Short symbol for column headers, e.g. `kg`; empty when unitless.
+    pub fn weight_symbol(&self) -> &str {
+        self.weight.as_ref().map(|u| u.symbol.as_str()).unwrap_or("")
+    }
+
+    fn label(unit: Option<&Unit>, value: usize) -> String {
+        match unit {
+            Some(u) => u.apply(&value.to_string()),
+            None => value.to_string(),
+        }
+    }
+}
+
+/// Mass units the form converts between. Entries written with a unit
+/// (`500g`, `1.5 lb`) are converted to the weight unit of the problem.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WeightUnit {
+    Gram,
+    Kilogram,
+    Pound,
+}
+
+impl WeightUnit {
+    pub const ALL: [WeightUnit; 3] = [WeightUnit::Gram, WeightUnit::Kilogram, WeightUnit::Pound];
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            WeightUnit::Gram => "g",
+            WeightUnit::Kilogram => "kg",
+            WeightUnit::Pound => "lb",
+        }
+    }
+
+    /// Accepts the symbol in any case, plus the plural `lbs`.
+    pub fn from_symbol(symbol: &str) -> Option<Self> {
+        match symbol.trim().to_ascii_lowercase().as_str() {
+            "g" => Some(WeightUnit::Gram),
+            "kg" => Some(WeightUnit::Kilogram),
+            "lb" | "lbs" => Some(WeightUnit::Pound),
+            _ => None,
+        }
+    }
+
+    fn grams(self) -> f64 {
+        match self {
+            WeightUnit::Gram => 1.0,
+            WeightUnit::Kilogram => 1000.0,
+            WeightUnit::Pound => 453.592_37,
+        }
+    }
+
+    /// `value` expressed in `to`.
+    pub fn convert(self, value: f64, to: WeightUnit) -> f64 {
+        value * self.grams() / to.grams()
+    }
+}
+
+// ─── Problem ─────────────────────────────────────────────────────────────────
+
+/// A 0/1 knapsack instance: `weights[i]` / `benefits[i]` describe item `i + 1`.
+/// Serialized as [`schema::Problem`](crate::schema::Problem).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "crate::schema::Problem", from = "crate::schema::Problem")]
+pub struct Problem {
+    pub capacity: usize,
+    pub weights: Vec<usize>,
+    pub benefits: Vec<usize>,
+    pub units: Units,
+}
+
+impl Problem {
+    pub fn len(&self) -> usize {
+        self.weights.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.weights.is_empty()
+    }
+
+    /// Check what the DP relies on in an instance that was read rather than
+    /// parsed from the form: one benefit per weight, a benefit total that
+    /// fits in `usize`, and a cell count that does too.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.weights.len() != self.benefits.len() {
+            return Err(format!(
+                "Number of weights ({}) must equal number of benefits ({}).",
+                self.weights.len(),
+                self.benefits.len()
+            ));
+        }
+        if self.benefits.iter().try_fold(0usize, |sum, &b| sum.checked_add(b)).is_none() {
+            return Err(format!("Benefits add up to more than {}, the largest native value.", usize::MAX));
+        }
+        match self.table_cells() {
+            Some(_) => Ok(()),
+            None => Err("This instance is too large to tabulate.".into()),
+        }
+    }
+
+    /// `(n + 1) × (capacity + 1)`, the size of the DP table; `None` when
+    /// that overflows.
+    pub fn table_cells(&self) -> Option<usize> {
+        (self.len() + 1).checked_mul(self.capacity.checked_add(1)?)
+    }
+
+    /// The same instance restricted to its first `n` items.
+    pub fn truncated(&self, n: usize) -> Problem {
+        let n = n.min(self.len());
+        Problem {
+            capacity: self.capacity,
+            weights: self.weights[..n].to_vec(),
+            benefits: self.benefits[..n].to_vec(),
+            units: self.units.clone(),
+        }
+    }
+}
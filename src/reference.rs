@@ -0,0 +1,133 @@
+//! Slow but obviously correct solvers, used to cross-check the DP.
+
+use crate::problem::Problem;
+
+/// Up to this many items every subset is enumerated directly.
+pub const BRUTE_FORCE_MAX_ITEMS: usize = 20;
+/// Up to this many items the two halves are enumerated and joined.
+pub const MEET_IN_THE_MIDDLE_MAX_ITEMS: usize = 32;
+
+/// Outcome of comparing a solver's optimum with the reference.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CrossCheck {
+    Agrees,
+    Disagrees { expected: u64 },
+    /// Too many items for the reference to run quickly.
+    Skipped,
+}
+
+/// Compare `best` with the reference optimum for `p`, picking brute force or
+/// meet-in-the-middle by size.
+pub fn cross_check(p: &Problem, best: usize) -> CrossCheck {
+    let expected = if p.len() <= BRUTE_FORCE_MAX_ITEMS {
+        brute_force(p)
+    } else if p.len() <= MEET_IN_THE_MIDDLE_MAX_ITEMS {
+        meet_in_the_middle(p)
+    } else {
+        return CrossCheck::Skipped;
+    };
+    if expected == best as u64 {
+        CrossCheck::Agrees
+    } else {
+        CrossCheck::Disagrees { expected }
+    }
+}
+
+/// Best benefit over all `2ⁿ` subsets.
+pub fn brute_force(p: &Problem) -> u64 {
+    subsets(&p.weights, &p.benefits)
+        .into_iter()
+        .filter(|&(w, _)| w <= p.capacity as u64)
+        .map(|(_, b)| b)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Best benefit by enumerating each half of the items and, for every subset
+/// of the first half, the best fitting subset of the second: `O(2^(n/2)·n)`.
+pub fn meet_in_the_middle(p: &Problem) -> u64 {
+    let mid = p.len() / 2;
+    let left = subsets(&p.weights[..mid], &p.benefits[..mid]);
+    let mut right = subsets(&p.weights[mid..], &p.benefits[mid..]);
+
+    // by weight, with each benefit raised to the best seen at or below it
+    right.sort_unstable();
+    let mut best_so_far = 0;
+    for (_, b) in right.iter_mut() {
+        best_so_far = best_so_far.max(*b);
+        *b = best_so_far;
+    }
+
+    let cap = p.capacity as u64;
+    left.into_iter()
+        .filter(|&(w, _)| w <= cap)
+        .filter_map(|(w, b)| {
+            let fits = right.partition_point(|&(rw, _)| rw <= cap - w);
+            fits.checked_sub(1).map(|k| b + right[k].1)
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+// ── helpers ──────────────────────────────────────────────────────────────────
+
+/// `(total weight, total benefit)` of every subset.
+fn subsets(weights: &[usize], benefits: &[usize]) -> Vec<(u64, u64)> {
+    let mut all = vec![(0u64, 0u64)];
+    for (&w, &b) in weights.iter().zip(benefits) {
+        let with: Vec<_> = all.iter().map(|&(sw, sb)| (sw + w as u64, sb + b as u64)).collect();
+        all.extend(with);
+    }
+    all
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::Rng;
+    use crate::solver::solve_headless;
+
+    fn random_problem(rng: &mut Rng, n: usize) -> Problem {
+        Problem {
+            capacity: rng.between(0, 40),
+            weights: (0..n).map(|_| rng.between(0, 15)).collect(),
+            benefits: (0..n).map(|_| rng.between(0, 30)).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn agrees_with_the_headless_solver() {
+        let mut rng = Rng::new(7);
+        for round in 0..300 {
+            let p = random_problem(&mut rng, round % (MEET_IN_THE_MIDDLE_MAX_ITEMS - 4));
+            let solution = solve_headless(&p);
+            assert_eq!(cross_check(&p, solution.best), CrossCheck::Agrees, "{p:?}");
+
+            let weight: usize = solution.chosen.iter().map(|&i| p.weights[i - 1]).sum();
+            let benefit: usize = solution.chosen.iter().map(|&i| p.benefits[i - 1]).sum();
+            assert!(weight <= p.capacity);
+            assert_eq!(benefit, solution.best);
+        }
+    }
+
+    #[test]
+    fn both_references_agree_where_both_run() {
+        let mut rng = Rng::new(11);
+        for n in 0..=BRUTE_FORCE_MAX_ITEMS.min(14) {
+            let p = random_problem(&mut rng, n);
+            assert_eq!(brute_force(&p), meet_in_the_middle(&p), "{p:?}");
+        }
+    }
+
+    #[test]
+    fn reports_a_wrong_optimum_and_skips_large_instances() {
+        let p = Problem { capacity: 5, weights: vec![2, 3, 4], benefits: vec![3, 4, 5], ..Default::default() };
+        assert_eq!(cross_check(&p, 7), CrossCheck::Agrees);
+        assert_eq!(cross_check(&p, 8), CrossCheck::Disagrees { expected: 7 });
+
+        let mut rng = Rng::new(3);
+        let large = random_problem(&mut rng, MEET_IN_THE_MIDDLE_MAX_ITEMS + 1);
+        assert_eq!(cross_check(&large, 0), CrossCheck::Skipped);
+    }
+}
@@ -0,0 +1,105 @@
+use crate::problem::Problem;
+
+// ─── Robust knapsack ─────────────────────────────────────────────────────────
+
+/// A packing judged by its worst case: up to `gamma` of the packed items
+/// lose their drop `d_i` (capped at the benefit), chosen adversarially.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RobustSolution {
+    /// 1-based item indices, ascending.
+    pub chosen: Vec<usize>,
+    /// Value when every benefit is as entered.
+    pub nominal: usize,
+    /// Value after the adversary's `gamma` drops.
+    pub worst_case: usize,
+}
+
+/// Worst-case value of `items` (1-based): the `gamma` largest drops among
+/// them are subtracted from their total benefit.
+pub fn worst_case_value(p: &Problem, drops: &[usize], gamma: usize, items: &[usize]) -> usize {
+    let mut lost: Vec<usize> = items.iter().map(|&i| drops[i - 1].min(p.benefits[i - 1])).collect();
+    lost.sort_unstable_by(|a, b| b.cmp(a));
+    let total: usize = items.iter().map(|&i| p.benefits[i - 1]).sum();
+    total - lost.iter().take(gamma).sum::<usize>()
+}
+
+/// Maximize the worst-case value (Bertsimas–Sim budget of uncertainty).
+/// With the items sorted by drop, largest first, the adversary always hits
+/// the first `gamma` packed ones, so a DP over (items, drops charged,
+/// capacity) finds the optimum: `O(n·Γ·m)` cells.
+pub fn solve_robust(p: &Problem, drops: &[usize], gamma: usize) -> RobustSolution {
+    let (n, m) = (p.len(), p.capacity);
+    let g_max = gamma.min(n);
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&a, &b| drops[b].min(p.benefits[b]).cmp(&drops[a].min(p.benefits[a])));
+
+    // t[j][g][c]: best worst case of the first j sorted items with g drops
+    // charged and weight ≤ c; fewer than g_max charged means every packed
+    // item was charged
+    let mut t = vec![vec![vec![None::<usize>; m + 1]; g_max + 1]; n + 1];
+    t[0][0] = vec![Some(0); m + 1];
+    for j in 1..=n {
+        let item = order[j - 1];
+        let (w, b) = (p.weights[item], p.benefits[item]);
+        let d = drops[item].min(b);
+        for g in 0..=g_max {
+            for c in 0..=m {
+                let mut v = t[j - 1][g][c];
+                if w <= c {
+                    if g > 0 {
+                        v = v.max(t[j - 1][g - 1][c - w].map(|prev| prev + b - d));
+                    }
+                    if g == g_max {
+                        v = v.max(t[j - 1][g][c - w].map(|prev| prev + b));
+                    }
+                }
+                t[j][g][c] = v;
+            }
+        }
+    }
+
+    let (mut g, worst_case) = (0..=g_max)
+        .filter_map(|g| t[n][g][m].map(|v| (g, v)))
+        .max_by_key(|&(_, v)| v)
+        .unwrap_or((0, 0));
+    let mut c = m;
+    let mut chosen = Vec::new();
+    for j in (1..=n).rev() {
+        let cur = t[j][g][c];
+        if cur == t[j - 1][g][c] {
+            continue;
+        }
+        let item = order[j - 1];
+        let (w, b) = (p.weights[item], p.benefits[item]);
+        let d = drops[item].min(b);
+        if g > 0 && t[j - 1][g - 1][c - w].map(|prev| prev + b - d) == cur {
+            g -= 1;
+        }
+        c -= w;
+        chosen.push(item + 1);
+    }
+    chosen.sort_unstable();
+    let nominal = chosen.iter().map(|&i| p.benefits[i - 1]).sum();
+    RobustSolution { chosen, nominal, worst_case }
+}
+
+/// One point of the price-of-robustness curve.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TradeOff {
+    pub gamma: usize,
+    pub robust: RobustSolution,
+    /// Worst case of the nominal optimum under the same `gamma`.
+    pub nominal_worst_case: usize,
+}
+
+/// [`solve_robust`] for every `gamma` from 0 to `n`, next to how the nominal
+/// optimum `nominal_items` fares under each.
+pub fn trade_off(p: &Problem, drops: &[usize], nominal_items: &[usize]) -> Vec<TradeOff> {
+    (0..=p.len())
+        .map(|gamma| TradeOff {
+            gamma,
+            robust: solve_robust(p, drops, gamma),
+            nominal_worst_case: worst_case_value(p, drops, gamma, nominal_items),
+        })
+        .collect()
+}
@@ -0,0 +1,121 @@
+//! The JSON shapes shared by `.knap` files, step logs and the CLI. Field
+//! names here are the public format: rename them only together with a bump
+//! of [`SCHEMA_VERSION`].
+
+use serde::{Deserialize, Serialize};
+
+use crate::problem::{self, Units};
+use crate::solver::{chosen_items, knapsack_table};
+
+/// Version of the layouts in this module, written into every `.knap` file.
+///
+/// - 1: problems as parallel `weights` / `benefits` lists
+/// - 2: problems as a list of `items`
+pub const SCHEMA_VERSION: u32 = 2;
+
+// ─── Problem ─────────────────────────────────────────────────────────────────
+
+/// One item that may go in the knapsack.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Item {
+    pub weight: usize,
+    pub benefit: usize,
+}
+
+/// An instance on the wire. Items are records, so a weight can never lose
+/// its benefit the way two separate lists can.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Problem {
+    /// Capacity of the knapsack, in weight units.
+    pub capacity: usize,
+    /// Items in row order: `items[0]` is row 1 of the table.
+    pub items: Vec<Item>,
+    /// Unit labels for display; both absent when unitless.
+    #[serde(default)]
+    pub units: Units,
+}
+
+impl From<problem::Problem> for Problem {
+    fn from(p: problem::Problem) -> Self {
+        let items = p.weights.iter().zip(&p.benefits).map(|(&weight, &benefit)| Item { weight, benefit }).collect();
+        Self { capacity: p.capacity, items, units: p.units }
+    }
+}
+
+impl From<Problem> for problem::Problem {
+    fn from(p: Problem) -> Self {
+        Self {
+            capacity: p.capacity,
+            weights: p.items.iter().map(|i| i.weight).collect(),
+            benefits: p.items.iter().map(|i| i.benefit).collect(),
+            units: p.units,
+        }
+    }
+}
+
+// ─── Table ───────────────────────────────────────────────────────────────────
+
+/// A finished DP table.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Table {
+    /// `values[i][w]`: the best benefit from the first `i` items within
+    /// capacity `w`. Rows `0..=n`, columns `0..=capacity`.
+    pub values: Vec<Vec<usize>>,
+}
+
+impl Table {
+    pub fn solve(p: &problem::Problem) -> Self {
+        Self { values: knapsack_table(p.capacity, &p.weights, &p.benefits) }
+    }
+
+    /// The bottom-right cell.
+    pub fn best(&self) -> usize {
+        self.values.last().and_then(|row| row.last()).copied().unwrap_or(0)
+    }
+}
+
+// ─── Step ────────────────────────────────────────────────────────────────────
+
+/// One cell filled during the walkthrough.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Step {
+    /// Position in the fill order, from 0.
+    pub ordinal: usize,
+    /// Table row, i.e. the item number (1-based).
+    pub row: usize,
+    /// Table column, i.e. the capacity.
+    pub col: usize,
+    /// `dp[row][col]`.
+    pub value: usize,
+}
+
+// ─── Solution ────────────────────────────────────────────────────────────────
+
+/// The answer to an instance.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Solution {
+    /// The optimal total benefit.
+    pub best: usize,
+    /// 1-based item numbers of one optimal packing, in ascending order.
+    pub items: Vec<usize>,
+    /// Total weight of those items.
+    pub weight: usize,
+}
+
+impl Solution {
+    pub fn from_table(table: &Table, p: &problem::Problem) -> Self {
+        let items = chosen_items(&table.values, &p.weights, p.capacity);
+        let weight = items.iter().map(|&i| p.weights[i - 1]).sum();
+        Self { best: table.best(), items, weight }
+    }
+}
+
+/// A solved instance as one document, e.g. the CLI's `--json` output.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Report {
+    pub version: u32,
+    pub problem: problem::Problem,
+    pub solution: Solution,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub table: Option<Table>,
+}
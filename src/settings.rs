@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+
+use crate::columns::ColumnFilter;
+use crate::display::{Density, Layout, Theme};
+use crate::format::{Locale, NumberFormat};
+use crate::order::FillOrder;
+use crate::problem::Problem;
+use crate::solver::ValueType;
+
+/// Cells per second auto-play can reveal; the fast ones reveal several per frame.
+pub const PLAY_SPEEDS: [u32; 7] = [2, 5, 10, 30, 100, 300, 1_000];
+/// Lengths offered for the reveal animation, in milliseconds; 0 turns it off.
+pub const REVEAL_DURATIONS: [u32; 5] = [0, 150, 250, 400, 700];
+
+/// User-adjustable display and behaviour options shared by the visualizer.
+///
+/// Missing fields take their default when deserializing, so files saved by
+/// older versions keep loading as settings are added.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// How benefit values are written in cells and summaries.
+    pub number_format: NumberFormat,
+    /// Digit grouping accepted in the form inputs (`1,000` vs `1.000`).
+    pub parse_locale: Locale,
+    /// Bounds enforced by the capacity stepper.
+    pub capacity_min: usize,
+    pub capacity_max: usize,
+    /// Above this many items the table is not drawn without asking first.
+    pub max_visual_items: usize,
+    /// Annotate the table with its `n × (W+1)` dimensions.
+    pub show_complexity_overlay: bool,
+    /// Order in which step mode reveals the cells.
+    pub fill_order: FillOrder,
+    /// Cells per second revealed by auto-play.
+    pub play_speed: u32,
+    /// Length of a revealed cell's fade-in, in milliseconds; the active
+    /// cell's pulse lasts a little over twice as long.
+    pub reveal_ms: u32,
+    /// Print each cell's position in the fill order in its corner.
+    pub show_fill_ordinals: bool,
+    /// Show each cell as its gain over the cell above, `dp[i][w] - dp[i-1][w]`.
+    pub show_row_deltas: bool,
+    /// Shade each revealed cell by its value as a share of the optimum.
+    pub shade_by_optimum: bool,
+    /// Show a second table with the weight each cell's best subset uses.
+    pub show_weight_used: bool,
+    /// Which capacity columns are drawn; the others collapse into `⋯`.
+    pub column_filter: ColumnFilter,
+    /// Keep the capacity header row and item column in view while scrolling.
+    pub freeze_headers: bool,
+    /// Cell size and padding of the DP table.
+    pub density: Density,
+    /// UI font size as a percentage of the default 16px.
+    pub font_scale: u16,
+    pub theme: Theme,
+    /// Order and heights of the visualizer's panels.
+    pub layout: Layout,
+    /// Play short sounds while stepping and on completion.
+    pub sound_effects: bool,
+    /// Debug overlay checking each revealed row against the DP invariants.
+    pub check_invariants: bool,
+    /// Compare every optimum against a slow reference solver.
+    pub cross_check: bool,
+    /// Integer type of the DP values; wide solves show a read-only table.
+    pub value_type: ValueType,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            number_format: NumberFormat::default(),
+            parse_locale: Locale::default(),
+            capacity_min: 1,
+            capacity_max: 500,
+            max_visual_items: 50,
+            show_complexity_overlay: false,
+            fill_order: FillOrder::default(),
+            play_speed: 10,
+            reveal_ms: 250,
+            show_fill_ordinals: false,
+            show_row_deltas: false,
+            shade_by_optimum: false,
+            show_weight_used: false,
+            column_filter: ColumnFilter::default(),
+            freeze_headers: false,
+            density: Density::default(),
+            font_scale: 100,
+            theme: Theme::default(),
+            layout: Layout::default(),
+            sound_effects: false,
+            check_invariants: false,
+            cross_check: false,
+            value_type: ValueType::default(),
+        }
+    }
+}
+
+impl Settings {
+    /// Check an instance that did not come through the form, such as one read
+    /// from a file, against the capacity bounds the form's stepper enforces
+    /// and what [`Problem::validate`] requires of the values. Too many items
+    /// is left to the large-input dialog.
+    pub fn check_size(&self, p: &Problem) -> Result<(), String> {
+        self.check_capacity(p.capacity, None)?;
+        p.validate()
+    }
+
+    /// Check a capacity against the range set in Settings. `scale` is the
+    /// factor decimals were multiplied by, which the stepper never sees;
+    /// scaling only grows a capacity, so then just the maximum is checked.
+    pub fn check_capacity(&self, capacity: usize, scale: Option<usize>) -> Result<(), String> {
+        match scale {
+            Some(k) if capacity > self.capacity_max => Err(format!(
+                "scaling ×{k} makes the capacity {capacity}, above the maximum of {} set in Settings",
+                self.capacity_max
+            )),
+            None if !(self.capacity_min..=self.capacity_max).contains(&capacity) => Err(format!(
+                "Capacity {capacity} is outside {}..={}, the range set in Settings.",
+                self.capacity_min, self.capacity_max
+            )),
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use crate::problem::Problem;
+use crate::solver::solve_headless;
+
+// ─── Random numbers ──────────────────────────────────────────────────────────
+
+/// Small seeded generator (SplitMix64), so a simulation can be repeated
+/// exactly from its seed without pulling in a `rand` dependency.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `lo..=hi`.
+    pub fn between(&mut self, lo: usize, hi: usize) -> usize {
+        match ((hi - lo) as u64).checked_add(1) {
+            Some(span) => lo + (self.next_u64() % span) as usize,
+            // the whole range of u64
+            None => self.next_u64() as usize,
+        }
+    }
+}
+
+// ─── Monte Carlo ─────────────────────────────────────────────────────────────
+
+/// Outcome of drawing every benefit uniformly from `b ± spread` many times.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Simulation {
+    /// Optimum on the expected (entered) benefits, 1-based items.
+    pub fixed_items: Vec<usize>,
+    /// Realized value of `fixed_items` in each sample.
+    pub fixed_values: Vec<usize>,
+    /// Optimum of each sample when solved again on its own benefits.
+    pub reoptimized_values: Vec<usize>,
+}
+
+impl Simulation {
+    pub fn samples(&self) -> usize {
+        self.fixed_values.len()
+    }
+
+    /// Share of samples in which the fixed set was still optimal.
+    pub fn still_optimal(&self) -> f64 {
+        let hits = self.fixed_values.iter().zip(&self.reoptimized_values).filter(|(f, r)| f == r).count();
+        hits as f64 / self.samples().max(1) as f64
+    }
+
+    pub fn mean_fixed(&self) -> f64 {
+        mean(&self.fixed_values)
+    }
+
+    pub fn mean_reoptimized(&self) -> f64 {
+        mean(&self.reoptimized_values)
+    }
+}
+
+fn mean(values: &[usize]) -> f64 {
+    values.iter().sum::<usize>() as f64 / values.len().max(1) as f64
+}
+
+/// Run `samples` draws. A spread larger than its benefit is cut at zero,
+/// which lifts that item's mean slightly above the entered value.
+pub fn simulate(p: &Problem, spreads: &[usize], samples: usize, seed: u64) -> Simulation {
+    let fixed_items = solve_headless(p).chosen;
+    let mut rng = Rng::new(seed);
+    let mut fixed_values = Vec::with_capacity(samples);
+    let mut reoptimized_values = Vec::with_capacity(samples);
+    let mut sample = p.clone();
+    for _ in 0..samples {
+        for (i, b) in sample.benefits.iter_mut().enumerate() {
+            let (base, spread) = (p.benefits[i], spreads[i]);
+            *b = rng.between(base.saturating_sub(spread), base.saturating_add(spread));
+        }
+        fixed_values.push(fixed_items.iter().fold(0usize, |sum, &i| sum.saturating_add(sample.benefits[i - 1])));
+        reoptimized_values.push(solve_headless(&sample).best);
+    }
+    Simulation { fixed_items, fixed_values, reoptimized_values }
+}
+
+/// Bucket counts of `series` over a shared range split into `bins` equal
+/// bins, returned with each bin's lower bound: `(lows, counts per series)`.
+pub fn histogram(series: &[&[usize]], bins: usize) -> (Vec<usize>, Vec<Vec<usize>>) {
+    let all = series.iter().flat_map(|s| s.iter().copied());
+    let (lo, hi) = all.fold((usize::MAX, 0), |(lo, hi), v| (lo.min(v), hi.max(v)));
+    if lo > hi {
+        return (Vec::new(), vec![Vec::new(); series.len()]);
+    }
+    let width = ((hi - lo) / bins.max(1)) + 1;
+    let bins = (hi - lo) / width + 1;
+    let lows = (0..bins).map(|b| lo + b * width).collect();
+    let counts = series
+        .iter()
+        .map(|s| {
+            let mut counts = vec![0; bins];
+            for &v in s.iter() {
+                counts[(v - lo) / width] += 1;
+            }
+            counts
+        })
+        .collect();
+    (lows, counts)
+}
@@ -0,0 +1,823 @@
+use serde::{Deserialize, Serialize};
+
+use crate::problem::{Problem, Units};
+
+// The recurrence itself lives in the `no_std` core crate
+pub use knapsack_core::{
+    chosen_items, subset_sums, trace_path, Pacer, Progress, RevealOrder, SimEvent, Simulation, StepPlan, TraceHop,
+};
+
+// ─── Incremental solving ─────────────────────────────────────────────────────
+
+/// How far an incremental solve has got, in item rows.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SolveProgress {
+    pub rows_done: usize,
+    pub rows_total: usize,
+}
+
+impl SolveProgress {
+    pub fn is_done(&self) -> bool {
+        self.rows_done >= self.rows_total
+    }
+}
+
+/// A solver that can be advanced a few item rows at a time, so long solves
+/// can be interleaved with rendering and report their progress.
+pub trait RowSolver {
+    type Output;
+
+    /// Compute up to `rows` more item rows.
+    fn fill_rows(&mut self, rows: usize) -> SolveProgress;
+
+    fn finish(self) -> Self::Output;
+}
+
+// ─── Table solver ────────────────────────────────────────────────────────────
+
+/// Solve the 0/1 knapsack problem and return the full DP table.
+/// table[i][w] = best value using items 0..i with capacity w.
+pub fn knapsack_table(capacity: usize, weights: &[usize], benefits: &[usize]) -> Vec<Vec<usize>> {
+    let mut solver = TableSolver::new(capacity, weights, benefits);
+    solver.fill_rows(usize::MAX);
+    solver.finish()
+}
+
+/// Incremental form of [`knapsack_table`].
+pub struct TableSolver {
+    weights: Vec<usize>,
+    benefits: Vec<usize>,
+    table: Vec<Vec<usize>>,
+    next_row: usize,
+}
+
+impl TableSolver {
+    pub fn new(capacity: usize, weights: &[usize], benefits: &[usize]) -> Self {
+        // (n+1) rows × (capacity+1) cols, row 0 is the "no items" baseline
+        Self {
+            weights: weights.to_vec(),
+            benefits: benefits.to_vec(),
+            table: vec![vec![0usize; capacity + 1]; weights.len() + 1],
+            next_row: 1,
+        }
+    }
+}
+
+impl RowSolver for TableSolver {
+    type Output = Vec<Vec<usize>>;
+
+    fn fill_rows(&mut self, rows: usize) -> SolveProgress {
+        let n = self.weights.len();
+        let last = n.min(self.next_row.saturating_add(rows).saturating_sub(1));
+        let table = &mut self.table;
+
+        for i in self.next_row..=last {
+            let (done, rest) = table.split_at_mut(i);
+            knapsack_core::fill_row(&done[i - 1], &mut rest[0], self.weights[i - 1], &self.benefits[i - 1]);
+        }
+        self.next_row = last + 1;
+        SolveProgress { rows_done: last, rows_total: n }
+    }
+
+    fn finish(self) -> Self::Output {
+        self.table
+    }
+}
+
+/// Walk the finished table from `table[n][capacity]` upwards and return the
+/// `(row, col)` cells where the item of that row was taken, bottom row first.
+pub fn backtrack(table: &[Vec<usize>], weights: &[usize], capacity: usize) -> Vec<(usize, usize)> {
+    trace_path(table, weights, capacity)
+        .into_iter()
+        .filter(|hop| hop.taken)
+        .map(|hop| (hop.row, hop.col))
+        .collect()
+}
+
+/// Total weight of the subset each cell's traceback recovers: `used[i][w]`
+/// never exceeds `w`, and is smaller wherever the best fill leaves room.
+pub fn weight_used(table: &[Vec<usize>], weights: &[usize]) -> Vec<Vec<usize>> {
+    let mut used = vec![vec![0; table.first().map_or(0, Vec::len)]; table.len()];
+    for i in 1..table.len() {
+        for w in 0..table[i].len() {
+            used[i][w] = if table[i][w] != table[i - 1][w] {
+                used[i - 1][w - weights[i - 1]] + weights[i - 1]
+            } else {
+                used[i - 1][w]
+            };
+        }
+    }
+    used
+}
+
+// ─── Recurrence variants ─────────────────────────────────────────────────────
+
+/// A deliberate mistake that can be switched into the recurrence, to show
+/// how a common bug changes the table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RecurrenceBug {
+    /// Take from `dp[i][w-wt]` instead of `dp[i-1][w-wt]`, so an item can be
+    /// packed more than once.
+    SameRowTake,
+    /// Always take the item when it fits instead of taking the max.
+    ForgetMax,
+    /// Treat an exact fit (`wt == w`) as too heavy.
+    StrictFit,
+    /// Write 0 instead of copying `dp[i-1][w]` when the item is too heavy.
+    ZeroWhenHeavy,
+}
+
+impl RecurrenceBug {
+    pub const ALL: [RecurrenceBug; 4] = [
+        RecurrenceBug::SameRowTake,
+        RecurrenceBug::ForgetMax,
+        RecurrenceBug::StrictFit,
+        RecurrenceBug::ZeroWhenHeavy,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            RecurrenceBug::SameRowTake => "dp[i][w−wᵢ] instead of dp[i−1][w−wᵢ]",
+            RecurrenceBug::ForgetMax => "forget the max (always take)",
+            RecurrenceBug::StrictFit => "wᵢ ≥ w counts as too heavy",
+            RecurrenceBug::ZeroWhenHeavy => "0 instead of dp[i−1][w] when too heavy",
+        }
+    }
+}
+
+/// [`knapsack_table`] with the given `bugs` applied to every cell; with no
+/// bugs it produces the correct table.
+pub fn knapsack_table_with(
+    capacity: usize,
+    weights: &[usize],
+    benefits: &[usize],
+    bugs: &[RecurrenceBug],
+) -> Vec<Vec<usize>> {
+    let has = |bug| bugs.contains(&bug);
+    let mut table = vec![vec![0usize; capacity + 1]; weights.len() + 1];
+    for i in 1..=weights.len() {
+        let (wt, b) = (weights[i - 1], benefits[i - 1]);
+        for w in 0..=capacity {
+            let too_heavy = if has(RecurrenceBug::StrictFit) { wt >= w } else { wt > w };
+            table[i][w] = if too_heavy {
+                if has(RecurrenceBug::ZeroWhenHeavy) { 0 } else { table[i - 1][w] }
+            } else {
+                let from = if has(RecurrenceBug::SameRowTake) { i } else { i - 1 };
+                let take = b + table[from][w - wt];
+                if has(RecurrenceBug::ForgetMax) { take } else { table[i - 1][w].max(take) }
+            };
+        }
+    }
+    table
+}
+
+// ─── Invariants ──────────────────────────────────────────────────────────────
+
+/// A cell breaking one of the properties every correct table has.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InvariantViolation {
+    /// `dp[row][col] < dp[row][col - 1]`: more capacity never hurts.
+    Decreasing { row: usize, col: usize },
+    /// `dp[row][col] < dp[row - 1][col]`: an extra item never hurts.
+    BelowPrevious { row: usize, col: usize },
+}
+
+impl InvariantViolation {
+    pub fn cell(self) -> (usize, usize) {
+        match self {
+            InvariantViolation::Decreasing { row, col } | InvariantViolation::BelowPrevious { row, col } => {
+                (row, col)
+            }
+        }
+    }
+
+    pub fn describe(self, table: &[Vec<usize>]) -> String {
+        match self {
+            InvariantViolation::Decreasing { row, col } => format!(
+                "dp[{row}][{col}] = {} < dp[{row}][{}] = {}",
+                table[row][col], col - 1, table[row][col - 1]
+            ),
+            InvariantViolation::BelowPrevious { row, col } => format!(
+                "dp[{row}][{col}] = {} < dp[{}][{col}] = {}",
+                table[row][col], row - 1, table[row - 1][col]
+            ),
+        }
+    }
+}
+
+/// Check item row `row` (1-based) against the row above and along itself,
+/// looking only at cells for which `revealed(col)` holds.
+pub fn check_row(table: &[Vec<usize>], row: usize, revealed: impl Fn(usize) -> bool) -> Vec<InvariantViolation> {
+    let mut found = Vec::new();
+    for col in (0..table[row].len()).filter(|&c| revealed(c)) {
+        if col > 0 && revealed(col - 1) && table[row][col] < table[row][col - 1] {
+            found.push(InvariantViolation::Decreasing { row, col });
+        }
+        if table[row][col] < table[row - 1][col] {
+            found.push(InvariantViolation::BelowPrevious { row, col });
+        }
+    }
+    found
+}
+
+// ─── Memory footprint ────────────────────────────────────────────────────────
+
+/// Bytes a [`knapsack_table`] for `n` items and `capacity` occupies:
+/// the row vectors' headers plus `(n+1) × (capacity+1)` values.
+pub fn table_bytes(n: usize, capacity: usize) -> usize {
+    let rows = n + 1;
+    let cols = capacity + 1;
+    rows * size_of::<Vec<usize>>() + rows * cols * size_of::<usize>()
+}
+
+/// Bytes [`solve_headless`] needs: one value row plus a bit per cell.
+pub fn headless_bytes(n: usize, capacity: usize) -> usize {
+    let cols = capacity + 1;
+    cols * size_of::<usize>() + (n * cols).div_ceil(64) * size_of::<u64>()
+}
+
+/// Bytes actually reserved by a built table, including spare capacity.
+pub fn allocated_bytes(table: &[Vec<usize>]) -> usize {
+    size_of_val(table)
+        + table.iter().map(|row| row.capacity() * size_of::<usize>()).sum::<usize>()
+}
+
+// ─── Headless solver ─────────────────────────────────────────────────────────
+
+/// Result of a solve that never materialises the value table.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HeadlessSolution {
+    pub best: usize,
+    /// 1-based item indices, ascending.
+    pub chosen: Vec<usize>,
+    pub weight_used: usize,
+    /// Number of DP cells evaluated, `n × (capacity + 1)`.
+    pub cells: usize,
+    /// The last table row `dp[n][0..=capacity]`, the one row kept.
+    pub last_row: Vec<usize>,
+}
+
+/// Solve `p` keeping a single value row plus one "taken" bit per cell, which
+/// is enough to recover the chosen items at a fraction of the table's memory.
+pub fn solve_headless(p: &Problem) -> HeadlessSolution {
+    let mut solver = HeadlessSolver::new(p);
+    solver.fill_rows(usize::MAX);
+    solver.finish()
+}
+
+/// Incremental form of [`solve_headless`].
+pub struct HeadlessSolver {
+    problem: Problem,
+    row: Vec<usize>,
+    taken: Vec<u64>,
+    next_item: usize,
+}
+
+impl HeadlessSolver {
+    pub fn new(p: &Problem) -> Self {
+        let cols = p.capacity + 1;
+        Self {
+            problem: p.clone(),
+            row: vec![0usize; cols],
+            taken: vec![0u64; (p.len() * cols).div_ceil(64)],
+            next_item: 0,
+        }
+    }
+}
+
+impl RowSolver for HeadlessSolver {
+    type Output = HeadlessSolution;
+
+    fn fill_rows(&mut self, rows: usize) -> SolveProgress {
+        let p = &self.problem;
+        let n = p.len();
+        let cols = p.capacity + 1;
+        let end = n.min(self.next_item.saturating_add(rows));
+
+        for i in self.next_item..end {
+            let (w, b) = (p.weights[i], p.benefits[i]);
+            // right-to-left so row[c - w] still holds the previous row's value
+            for c in (w..cols).rev() {
+                let with = self.row[c - w] + b;
+                if with > self.row[c] {
+                    self.row[c] = with;
+                    let bit = i * cols + c;
+                    self.taken[bit / 64] |= 1 << (bit % 64);
+                }
+            }
+        }
+        self.next_item = end;
+        SolveProgress { rows_done: end, rows_total: n }
+    }
+
+    fn finish(self) -> Self::Output {
+        let p = &self.problem;
+        let n = p.len();
+        let cols = p.capacity + 1;
+
+        let mut chosen = Vec::new();
+        let mut c = p.capacity;
+        for i in (0..n).rev() {
+            let bit = i * cols + c;
+            if self.taken[bit / 64] & (1 << (bit % 64)) != 0 {
+                chosen.push(i + 1);
+                c -= p.weights[i];
+            }
+        }
+        chosen.reverse();
+
+        HeadlessSolution {
+            best: self.row[p.capacity],
+            weight_used: chosen.iter().map(|&i| p.weights[i - 1]).sum(),
+            chosen,
+            cells: n * cols,
+            last_row: self.row,
+        }
+    }
+}
+
+// ─── Wide values ─────────────────────────────────────────────────────────────
+
+/// Integer type of the table values. Native `usize` is half the memory of
+/// `u128` on 64-bit targets and a quarter of it in wasm, where `usize` is
+/// 32 bits and overflows past about 4.29 billion.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValueType {
+    #[default]
+    Native,
+    Wide,
+}
+
+impl ValueType {
+    pub const ALL: [ValueType; 2] = [ValueType::Native, ValueType::Wide];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            ValueType::Native => "native",
+            ValueType::Wide => "wide",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|v| v.code() == code)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ValueType::Native => "Native (usize)",
+            ValueType::Wide => "Wide (u128)",
+        }
+    }
+}
+
+/// An instance whose benefits need the wide backend.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WideProblem {
+    pub capacity: usize,
+    pub weights: Vec<usize>,
+    pub benefits: Vec<u128>,
+    pub units: Units,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WideSolution {
+    pub table: Vec<Vec<u128>>,
+    pub best: u128,
+    /// 1-based item indices, ascending.
+    pub chosen: Vec<usize>,
+}
+
+pub fn solve_wide(p: &WideProblem) -> WideSolution {
+    let table = knapsack_core::table(p.capacity, &p.weights, &p.benefits);
+    let best = table[p.weights.len()][p.capacity];
+    let chosen = chosen_items(&table, &p.weights, p.capacity);
+    WideSolution { table, best, chosen }
+}
+
+// ─── Cardinality limit ───────────────────────────────────────────────────────
+
+/// Solution of the knapsack restricted to at most `k` items, kept as
+/// `k + 1` layers: `layers[j][i][c]` is the best value of items `0..i` at
+/// capacity `c` using at most `j` of them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CardinalitySolution {
+    pub layers: Vec<Vec<Vec<usize>>>,
+    pub best: usize,
+    /// 1-based item indices, ascending.
+    pub chosen: Vec<usize>,
+    /// Cells `(layer, row, col)` where the traceback took an item.
+    pub path: Vec<(usize, usize, usize)>,
+}
+
+/// Layer `j` takes item `i` from layer `j - 1`:
+/// `T_j[i][c] = max(T_j[i-1][c], T_{j-1}[i-1][c - w_i] + b_i)`.
+/// Layer 0 is all zeros. Time and memory are `O(k·n·m)`; a `k` above `n`
+/// is taken as `n`, which already allows every item.
+pub fn solve_cardinality(p: &Problem, k: usize) -> CardinalitySolution {
+    let (n, m) = (p.len(), p.capacity);
+    let k = k.min(n);
+    let mut layers = vec![vec![vec![0usize; m + 1]; n + 1]];
+    for j in 1..=k {
+        let below = &layers[j - 1];
+        let mut layer = vec![vec![0usize; m + 1]; n + 1];
+        for i in 1..=n {
+            let (w, b) = (p.weights[i - 1], p.benefits[i - 1]);
+            for c in 0..=m {
+                let skip = layer[i - 1][c];
+                layer[i][c] = if w <= c { skip.max(below[i - 1][c - w] + b) } else { skip };
+            }
+        }
+        layers.push(layer);
+    }
+
+    let best = layers[k][n][m];
+    let (mut j, mut c) = (k, m);
+    let mut path = Vec::new();
+    for i in (1..=n).rev() {
+        if j == 0 {
+            break;
+        }
+        if layers[j][i][c] != layers[j][i - 1][c] {
+            path.push((j, i, c));
+            c -= p.weights[i - 1];
+            j -= 1;
+        }
+    }
+    let mut chosen: Vec<usize> = path.iter().map(|&(_, i, _)| i).collect();
+    chosen.reverse();
+    CardinalitySolution { layers, best, chosen, path }
+}
+
+// ─── Bounded items ───────────────────────────────────────────────────────────
+
+/// An item available up to `count` times.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BoundedItem {
+    pub weight: usize,
+    pub benefit: usize,
+    pub count: usize,
+}
+
+/// Identical `(weight, benefit)` pairs merged into one bounded item each,
+/// in order of first appearance.
+pub fn merge_duplicates(p: &Problem) -> Vec<BoundedItem> {
+    let mut items: Vec<BoundedItem> = Vec::new();
+    for (&weight, &benefit) in p.weights.iter().zip(&p.benefits) {
+        match items.iter_mut().find(|it| it.weight == weight && it.benefit == benefit) {
+            Some(it) => it.count += 1,
+            None => items.push(BoundedItem { weight, benefit, count: 1 }),
+        }
+    }
+    items
+}
+
+/// Solution of the bounded knapsack: `table[i][c]` is the best value of the
+/// first `i` bounded items at capacity `c`, and `copies[i][c]` how many
+/// copies of item `i` that cell took.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BoundedSolution {
+    pub table: Vec<Vec<usize>>,
+    pub copies: Vec<Vec<usize>>,
+    pub best: usize,
+    /// Copies of each item in the optimum.
+    pub taken: Vec<usize>,
+}
+
+/// `T[i][c] = max over k ≤ count_i, k·w_i ≤ c of T[i-1][c - k·w_i] + k·b_i`.
+/// Time is `O(m · Σ count)`, the same as the 0/1 table of the unmerged items.
+pub fn solve_bounded(items: &[BoundedItem], capacity: usize) -> BoundedSolution {
+    let n = items.len();
+    let mut table = vec![vec![0usize; capacity + 1]; n + 1];
+    let mut copies = vec![vec![0usize; capacity + 1]; n + 1];
+    for (i, it) in items.iter().enumerate().map(|(i, it)| (i + 1, it)) {
+        for c in 0..=capacity {
+            let (mut best, mut k_best) = (table[i - 1][c], 0);
+            for k in 1..=it.count {
+                let Some(w) = it.weight.checked_mul(k).filter(|&w| w <= c) else { break };
+                let v = table[i - 1][c - w] + k * it.benefit;
+                if v > best {
+                    (best, k_best) = (v, k);
+                }
+            }
+            table[i][c] = best;
+            copies[i][c] = k_best;
+        }
+    }
+
+    let mut taken = vec![0; n];
+    let mut c = capacity;
+    for i in (1..=n).rev() {
+        taken[i - 1] = copies[i][c];
+        c -= copies[i][c] * items[i - 1].weight;
+    }
+    BoundedSolution { best: table[n][capacity], table, copies, taken }
+}
+
+// ─── Precedence constraints ──────────────────────────────────────────────────
+
+/// Solution of the knapsack where an item can only be packed together with
+/// its prerequisite, for prerequisites that form a forest.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PrecedenceSolution {
+    /// 0-based items in depth-first preorder of the forest; row `r` of
+    /// `table` belongs to item `order[r]`.
+    pub order: Vec<usize>,
+    /// Row just past the subtree of the item at each row.
+    pub subtree_end: Vec<usize>,
+    /// `table[r][c]`: best value of the items at rows `r..` with capacity
+    /// `c`, where a subtree is only reachable through its root. The last
+    /// row is the empty base.
+    pub table: Vec<Vec<usize>>,
+    pub best: usize,
+    /// 1-based item indices, ascending.
+    pub chosen: Vec<usize>,
+    /// `(row, col)` cells where the traceback took an item.
+    pub path: Vec<(usize, usize)>,
+}
+
+/// Tree knapsack over the preorder of the forest: skipping the item at row
+/// `r` skips its whole subtree, taking it moves on to its first child:
+/// `T[r][c] = max(T[end(r)][c], T[r+1][c - w] + b)`.
+pub fn solve_precedence(p: &Problem, parents: &[Option<usize>]) -> PrecedenceSolution {
+    let (n, m) = (p.len(), p.capacity);
+    let mut children = vec![Vec::new(); n];
+    let mut roots = Vec::new();
+    for (i, parent) in parents.iter().enumerate() {
+        match parent {
+            Some(a) => children[*a].push(i),
+            None => roots.push(i),
+        }
+    }
+
+    let mut order = Vec::with_capacity(n);
+    let mut subtree_end = vec![0; n];
+    // (item, row) pairs still to close, and items still to open
+    let mut open: Vec<(usize, usize)> = Vec::new();
+    let mut pending: Vec<usize> = roots.into_iter().rev().collect();
+    while let Some(i) = pending.pop() {
+        while let Some(&(top, row)) = open.last() {
+            if parents[i] == Some(top) {
+                break;
+            }
+            subtree_end[row] = order.len();
+            open.pop();
+        }
+        open.push((i, order.len()));
+        order.push(i);
+        pending.extend(children[i].iter().rev());
+    }
+    for (_, row) in open {
+        subtree_end[row] = n;
+    }
+
+    let mut table = vec![vec![0usize; m + 1]; n + 1];
+    for r in (0..n).rev() {
+        let (w, b) = (p.weights[order[r]], p.benefits[order[r]]);
+        for c in 0..=m {
+            let skip = table[subtree_end[r]][c];
+            table[r][c] = if w <= c { skip.max(table[r + 1][c - w] + b) } else { skip };
+        }
+    }
+
+    let (mut r, mut c) = (0, m);
+    let mut path = Vec::new();
+    while r < n {
+        if table[r][c] != table[subtree_end[r]][c] {
+            path.push((r, c));
+            c -= p.weights[order[r]];
+            r += 1;
+        } else {
+            r = subtree_end[r];
+        }
+    }
+    let mut chosen: Vec<usize> = path.iter().map(|&(r, _)| order[r] + 1).collect();
+    chosen.sort_unstable();
+    PrecedenceSolution { best: table[0][m], order, subtree_end, table, chosen, path }
+}
+
+/// Chosen items (1-based) whose prerequisite is not chosen, each paired
+/// with that prerequisite.
+pub fn missing_prerequisites(chosen: &[usize], parents: &[Option<usize>]) -> Vec<(usize, usize)> {
+    chosen
+        .iter()
+        .filter_map(|&i| parents[i - 1].map(|a| (i, a + 1)))
+        .filter(|(_, a)| !chosen.contains(a))
+        .collect()
+}
+
+// ─── Branch and bound ────────────────────────────────────────────────────────
+
+/// A partial packing: the first `level` items of the ratio order decided.
+#[derive(Clone, Debug)]
+struct Node {
+    level: usize,
+    value: usize,
+    weight: usize,
+    /// 0-based items taken so far.
+    taken: Vec<usize>,
+}
+
+/// Depth-first branch and bound over the items sorted by benefit/weight,
+/// pruning nodes whose fractional bound cannot beat the best packing found.
+/// Advanced one node at a time so it can be animated against the DP.
+pub struct BranchAndBound {
+    problem: Problem,
+    order: Vec<usize>,
+    stack: Vec<Node>,
+    best: usize,
+    best_items: Vec<usize>,
+    /// Nodes taken off the stack so far.
+    pub nodes: usize,
+    /// Nodes discarded by their bound.
+    pub pruned: usize,
+}
+
+impl BranchAndBound {
+    pub fn new(p: &Problem) -> Self {
+        Self {
+            problem: p.clone(),
+            order: crate::analysis::by_ratio(p),
+            stack: vec![Node { level: 0, value: 0, weight: 0, taken: Vec::new() }],
+            best: 0,
+            best_items: Vec::new(),
+            nodes: 0,
+            pruned: 0,
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.stack.is_empty()
+    }
+
+    pub fn best(&self) -> usize {
+        self.best
+    }
+
+    /// 1-based item indices of the best packing so far, ascending.
+    pub fn chosen(&self) -> Vec<usize> {
+        let mut items: Vec<usize> = self.best_items.iter().map(|i| i + 1).collect();
+        items.sort_unstable();
+        items
+    }
+
+    /// Explore up to `budget` nodes; returns how many were explored.
+    pub fn step(&mut self, budget: usize) -> usize {
+        let mut done = 0;
+        while done < budget {
+            let Some(node) = self.stack.pop() else { break };
+            done += 1;
+            self.nodes += 1;
+            if node.value > self.best {
+                self.best = node.value;
+                self.best_items = node.taken.clone();
+            }
+            if node.level == self.order.len() {
+                continue;
+            }
+            if self.bound(&node) <= self.best as f64 {
+                self.pruned += 1;
+                continue;
+            }
+
+            let item = self.order[node.level];
+            let (w, b) = (self.problem.weights[item], self.problem.benefits[item]);
+            // pushed first, so explored after the branch that takes the item
+            self.stack.push(Node { level: node.level + 1, ..node.clone() });
+            if node.weight + w <= self.problem.capacity {
+                let mut taken = node.taken;
+                taken.push(item);
+                self.stack.push(Node { level: node.level + 1, value: node.value + b, weight: node.weight + w, taken });
+            }
+        }
+        done
+    }
+
+    /// Node value plus the fractional greedy fill of the undecided items.
+    fn bound(&self, node: &Node) -> f64 {
+        let p = &self.problem;
+        let mut room = (p.capacity - node.weight) as f64;
+        let mut bound = node.value as f64;
+        for &i in &self.order[node.level..] {
+            let (w, b) = (p.weights[i] as f64, p.benefits[i] as f64);
+            if w <= room {
+                room -= w;
+                bound += b;
+            } else {
+                bound += b * room / w;
+                break;
+            }
+        }
+        bound
+    }
+}
+
+// ─── Top-down ────────────────────────────────────────────────────────────────
+
+/// Result of the memoized recursion `kp(i, w)` from `kp(n, m)` down, with
+/// how often each subproblem was asked for.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TopDownSolution {
+    /// `memo[i][w]`: value of `kp(i, w)`, `None` where never reached.
+    pub memo: Vec<Vec<Option<usize>>>,
+    /// `requests[i][w]`: calls of `kp(i, w)`, the first one included.
+    pub requests: Vec<Vec<usize>>,
+    pub best: usize,
+    /// Subproblems actually computed.
+    pub computed: usize,
+    /// Calls answered from the memo.
+    pub cache_hits: usize,
+    /// Every call in order, for stepping through the recursion.
+    pub events: Vec<CallEvent>,
+}
+
+/// One step of the top-down recursion.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CallEvent {
+    /// `kp(i, w)` entered, not in the memo yet.
+    Call { i: usize, w: usize },
+    /// `kp(i, w)` answered from the memo.
+    Hit { i: usize, w: usize, value: usize },
+    /// `kp(i, w)` computed `value` and returned.
+    Return { i: usize, w: usize, value: usize },
+}
+
+impl CallEvent {
+    pub fn cell(self) -> (usize, usize) {
+        match self {
+            CallEvent::Call { i, w } | CallEvent::Hit { i, w, .. } | CallEvent::Return { i, w, .. } => (i, w),
+        }
+    }
+
+    pub fn describe(self) -> String {
+        match self {
+            CallEvent::Call { i, w } => format!("call kp({i}, {w})"),
+            CallEvent::Hit { i, w, value } => format!("kp({i}, {w}) = {value} from the memo"),
+            CallEvent::Return { i, w, value } => format!("kp({i}, {w}) returns {value}"),
+        }
+    }
+}
+
+/// Frames open after `events`, outermost first.
+pub fn call_stack(events: &[CallEvent]) -> Vec<(usize, usize)> {
+    let mut stack = Vec::new();
+    for e in events {
+        match e {
+            CallEvent::Call { i, w } => stack.push((*i, *w)),
+            CallEvent::Return { .. } => {
+                stack.pop();
+            }
+            CallEvent::Hit { .. } => {}
+        }
+    }
+    stack
+}
+
+/// Requests per subproblem after `events`, as in [`TopDownSolution::requests`].
+pub fn requests_after(events: &[CallEvent], n: usize, m: usize) -> Vec<Vec<usize>> {
+    let mut requests = vec![vec![0; m + 1]; n + 1];
+    for e in events {
+        if let CallEvent::Call { i, w } | CallEvent::Hit { i, w, .. } = *e {
+            requests[i][w] += 1;
+        }
+    }
+    requests
+}
+
+/// `kp(0, w) = 0`; `kp(i, w) = kp(i-1, w)` when item `i` is heavier than
+/// `w`, else `max(kp(i-1, w), kp(i-1, w - w_i) + b_i)`. The recursion is
+/// `n + 1` frames deep at most.
+pub fn solve_top_down(p: &Problem) -> TopDownSolution {
+    let (n, m) = (p.len(), p.capacity);
+    let mut s = TopDownSolution {
+        memo: vec![vec![None; m + 1]; n + 1],
+        requests: vec![vec![0; m + 1]; n + 1],
+        best: 0,
+        computed: 0,
+        cache_hits: 0,
+        events: Vec::new(),
+    };
+    s.best = kp(p, &mut s, n, m);
+    s
+}
+
+fn kp(p: &Problem, s: &mut TopDownSolution, i: usize, w: usize) -> usize {
+    s.requests[i][w] += 1;
+    if let Some(value) = s.memo[i][w] {
+        s.cache_hits += 1;
+        s.events.push(CallEvent::Hit { i, w, value });
+        return value;
+    }
+    s.computed += 1;
+    s.events.push(CallEvent::Call { i, w });
+    let v = if i == 0 {
+        0
+    } else {
+        let skip = kp(p, s, i - 1, w);
+        let (wi, bi) = (p.weights[i - 1], p.benefits[i - 1]);
+        if wi <= w { skip.max(kp(p, s, i - 1, w - wi) + bi) } else { skip }
+    };
+    s.memo[i][w] = Some(v);
+    s.events.push(CallEvent::Return { i, w, value: v });
+    v
+}
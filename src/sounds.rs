@@ -0,0 +1,65 @@
+use std::cell::RefCell;
+
+use web_sys::{AudioContext, OscillatorType};
+
+/// Short feedback cues, synthesised with WebAudio so no assets are needed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Sound {
+    /// A cell was revealed and the item was left out.
+    Step,
+    /// A cell was revealed and taking the item won.
+    Take,
+    /// The last cell was revealed.
+    Complete,
+}
+
+impl Sound {
+    /// `(frequency Hz, start offset s)` for each note of the cue.
+    fn notes(self) -> &'static [(f32, f64)] {
+        match self {
+            Sound::Step => &[(660.0, 0.0)],
+            Sound::Take => &[(880.0, 0.0)],
+            Sound::Complete => &[(523.0, 0.0), (659.0, 0.09), (784.0, 0.18)],
+        }
+    }
+}
+
+const NOTE_SECS: f64 = 0.08;
+const VOLUME: f32 = 0.05;
+
+thread_local! {
+    // Browsers cap the number of live contexts, so one is shared for the page
+    static CONTEXT: RefCell<Option<AudioContext>> = const { RefCell::new(None) };
+}
+
+/// Play `sound`. Silently does nothing where WebAudio is unavailable.
+pub fn play(sound: Sound) {
+    CONTEXT.with(|cell| {
+        let mut cell = cell.borrow_mut();
+        if cell.is_none() {
+            *cell = AudioContext::new().ok();
+        }
+        if let Some(ctx) = cell.as_ref() {
+            _ = play_notes(ctx, sound.notes());
+        }
+    });
+}
+
+fn play_notes(ctx: &AudioContext, notes: &[(f32, f64)]) -> Result<(), wasm_bindgen::JsValue> {
+    let now = ctx.current_time();
+    for &(freq, offset) in notes {
+        let start = now + offset;
+        let osc = ctx.create_oscillator()?;
+        let gain = ctx.create_gain()?;
+        osc.set_type(OscillatorType::Sine);
+        osc.frequency().set_value(freq);
+        // quick fade out so notes do not click
+        gain.gain().set_value_at_time(VOLUME, start)?;
+        gain.gain().exponential_ramp_to_value_at_time(0.0001, start + NOTE_SECS)?;
+        osc.connect_with_audio_node(&gain)?;
+        gain.connect_with_audio_node(&ctx.destination())?;
+        osc.start_with_when(start)?;
+        osc.stop_with_when(start + NOTE_SECS)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use leptos::prelude::*;
+
+/// Speak `text` with the Web Speech API, interrupting anything still being
+/// read. Silently does nothing where speech synthesis is unavailable.
+pub fn speak(text: &str, lang: &str) {
+    let Ok(synth) = window().speech_synthesis() else { return };
+    let Ok(utterance) = web_sys::SpeechSynthesisUtterance::new_with_text(text) else {
+        return;
+    };
+    utterance.set_lang(lang);
+    synth.cancel();
+    synth.speak(&utterance);
+}
+
+pub fn stop() {
+    if let Ok(synth) = window().speech_synthesis() {
+        synth.cancel();
+    }
+}
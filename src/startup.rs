@@ -0,0 +1,78 @@
+/// How soon after navigation the first table should be on screen.
+pub const FIRST_TABLE_BUDGET_MS: f64 = 1_000.0;
+
+/// A point in start-up worth timing, in the order they are reached.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mark {
+    /// The wasm module has loaded and `main` runs.
+    WasmInit,
+    /// The page's first view is built and attached.
+    Mounted,
+    /// The first instance is solved.
+    FirstSolve,
+    /// The frame after the first table is drawn.
+    FirstTable,
+}
+
+impl Mark {
+    pub const ALL: [Mark; 4] = [Mark::WasmInit, Mark::Mounted, Mark::FirstSolve, Mark::FirstTable];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Mark::WasmInit => "wasm-init",
+            Mark::Mounted => "mounted",
+            Mark::FirstSolve => "first-solve",
+            Mark::FirstTable => "first-table",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Mark> {
+        Mark::ALL.into_iter().find(|m| m.code() == code)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Mark::WasmInit => "wasm initialised",
+            Mark::Mounted => "first view mounted",
+            Mark::FirstSolve => "first solve",
+            Mark::FirstTable => "first table drawn",
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// When each mark was first reached, in milliseconds since navigation began.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct StartupTimings {
+    at: [Option<f64>; 4],
+}
+
+impl StartupTimings {
+    /// Note `mark` at `ms` unless it was reached before; true if noted.
+    pub fn record(&mut self, mark: Mark, ms: f64) -> bool {
+        let slot = &mut self.at[mark.index()];
+        let first = slot.is_none();
+        if first {
+            *slot = Some(ms);
+        }
+        first
+    }
+
+    pub fn at(&self, mark: Mark) -> Option<f64> {
+        self.at[mark.index()]
+    }
+
+    /// Time from the latest earlier mark reached (or navigation) to `mark`.
+    pub fn since_previous(&self, mark: Mark) -> Option<f64> {
+        let before = self.at[..mark.index()].iter().rev().find_map(|&t| t).unwrap_or(0.0);
+        self.at(mark).map(|t| t - before)
+    }
+
+    /// Whether the first table came within [`FIRST_TABLE_BUDGET_MS`], once drawn.
+    pub fn within_budget(&self) -> Option<bool> {
+        self.at(Mark::FirstTable).map(|t| t <= FIRST_TABLE_BUDGET_MS)
+    }
+}
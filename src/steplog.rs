@@ -0,0 +1,210 @@
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::format::NumberFormat;
+use crate::narration::{explain, narrate, narrate_hop};
+use crate::order::FillOrder;
+use crate::problem::{Problem, Units};
+use crate::schema::Step;
+use crate::settings::Settings;
+use crate::solver::{knapsack_table, trace_path, TraceHop};
+
+/// One line of an exported walkthrough. A log is a `Start` followed by the
+/// reveals in fill order, then `Complete` and the traceback hops.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum StepEvent {
+    Start {
+        #[serde(deserialize_with = "logged_problem")]
+        problem: Problem,
+        fill_order: FillOrder,
+    },
+    Reveal {
+        #[serde(flatten)]
+        step: Step,
+        narration: String,
+    },
+    /// The fill is done; `explanation` summarizes the answer.
+    Complete {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        explanation: Option<String>,
+    },
+    TraceHop { row: usize, col: usize, taken: bool, narration: String },
+}
+
+impl StepEvent {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            StepEvent::Start { .. } => "start",
+            StepEvent::Reveal { .. } => "reveal",
+            StepEvent::Complete { .. } => "complete",
+            StepEvent::TraceHop { .. } => "trace_hop",
+        }
+    }
+}
+
+/// The problem of a start event, as written now or, in logs recorded before
+/// items became records, as separate weight and benefit lists.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum LoggedProblem {
+    Items(Problem),
+    Lists {
+        capacity: usize,
+        weights: Vec<usize>,
+        benefits: Vec<usize>,
+        #[serde(default)]
+        units: Units,
+    },
+}
+
+fn logged_problem<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Problem, D::Error> {
+    match LoggedProblem::deserialize(deserializer)? {
+        LoggedProblem::Items(problem) => Ok(problem),
+        LoggedProblem::Lists { weights, benefits, .. } if weights.len() != benefits.len() => Err(serde::de::Error::custom(
+            format!("{} weights but {} benefits", weights.len(), benefits.len()),
+        )),
+        LoggedProblem::Lists { capacity, weights, benefits, units } => Ok(Problem { capacity, weights, benefits, units }),
+    }
+}
+
+/// Where a replayed log leaves the walkthrough.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Replay {
+    pub problem: Problem,
+    pub fill_order: FillOrder,
+    /// Data cells revealed; `None` once the fill is complete.
+    pub revealed: Option<usize>,
+    /// Traceback hops shown after completion.
+    pub traced: usize,
+}
+
+/// The events of a walkthrough that has revealed `revealed` cells (`None`
+/// when complete) and, after that, `traced` traceback hops.
+pub fn walkthrough(
+    table: &[Vec<usize>],
+    problem: &Problem,
+    order: FillOrder,
+    fmt: &NumberFormat,
+    revealed: Option<usize>,
+    traced: usize,
+) -> Vec<StepEvent> {
+    let (n, n_cols) = (problem.len(), problem.capacity + 1);
+    let total = n * n_cols;
+    let plan = order.plan().layout(n, n_cols);
+    let mut events = vec![StepEvent::Start { problem: problem.clone(), fill_order: order }];
+    events.extend((0..revealed.unwrap_or(total).min(total)).map(|ordinal| {
+        let (row, col) = plan.cell(ordinal);
+        StepEvent::Reveal {
+            step: Step { ordinal, row, col, value: table[row][col] },
+            narration: narrate(table, problem, row, col, fmt),
+        }
+    }));
+    if revealed.is_none() {
+        events.push(StepEvent::Complete { explanation: Some(explain(table, problem, fmt)) });
+        let hops = trace_path(table, &problem.weights, problem.capacity);
+        events.extend(hops.into_iter().take(traced).map(|hop| StepEvent::TraceHop {
+            row: hop.row,
+            col: hop.col,
+            taken: hop.taken,
+            narration: narrate_hop(table, problem, hop, fmt),
+        }));
+    }
+    events
+}
+
+pub fn to_jsonl(events: &[StepEvent]) -> String {
+    events
+        .iter()
+        .map(|e| serde_json::to_string(e).expect("events are always serializable") + "\n")
+        .collect()
+}
+
+/// Parse and check a log: every reveal and hop must be the one the solver
+/// produces next for the logged problem, so the replay is exact. The problem
+/// must fit `limits` before its table is built to check against.
+pub fn replay_jsonl(text: &str, limits: &Settings) -> Result<Replay, String> {
+    let mut events = text.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()).map(|(i, l)| {
+        serde_json::from_str::<StepEvent>(l).map_err(|e| format!("Step log line {}: {e}", i + 1))
+    });
+
+    let Some(StepEvent::Start { problem, fill_order }) = events.next().transpose()? else {
+        return Err("A step log must begin with a start event.".into());
+    };
+    limits.check_size(&problem)?;
+    if problem.len() > limits.max_visual_items {
+        return Err(format!(
+            "This log steps through {} items; the table is drawn for at most {}.",
+            problem.len(), limits.max_visual_items
+        ));
+    }
+    let table = knapsack_table(problem.capacity, &problem.weights, &problem.benefits);
+    let hops = trace_path(&table, &problem.weights, problem.capacity);
+    let (n, n_cols) = (problem.len(), problem.capacity + 1);
+    let plan = fill_order.plan().layout(n, n_cols);
+
+    let mut replay = Replay { problem, fill_order, revealed: Some(0), traced: 0 };
+    for event in events {
+        match (event?, replay.revealed) {
+            (StepEvent::Reveal { step: Step { ordinal, row, col, value }, .. }, Some(r)) => {
+                if ordinal != r || ordinal >= plan.len() || plan.cell(ordinal) != (row, col) {
+                    return Err(format!("Step {} reveals dp[{row}][{col}] out of order.", r + 1));
+                }
+                if table[row][col] != value {
+                    return Err(format!("Step {}: dp[{row}][{col}] should be {}, not {value}.", r + 1, table[row][col]));
+                }
+                replay.revealed = Some(r + 1);
+            }
+            (StepEvent::Complete { .. }, Some(r)) if r == n * n_cols => replay.revealed = None,
+            (StepEvent::Complete { .. }, Some(r)) => {
+                return Err(format!("Completed after {r} of {} cells.", n * n_cols));
+            }
+            (StepEvent::TraceHop { row, col, taken, .. }, None) => {
+                if hops.get(replay.traced) != Some(&TraceHop { row, col, taken }) {
+                    return Err(format!("Traceback hop {} does not match the table.", replay.traced + 1));
+                }
+                replay.traced += 1;
+            }
+            (e, _) => return Err(format!("Unexpected '{}' event at this point in the log.", e.kind())),
+        }
+    }
+    Ok(replay)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_start_events_logged_with_weight_and_benefit_lists() {
+        let problem = Problem { capacity: 5, weights: vec![2, 3], benefits: vec![3, 4], units: Units::default() };
+        let order = serde_json::to_string(&FillOrder::default()).unwrap();
+        let old = format!(
+            r#"{{"event":"start","problem":{{"capacity":5,"weights":[2,3],"benefits":[3,4]}},"fill_order":{order}}}"#
+        );
+        let event: StepEvent = serde_json::from_str(&old).unwrap();
+        assert_eq!(event, StepEvent::Start { problem, fill_order: FillOrder::default() });
+
+        let current = serde_json::to_string(&event).unwrap();
+        assert_eq!(serde_json::from_str::<StepEvent>(&current).unwrap(), event);
+    }
+
+    #[test]
+    fn rejects_lists_of_different_lengths() {
+        let order = serde_json::to_string(&FillOrder::default()).unwrap();
+        let old = format!(
+            r#"{{"event":"start","problem":{{"capacity":5,"weights":[2,3],"benefits":[3]}},"fill_order":{order}}}"#
+        );
+        assert!(serde_json::from_str::<StepEvent>(&old).is_err());
+    }
+
+    #[test]
+    fn replays_are_refused_when_the_benefits_overflow() {
+        let order = serde_json::to_string(&FillOrder::default()).unwrap();
+        let log = format!(
+            r#"{{"event":"start","problem":{{"capacity":5,"items":[{{"weight":1,"benefit":{}}},{{"weight":1,"benefit":1}}]}},"fill_order":{order}}}"#,
+            usize::MAX
+        );
+        let err = replay_jsonl(&log, &Settings::default()).map(|_| ()).unwrap_err();
+        assert!(err.contains("Benefits add up to more than"), "{err}");
+    }
+}
@@ -0,0 +1,129 @@
+//! Flags kept in the browser's local storage across visits, and a store for
+//! larger records.
+
+use leptos::prelude::*;
+
+use crate::analytics::USAGE_KEY;
+use crate::idb::IdbStore;
+use crate::store::{MemoryStore, StateStore};
+
+const VISITED_KEY: &str = "knapsack-dp.visited";
+
+fn local_storage() -> Option<web_sys::Storage> {
+    if is_server() {
+        return None;
+    }
+    window().local_storage().ok().flatten()
+}
+
+/// True until [`mark_visited`] runs in this browser. Without storage (e.g.
+/// blocked by privacy settings) no visit counts as the first, so the sample
+/// is not loaded over and over.
+pub fn is_first_visit() -> bool {
+    local_storage().is_some_and(|s| matches!(s.get_item(VISITED_KEY), Ok(None)))
+}
+
+pub fn mark_visited() {
+    if let Some(s) = local_storage() {
+        _ = s.set_item(VISITED_KEY, "1");
+    }
+}
+
+// ─── Records ─────────────────────────────────────────────────────────────────
+
+/// Local storage, for browsers that block IndexedDB.
+#[derive(Clone, Copy)]
+pub struct LocalStore;
+
+impl StateStore for LocalStore {
+    async fn get(&self, key: &str) -> Result<Option<String>, String> {
+        Ok(local_storage().and_then(|s| s.get_item(key).ok().flatten()))
+    }
+
+    async fn put(&self, key: &str, value: &str) -> Result<(), String> {
+        let s = local_storage().ok_or_else(|| "Local storage is not available.".to_string())?;
+        s.set_item(key, value).map_err(|_| "Local storage is full.".to_string())
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), String> {
+        if let Some(s) = local_storage() {
+            _ = s.remove_item(key);
+        }
+        Ok(())
+    }
+}
+
+/// The store [`open_store`] picked.
+#[derive(Clone)]
+pub enum Store {
+    Idb(IdbStore),
+    Local(LocalStore),
+    Memory(MemoryStore),
+}
+
+impl StateStore for Store {
+    async fn get(&self, key: &str) -> Result<Option<String>, String> {
+        match self {
+            Store::Idb(s) => s.get(key).await,
+            Store::Local(s) => s.get(key).await,
+            Store::Memory(s) => s.get(key).await,
+        }
+    }
+
+    async fn put(&self, key: &str, value: &str) -> Result<(), String> {
+        match self {
+            Store::Idb(s) => s.put(key, value).await,
+            Store::Local(s) => s.put(key, value).await,
+            Store::Memory(s) => s.put(key, value).await,
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), String> {
+        match self {
+            Store::Idb(s) => s.delete(key).await,
+            Store::Local(s) => s.delete(key).await,
+            Store::Memory(s) => s.delete(key).await,
+        }
+    }
+}
+
+/// IndexedDB where the browser allows it, else local storage, else memory
+/// (on the server, or with storage blocked). Records an earlier version left
+/// in local storage move to IndexedDB on the way.
+pub async fn open_store() -> Store {
+    if is_server() {
+        return Store::Memory(MemoryStore::default());
+    }
+    let Ok(idb) = IdbStore::open().await else {
+        return match local_storage() {
+            Some(_) => Store::Local(LocalStore),
+            None => Store::Memory(MemoryStore::default()),
+        };
+    };
+    for key in MOVED_KEYS {
+        if let Ok(Some(value)) = LocalStore.get(key).await
+            && idb.put(key, &value).await.is_ok()
+        {
+            _ = LocalStore.delete(key).await;
+        }
+    }
+    Store::Idb(idb)
+}
+
+// ─── Usage dashboard ─────────────────────────────────────────────────────────
+
+const ANALYTICS_KEY: &str = "knapsack-dp.analytics";
+
+/// Records once kept in local storage that now belong in [`open_store`].
+const MOVED_KEYS: [&str; 1] = [USAGE_KEY];
+
+/// Whether the user turned on the usage dashboard; off until they do.
+pub fn analytics_enabled() -> bool {
+    local_storage().is_some_and(|s| matches!(s.get_item(ANALYTICS_KEY), Ok(Some(v)) if v == "1"))
+}
+
+pub fn set_analytics_enabled(on: bool) {
+    if let Some(s) = local_storage() {
+        _ = if on { s.set_item(ANALYTICS_KEY, "1") } else { s.remove_item(ANALYTICS_KEY) };
+    }
+}
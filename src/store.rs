@@ -0,0 +1,93 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::rc::Rc;
+
+/// A place that keeps string records by key across page loads. The browser
+/// keeps them in IndexedDB or local storage; [`MemoryStore`] only for as long
+/// as it lives, which is what the server and code without a browser get.
+///
+/// Calls may wait on the backend, so they are async even where it is not.
+pub trait StateStore {
+    fn get(&self, key: &str) -> impl Future<Output = Result<Option<String>, String>>;
+    fn put(&self, key: &str, value: &str) -> impl Future<Output = Result<(), String>>;
+    fn delete(&self, key: &str) -> impl Future<Output = Result<(), String>>;
+}
+
+/// Records in memory. Clones share the same records.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryStore {
+    records: Rc<RefCell<BTreeMap<String, String>>>,
+}
+
+impl MemoryStore {
+    pub fn len(&self) -> usize {
+        self.records.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.borrow().is_empty()
+    }
+}
+
+impl StateStore for MemoryStore {
+    async fn get(&self, key: &str) -> Result<Option<String>, String> {
+        Ok(self.records.borrow().get(key).cloned())
+    }
+
+    async fn put(&self, key: &str, value: &str) -> Result<(), String> {
+        self.records.borrow_mut().insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), String> {
+        self.records.borrow_mut().remove(key);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::*;
+    use crate::analytics::{self, Usage, UsageEvent};
+
+    #[test]
+    fn keeps_records_until_deleted() {
+        let store = MemoryStore::default();
+        block_on(async {
+            assert_eq!(store.get("a").await, Ok(None));
+            store.put("a", "1").await.unwrap();
+            store.put("a", "2").await.unwrap();
+            assert_eq!(store.get("a").await, Ok(Some("2".to_string())));
+            assert_eq!(store.len(), 1);
+            store.delete("a").await.unwrap();
+            store.delete("a").await.unwrap();
+            assert!(store.is_empty());
+        });
+    }
+
+    #[test]
+    fn clones_share_their_records() {
+        let store = MemoryStore::default();
+        let other = store.clone();
+        block_on(store.put("k", "v")).unwrap();
+        assert_eq!(block_on(other.get("k")), Ok(Some("v".to_string())));
+    }
+
+    #[test]
+    fn usage_history_round_trips_through_a_store() {
+        let store = MemoryStore::default();
+        let mut usage = Usage::default();
+        usage.record(20_000, UsageEvent::Solved("table DP".into()));
+        usage.record(20_001, UsageEvent::Steps(12));
+        block_on(async {
+            assert_eq!(analytics::load(&store).await, Usage::default());
+            analytics::save(&store, &usage).await.unwrap();
+            assert_eq!(analytics::load(&store).await, usage);
+            analytics::clear(&store).await.unwrap();
+            assert_eq!(analytics::load(&store).await, Usage::default());
+        });
+    }
+}
@@ -0,0 +1,55 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use futures::channel::mpsc::UnboundedSender;
+use leptos::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+
+use crate::solver::{RowSolver, SolveProgress};
+
+/// Roughly how many cells to compute before yielding back to the browser.
+const CHUNK_CELLS: usize = 200_000;
+
+/// Solves with more cells than this are run in chunks with live progress.
+pub const STREAM_THRESHOLD_CELLS: usize = 250_000;
+
+/// Drive `solver` to completion a chunk of rows at a time, yielding to the
+/// event loop between chunks and sending the rows completed after each one.
+///
+/// `cancel` is checked before every row; once it is set the partial work is
+/// dropped and `None` is returned.
+pub async fn solve_streaming<S: RowSolver>(
+    mut solver: S,
+    cols: usize,
+    progress: UnboundedSender<SolveProgress>,
+    cancel: &AtomicBool,
+) -> Option<S::Output> {
+    let rows_per_chunk = (CHUNK_CELLS / cols.max(1)).max(1);
+    loop {
+        let mut p = SolveProgress::default();
+        for _ in 0..rows_per_chunk {
+            if cancel.load(Ordering::Relaxed) {
+                return None;
+            }
+            p = solver.fill_rows(1);
+            if p.is_done() {
+                break;
+            }
+        }
+        // the receiver going away only means nobody is watching any more
+        _ = progress.unbounded_send(p);
+        if p.is_done() {
+            break;
+        }
+        next_tick().await;
+    }
+    Some(solver.finish())
+}
+
+/// Resolve on the next macrotask, letting the browser paint in between.
+async fn next_tick() {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        _ = window().set_timeout_with_callback(resolve.unchecked_ref());
+    });
+    _ = JsFuture::from(promise).await;
+}
@@ -0,0 +1,61 @@
+/// Colours and fonts an embedding site can override, each mapped to the CSS
+/// custom property the stylesheet reads. Unset fields keep the value of the
+/// colour scheme chosen in the settings ([`crate::display::Theme`]); any
+/// CSS value works, e.g. `"#0a7"`, `"rgb(0 120 90)"` or `"var(--brand)"`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Theme {
+    /// `--accent`: buttons, the progress bar and the active cell.
+    pub accent: Option<String>,
+    /// `--accent2`: the secondary highlight (the formula's active case).
+    pub accent2: Option<String>,
+    /// `--bg`: page background.
+    pub background: Option<String>,
+    /// `--surface`: cards and panels.
+    pub surface: Option<String>,
+    /// `--border`: card, field and cell borders.
+    pub border: Option<String>,
+    /// `--text`: body text.
+    pub text: Option<String>,
+    /// `--text-dim`: hints and secondary text.
+    pub text_dim: Option<String>,
+    /// `--cell-active`: the cell revealed last while stepping.
+    pub cell_active: Option<String>,
+    /// `--cell-took`: cells whose item is taken.
+    pub cell_took: Option<String>,
+    /// `--cell-path`: cells on the traceback path.
+    pub cell_path: Option<String>,
+    /// `--cell-size`: width and height of a table cell.
+    pub cell_size: Option<String>,
+    /// `--font-mono`: the font of the whole visualizer.
+    pub font_family: Option<String>,
+}
+
+impl Theme {
+    /// `(property, value)` for every field that is set. Values that could
+    /// end the declaration (`;`, braces) are dropped.
+    pub fn css_vars(&self) -> Vec<(&'static str, &str)> {
+        [
+            ("--accent", &self.accent),
+            ("--accent2", &self.accent2),
+            ("--bg", &self.background),
+            ("--surface", &self.surface),
+            ("--border", &self.border),
+            ("--text", &self.text),
+            ("--text-dim", &self.text_dim),
+            ("--cell-active", &self.cell_active),
+            ("--cell-took", &self.cell_took),
+            ("--cell-path", &self.cell_path),
+            ("--cell-size", &self.cell_size),
+            ("--font-mono", &self.font_family),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| value.as_deref().map(|v| (name, v.trim())))
+        .filter(|(_, v)| !v.is_empty() && !v.contains([';', '{', '}']))
+        .collect()
+    }
+
+    /// The overrides as an inline `style` value, empty when nothing is set.
+    pub fn to_style(&self) -> String {
+        self.css_vars().into_iter().map(|(name, value)| format!("{name}: {value};")).collect::<Vec<_>>().join(" ")
+    }
+}
@@ -0,0 +1,45 @@
+use std::cell::Cell;
+
+use leptos::prelude::*;
+
+use crate::startup::{Mark, StartupTimings};
+
+thread_local! {
+    static STARTUP: Cell<StartupTimings> = Cell::new(StartupTimings::default());
+}
+
+/// High-resolution timestamp in milliseconds, from `performance.now()`.
+pub fn now_ms() -> f64 {
+    window().performance().map(|p| p.now()).unwrap_or_default()
+}
+
+/// Note that start-up reached `mark`, the first time only. It also goes to
+/// the browser's performance timeline, where dev tools show it.
+pub fn mark_startup(mark: Mark) {
+    if is_server() {
+        return;
+    }
+    let now = now_ms();
+    STARTUP.with(|s| {
+        let mut timings = s.get();
+        if timings.record(mark, now) {
+            s.set(timings);
+            if let Some(p) = window().performance() {
+                _ = p.mark(&format!("knapsack-dp:{}", mark.code()));
+            }
+        }
+    });
+}
+
+/// Call `frame` with the time of each animation frame until it returns false.
+pub fn each_frame(mut frame: impl FnMut(f64) -> bool + 'static) {
+    request_animation_frame(move || {
+        if frame(now_ms()) {
+            each_frame(frame);
+        }
+    });
+}
+
+pub fn startup_timings() -> StartupTimings {
+    STARTUP.with(Cell::get)
+}
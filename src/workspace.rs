@@ -0,0 +1,429 @@
+use std::collections::HashMap;
+
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use leptos_router::hooks::use_query_map;
+
+use crate::assignment::{self, ASSIGNMENT_PARAM};
+use crate::components::assignment::{AssignmentView, VerifyCodePanel};
+use crate::components::metrics::{provide_metrics, MetricsPane};
+use crate::components::onboarding::WelcomeCallout;
+use crate::components::startup::StartupPanel;
+use crate::components::usage::{provide_usage, UsageDashboard};
+use crate::diff::diff;
+use crate::files::{copy_text, download_text};
+use crate::io::{CellNotes, KnapFile, StepPosition};
+use crate::knapsack::KnapsackVisualizer;
+use crate::playlist::{self, PLAYLIST_PARAM};
+use crate::presets::PRESETS;
+use crate::problem::Problem;
+use crate::settings::Settings;
+use crate::storage;
+
+/// Cells already revealed in the sample shown on a first visit.
+const ONBOARDING_STEP: usize = 5;
+/// Query parameter that shows the start-up timings panel.
+const DEBUG_PARAM: &str = "debug";
+
+/// One open problem. `seed` is the state a duplicated tab starts from.
+#[derive(Clone)]
+struct Tab {
+    id: usize,
+    seed: Option<KnapFile>,
+}
+
+// ─── Linked stepping ─────────────────────────────────────────────────────────
+
+/// A stepper action one visualizer repeats for the others it is linked to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LinkedStep {
+    Forward,
+    Back,
+    /// Play or pause the traceback animation.
+    TracePlay,
+}
+
+/// Shared by every visualizer in the workspace. While linking is on, a step
+/// taken in one member is replayed by the other members.
+#[derive(Clone, Copy)]
+pub struct StepLink {
+    /// Instances currently linked; empty when linking is off.
+    members: Memo<Vec<usize>>,
+    /// Latest step and the instance it came from.
+    last: RwSignal<Option<(usize, LinkedStep)>>,
+    /// Each instance's problem: the solved one, else the form's.
+    problems: RwSignal<HashMap<usize, Option<Problem>>>,
+}
+
+impl StepLink {
+    pub fn send(&self, from: usize, step: LinkedStep) {
+        if self.members.with_untracked(|m| m.contains(&from)) {
+            self.last.set(Some((from, step)));
+        }
+    }
+
+    /// Run `f` for every step another linked member sends while `me` is linked.
+    pub fn on_step(&self, me: usize, f: impl Fn(LinkedStep) + 'static) {
+        let (members, last) = (self.members, self.last);
+        // the first run only subscribes: a newly opened tab must not replay
+        // whatever was sent before it existed
+        Effect::new(move |subscribed: Option<()>| {
+            let latest = last.get();
+            if subscribed.is_some()
+                && let Some((from, step)) = latest
+                && from != me
+                && members.with_untracked(|m| m.contains(&me))
+            {
+                // the step reads the member's own state; only `last` drives this
+                untrack(|| f(step));
+            }
+        });
+    }
+
+    /// Keep this instance's problem up to date until it unmounts.
+    pub fn report_problem(&self, me: usize, problem: Signal<Option<Problem>>) {
+        let problems = self.problems;
+        Effect::new(move |_| {
+            let problem = problem.get();
+            problems.update(|p| _ = p.insert(me, problem));
+        });
+        on_cleanup(move || problems.update(|p| _ = p.remove(&me)));
+    }
+}
+
+// ─── Workspace ───────────────────────────────────────────────────────────────
+
+/// Several problems open at once as tabs. Each tab is a full visualizer
+/// with its own table, stepper and settings; hidden tabs stay mounted, so
+/// switching back finds everything where it was left.
+#[component]
+pub fn Workspace() -> impl IntoView {
+    provide_usage();
+    provide_metrics();
+    let query = use_query_map();
+    // An `?assignment=` link shows only that instance, to be answered
+    if let Some(text) = query.with_untracked(|q| q.get(ASSIGNMENT_PARAM)) {
+        return view! {
+            <div class="page">
+                {match assignment::decode(&text) {
+                    Ok(problem) => view! { <AssignmentView problem /> }.into_any(),
+                    Err(e) => view! { <p class="error">"⚠  Invalid assignment link: "{e}</p> }.into_any(),
+                }}
+            </div>
+        }
+        .into_any();
+    }
+    // A `?playlist=` link opens its first instance in the first tab
+    let (playlist, playlist_error) = match query.with_untracked(|q| q.get(PLAYLIST_PARAM)).map(|t| playlist::decode(&t)) {
+        Some(Ok(problems)) => (problems, None),
+        Some(Err(e)) => (Vec::new(), Some(e)),
+        None => (Vec::new(), None),
+    };
+    let debug = query.with_untracked(|q| q.get(DEBUG_PARAM).is_some());
+    let unsolved = |p: &Problem| KnapFile::new(p.clone(), Settings::default(), StepPosition::Unsolved, &CellNotes::new());
+    let sample = || {
+        KnapFile::new(PRESETS[0].problem(), Settings::default(), StepPosition::Stepping(ONBOARDING_STEP), &CellNotes::new())
+    };
+
+    // A first visit opens on a solved-in-part sample instead of a blank form.
+    // The client-side app seeds it into the first tab, so only one table is
+    // built before the first paint; a hydrated page must match the server's,
+    // so it swaps the sample in after mounting instead.
+    let has_playlist = !playlist.is_empty();
+    let swap_in_sample = cfg!(feature = "hydrate");
+    let first_visit = !swap_in_sample && !has_playlist && storage::is_first_visit();
+    if first_visit {
+        storage::mark_visited();
+    }
+    let seed = if first_visit { Some(sample()) } else { playlist.first().map(unsolved) };
+    let tabs = RwSignal::new(vec![Tab { id: 0, seed }]);
+    let (active, set_active) = signal(0usize);
+    // the tab shown last before the active one, for side-by-side view
+    let previous = RwSignal::new(None::<usize>);
+    let next_id = StoredValue::new(1usize);
+    let (split, set_split) = signal(false);
+    let (linking, set_linking) = signal(false);
+
+    let select = move |id: usize| {
+        let current = active.get_untracked();
+        if current != id {
+            previous.set(Some(current));
+            set_active.set(id);
+        }
+    };
+    let open_tab = move |seed: Option<KnapFile>| {
+        let id = next_id.get_value();
+        next_id.set_value(id + 1);
+        tabs.update(|t| t.push(Tab { id, seed }));
+        select(id);
+    };
+    let close_tab = move |id: usize| {
+        tabs.update(|t| t.retain(|tab| tab.id != id));
+        if previous.get_untracked() == Some(id) {
+            previous.set(None);
+        }
+        if active.get_untracked() == id
+            && let Some(last) = tabs.with_untracked(|t| t.last().map(|tab| tab.id))
+        {
+            set_active.set(last);
+        }
+    };
+    let on_duplicate = Callback::new(move |file: KnapFile| open_tab(Some(file)));
+
+    let (welcome, set_welcome) = signal(first_visit);
+    Effect::new(move |_| {
+        if !swap_in_sample || has_playlist || !storage::is_first_visit() {
+            return;
+        }
+        storage::mark_visited();
+        let id = next_id.get_value();
+        next_id.set_value(id + 1);
+        tabs.set(vec![Tab { id, seed: Some(sample()) }]);
+        set_active.set(id);
+        set_welcome.set(true);
+    });
+
+    // The second tab on screen in side-by-side view
+    let partner = Memo::new(move |_| {
+        if !split.get() {
+            return None;
+        }
+        let active = active.get();
+        tabs.with(|t| {
+            let open = |id: &usize| *id != active && t.iter().any(|tab| tab.id == *id);
+            previous.get().filter(open).or_else(|| t.iter().map(|tab| tab.id).find(|id| open(id)))
+        })
+    });
+
+    let problems = RwSignal::new(HashMap::new());
+    let problem_of = move |id: usize| problems.with(|p| p.get(&id).cloned().flatten());
+    // Linking needs two tables of the same shape side by side
+    let same_size = Memo::new(move |_| {
+        let Some(other) = partner.get() else { return false };
+        let size = |id| problem_of(id).map(|p: Problem| (p.len(), p.capacity));
+        size(active.get()).is_some() && size(active.get()) == size(other)
+    });
+    let members = Memo::new(move |_| match partner.get() {
+        Some(other) if linking.get() && same_size.get() => vec![active.get(), other],
+        _ => Vec::new(),
+    });
+    let link = StepLink { members, last: RwSignal::new(None), problems };
+
+    // Markdown diff of the two tabs on screen, from the previous one to the active one
+    let (report, set_report) = signal(None::<String>);
+    let on_diff = move |_| {
+        let Some(other) = partner.get_untracked() else { return };
+        let current = active.get_untracked();
+        let md = match (problem_of(other), problem_of(current)) {
+            (Some(a), Some(b)) => {
+                diff(&a, &b).to_markdown(&format!("Problem {}", other + 1), &format!("Problem {}", current + 1))
+            }
+            _ => "Both tabs need a valid instance to compare.".to_string(),
+        };
+        set_report.set(Some(md));
+    };
+    let download_report = move |_| {
+        if let Some(md) = report.get_untracked() {
+            _ = download_text("knapsack-diff.md", "text/markdown", &md);
+        }
+    };
+
+    // ── Playlist ─────────────────────────────────────────────────────────────
+    // The playlist plays in the tab it opened in; moving on remounts that tab
+    let playlist = StoredValue::new(playlist);
+    let playlist_len = playlist.with_value(Vec::len);
+    let (track, set_track) = signal(0usize);
+    let playlist_tab = StoredValue::new(0usize);
+    let play = move |k: usize| {
+        let Some(seed) = playlist.with_value(|p| p.get(k).map(unsolved)) else { return };
+        let id = next_id.get_value();
+        next_id.set_value(id + 1);
+        let old = playlist_tab.get_value();
+        tabs.update(|t| match t.iter_mut().find(|tab| tab.id == old) {
+            Some(tab) => *tab = Tab { id, seed: Some(seed) },
+            None => t.push(Tab { id, seed: Some(seed) }),
+        });
+        if previous.get_untracked() == Some(old) {
+            previous.set(None);
+        }
+        playlist_tab.set_value(id);
+        set_active.set(id);
+        set_track.set(k);
+    };
+
+    // Links to this page carrying instances; the link itself is shown
+    // when the clipboard refuses it
+    let (share_msg, set_share_msg) = signal(None::<String>);
+    let share = move |param: &'static str, problems: Vec<Problem>, what: &'static str| {
+        if problems.is_empty() {
+            set_share_msg.set(Some("No tab holds a valid instance yet.".into()));
+            return;
+        }
+        let location = window().location();
+        let url = format!(
+            "{}{}?{param}={}",
+            location.origin().unwrap_or_default(),
+            location.pathname().unwrap_or_default(),
+            playlist::encode(&problems)
+        );
+        spawn_local(async move {
+            set_share_msg.set(Some(match copy_text(&url).await {
+                Ok(()) => format!("{what} copied."),
+                Err(_) => url,
+            }));
+        });
+    };
+    // Every open tab's instance as one playlist
+    let on_share = move |_| {
+        let problems: Vec<Problem> = tabs.with_untracked(|t| t.iter().filter_map(|tab| problem_of(tab.id)).collect());
+        share(PLAYLIST_PARAM, problems, "Playlist link");
+    };
+    // The active tab's instance as homework
+    let on_assign = move |_| {
+        share(ASSIGNMENT_PARAM, problem_of(active.get_untracked()).into_iter().collect(), "Assignment link");
+    };
+
+    let (verifying, set_verifying) = signal(false);
+
+    view! {
+        <div class="workspace" class:split=move || partner.with(Option::is_some)>
+            {playlist_error.map(|e| view! { <p class="error">"⚠  "{e}</p> })}
+            {(playlist_len > 0).then(|| view! {
+                <nav class="playlist-bar" aria-label="Playlist">
+                    <button
+                        class="btn-small"
+                        disabled=move || track.get() == 0
+                        on:click=move |_| play(track.get_untracked().saturating_sub(1))
+                    >
+                        "◀ Previous"
+                    </button>
+                    <span class="playlist-pos">
+                        {move || format!("Instance {} of {playlist_len}", track.get() + 1)}
+                    </span>
+                    <button
+                        class="btn-small"
+                        disabled=move || track.get() + 1 >= playlist_len
+                        on:click=move |_| play(track.get_untracked() + 1)
+                    >
+                        "Next ▶"
+                    </button>
+                </nav>
+            })}
+            <nav class="tab-bar" role="tablist" aria-label="Open problems">
+                <For each=move || tabs.get() key=|tab| tab.id let:tab>
+                    {
+                        let id = tab.id;
+                        view! {
+                            <div class="tab" class:active=move || active.get() == id>
+                                <button
+                                    class="tab-label"
+                                    role="tab"
+                                    aria-selected=move || (active.get() == id).to_string()
+                                    on:click=move |_| select(id)
+                                >
+                                    {format!("Problem {}", id + 1)}
+                                </button>
+                                <Show when=move || tabs.with(|t| t.len() > 1)>
+                                    <button
+                                        class="tab-close"
+                                        aria-label=format!("Close problem {}", id + 1)
+                                        on:click=move |_| close_tab(id)
+                                    >
+                                        "×"
+                                    </button>
+                                </Show>
+                            </div>
+                        }
+                    }
+                </For>
+                <button class="tab-add" title="Open another problem" on:click=move |_| open_tab(None)>
+                    "+"
+                </button>
+                <Show when=move || tabs.with(|t| t.len() > 1)>
+                    <label class="tab-toggle" title="Show this tab next to the one viewed before it">
+                        <input
+                            type="checkbox"
+                            prop:checked=split
+                            on:change:target=move |ev| set_split.set(ev.target().checked())
+                        />
+                        "Side by side"
+                    </label>
+                </Show>
+                <Show when=move || partner.with(Option::is_some)>
+                    <label
+                        class="tab-toggle"
+                        title=move || if same_size.get() {
+                            "Next, Back and Play act on both tables"
+                        } else {
+                            "Both instances need the same number of items and capacity"
+                        }
+                    >
+                        <input
+                            type="checkbox"
+                            prop:checked=linking
+                            disabled=move || !same_size.get()
+                            on:change:target=move |ev| set_linking.set(ev.target().checked())
+                        />
+                        "Link steppers"
+                    </label>
+                    <button class="tab-add" title="Compare the two instances on screen" on:click=on_diff>
+                        "Diff report"
+                    </button>
+                </Show>
+                <button class="tab-add" title="Copy a link that opens every tab's instance as a playlist" on:click=on_share>
+                    "Share as playlist"
+                </button>
+                <button class="tab-add" title="Copy a link that asks for this tab's optimum without showing the table" on:click=on_assign>
+                    "Assign"
+                </button>
+                <button
+                    class="tab-add"
+                    class:active=verifying
+                    title="Check a student's result code against this tab's instance"
+                    on:click=move |_| set_verifying.update(|v| *v = !*v)
+                >
+                    "Verify code"
+                </button>
+                {move || share_msg.get().map(|m| view! { <span class="toolbar-hint share-msg">{m}</span> })}
+            </nav>
+            {move || report.get().map(|md| view! {
+                <section class="diff-report">
+                    <pre>{md}</pre>
+                    <div class="btn-row">
+                        <button class="btn-small" on:click=download_report>"Download .md"</button>
+                        <button class="btn-small" on:click=move |_| set_report.set(None)>"Close"</button>
+                    </div>
+                </section>
+            })}
+            <Show when=move || verifying.get()>
+                <VerifyCodePanel problem=Signal::derive(move || problem_of(active.get())) />
+            </Show>
+            <Show when=move || welcome.get()>
+                <WelcomeCallout on_dismiss=Callback::new(move |_| set_welcome.set(false)) />
+            </Show>
+            <div class="tab-panels">
+                <For each=move || tabs.get() key=|tab| tab.id let:tab>
+                    {
+                        let id = tab.id;
+                        let shown = move || active.get() == id || partner.get() == Some(id);
+                        view! {
+                            <div class="tab-panel" role="tabpanel" hidden=move || !shown()>
+                                <KnapsackVisualizer
+                                    instance=id
+                                    active=Signal::derive(move || active.get() == id)
+                                    seed=tab.seed
+                                    on_duplicate
+                                    link
+                                />
+                            </div>
+                        }
+                    }
+                </For>
+            </div>
+            <MetricsPane />
+            {debug.then(|| view! { <StartupPanel /> })}
+            <UsageDashboard />
+        </div>
+    }
+    .into_any()
+}
@@ -0,0 +1,206 @@
+use crate::format::NumberFormat;
+use crate::narration::explain;
+use crate::problem::Problem;
+use crate::solver::{chosen_items, trace_path, TraceHop};
+
+/// Everything a solution write-up states about a solved instance, gathered
+/// once so the Markdown and the LaTeX exports say exactly the same thing.
+#[derive(Clone, Debug)]
+pub struct WriteUp {
+    pub problem: Problem,
+    pub table: Vec<Vec<usize>>,
+    /// Cells visited by the traceback, from `dp[n][m]` up to row 1.
+    pub path: Vec<TraceHop>,
+    /// 1-based items in the optimum, in item order.
+    pub chosen: Vec<usize>,
+    pub best: usize,
+    pub used: usize,
+    pub explanation: String,
+    pub fmt: NumberFormat,
+}
+
+impl WriteUp {
+    pub fn new(table: &[Vec<usize>], problem: &Problem, fmt: &NumberFormat) -> Self {
+        let (n, m) = (problem.len(), problem.capacity);
+        let chosen = chosen_items(table, &problem.weights, m);
+        WriteUp {
+            problem: problem.clone(),
+            table: table.to_vec(),
+            path: trace_path(table, &problem.weights, m),
+            chosen: chosen.clone(),
+            best: table[n][m],
+            used: chosen.iter().map(|&i| problem.weights[i - 1]).sum(),
+            explanation: explain(table, problem, fmt),
+            fmt: *fmt,
+        }
+    }
+
+    /// Whether `(row, col)` is on the traceback, and if so whether it took
+    /// its item.
+    fn hop(&self, row: usize, col: usize) -> Option<bool> {
+        self.path.iter().find(|h| h.row == row && h.col == col).map(|h| h.taken)
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let p = &self.problem;
+        let units = &p.units;
+        let mut md = format!(
+            "# 0/1 knapsack solution\n\n## Problem\n\n\
+             Choose a subset of the {} items below, each at most once, whose total weight \
+             is at most {} and whose total benefit is as large as possible.\n\n\
+             | Item | Weight | Benefit |\n|---:|---:|---:|\n",
+            p.len(),
+            units.weight(p.capacity),
+        );
+        for i in 1..=p.len() {
+            md.push_str(&format!(
+                "| {i} | {} | {} |\n",
+                units.weight(p.weights[i - 1]),
+                units.benefit(p.benefits[i - 1], &self.fmt)
+            ));
+        }
+        md.push_str(
+            "\n## Recurrence\n\n\
+             - `dp[i][w] = 0` if `i = 0` or `w = 0`\n\
+             - `dp[i][w] = dp[i-1][w]` if `wt_i > w`\n\
+             - `dp[i][w] = max(dp[i-1][w], dp[i-1][w - wt_i] + b_i)` if `wt_i ≤ w`\n\n\
+             ## Table\n\n\
+             Cells on the traceback are in bold; a ★ marks the ones that took their item.\n\n",
+        );
+        md.push_str(&format!(
+            "| i \\ w | {} |\n|---|{}\n",
+            (0..=p.capacity).map(|w| w.to_string()).collect::<Vec<_>>().join(" | "),
+            "---:|".repeat(p.capacity + 1)
+        ));
+        for (i, row) in self.table.iter().enumerate() {
+            let cells = row.iter().enumerate().map(|(c, &v)| {
+                let v = self.fmt.format(v);
+                match self.hop(i, c) {
+                    Some(true) => format!("**{v}★**"),
+                    Some(false) => format!("**{v}**"),
+                    None => v,
+                }
+            });
+            md.push_str(&format!("| {i} | {} |\n", cells.collect::<Vec<_>>().join(" | ")));
+        }
+        md.push_str("\n## Answer\n\n");
+        for &i in &self.chosen {
+            md.push_str(&format!(
+                "- Item {i}: weight {}, benefit {}\n",
+                units.weight(p.weights[i - 1]),
+                units.benefit(p.benefits[i - 1], &self.fmt)
+            ));
+        }
+        md.push_str(&format!(
+            "{}Total weight {} of {}, total benefit {}.\n\n{}\n",
+            if self.chosen.is_empty() { "" } else { "\n" },
+            units.weight(self.used),
+            units.weight(p.capacity),
+            units.benefit(self.best, &self.fmt),
+            self.explanation,
+        ));
+        md
+    }
+
+    /// A standalone LaTeX document; it needs only `amsmath`, `graphicx` and
+    /// `xcolor` with its `table` option.
+    pub fn to_latex(&self) -> String {
+        let p = &self.problem;
+        let units = &p.units;
+        let weight = |v: usize| escape_latex(&units.weight(v));
+        let benefit = |v: usize| escape_latex(&units.benefit(v, &self.fmt));
+
+        let mut tex = String::from(
+            "\\documentclass{article}\n\
+             \\usepackage[utf8]{inputenc}\n\
+             \\usepackage{amsmath}\n\
+             \\usepackage{graphicx}\n\
+             \\usepackage[table]{xcolor}\n\
+             \\definecolor{taken}{HTML}{4AE8B0}\n\
+             \\definecolor{visited}{HTML}{D6F8EC}\n\n\
+             \\title{0/1 knapsack solution}\n\\date{}\n\n\
+             \\begin{document}\n\\maketitle\n\n\
+             \\section*{Problem}\n\n",
+        );
+        tex.push_str(&format!(
+            "Choose a subset of the {} items below, each at most once, whose total weight \
+             is at most {} and whose total benefit is as large as possible.\n\n\
+             \\begin{{center}}\n\\begin{{tabular}}{{rrr}}\n\\hline\n\
+             Item & Weight & Benefit \\\\\n\\hline\n",
+            p.len(),
+            weight(p.capacity),
+        ));
+        for i in 1..=p.len() {
+            tex.push_str(&format!("{i} & {} & {} \\\\\n", weight(p.weights[i - 1]), benefit(p.benefits[i - 1])));
+        }
+        tex.push_str(
+            "\\hline\n\\end{tabular}\n\\end{center}\n\n\
+             \\section*{Recurrence}\n\n\
+             \\[\n\
+             dp[i][w] = \\begin{cases}\n\
+             \x20 0 & \\text{if } i = 0 \\text{ or } w = 0 \\\\\n\
+             \x20 dp[i-1][w] & \\text{if } wt_i > w \\\\\n\
+             \x20 \\max\\bigl(dp[i-1][w],\\ dp[i-1][w - wt_i] + b_i\\bigr) & \\text{if } wt_i \\leq w\n\
+             \\end{cases}\n\
+             \\]\n\n\
+             \\section*{Table}\n\n\
+             Shaded cells are on the traceback from $dp[n][m]$; the darker ones took their item.\n\n\
+             \\begin{center}\n\\resizebox{\\ifdim\\width>\\linewidth\\linewidth\\else\\width\\fi}{!}{%\n",
+        );
+        tex.push_str(&format!(
+            "\\begin{{tabular}}{{r|{}}}\n$i \\backslash w$ & {} \\\\\n\\hline\n",
+            "r".repeat(p.capacity + 1),
+            (0..=p.capacity).map(|w| w.to_string()).collect::<Vec<_>>().join(" & ")
+        ));
+        for (i, row) in self.table.iter().enumerate() {
+            let cells = row.iter().enumerate().map(|(c, &v)| {
+                let v = escape_latex(&self.fmt.format(v));
+                match self.hop(i, c) {
+                    Some(true) => format!("\\cellcolor{{taken}}\\textbf{{{v}}}"),
+                    Some(false) => format!("\\cellcolor{{visited}}{v}"),
+                    None => v,
+                }
+            });
+            tex.push_str(&format!("{i} & {} \\\\\n", cells.collect::<Vec<_>>().join(" & ")));
+        }
+        tex.push_str("\\end{tabular}}\n\\end{center}\n\n\\section*{Answer}\n\n");
+        if !self.chosen.is_empty() {
+            tex.push_str("\\begin{itemize}\n");
+            for &i in &self.chosen {
+                tex.push_str(&format!(
+                    "  \\item Item {i}: weight {}, benefit {}\n",
+                    weight(p.weights[i - 1]),
+                    benefit(p.benefits[i - 1])
+                ));
+            }
+            tex.push_str("\\end{itemize}\n\n");
+        }
+        tex.push_str(&format!(
+            "Total weight {} of {}, total benefit \\textbf{{{}}}.\n\n{}\n\n\\end{{document}}\n",
+            weight(self.used),
+            weight(p.capacity),
+            benefit(self.best),
+            escape_latex(&self.explanation),
+        ));
+        tex
+    }
+}
+
+/// `text` with LaTeX's special characters escaped, for unit symbols and
+/// prose that end up in running text.
+pub fn escape_latex(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '\\' => out.push_str("\\textbackslash{}"),
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                out.push('\\');
+                out.push(ch);
+            }
+            '~' => out.push_str("\\textasciitilde{}"),
+            '^' => out.push_str("\\textasciicircum{}"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}